@@ -229,7 +229,7 @@ fn dev_genesis(
 		dynamic_fee: Default::default(),
 		base_fee: Default::default(),
 		upfront_pool: Default::default(),
-		staking_pool: StakingPoolConfig {},
+		staking_pool: StakingPoolConfig { max_player: 1_000 },
 		faucet: FaucetConfig {
 			genesis_accounts: vec![
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
@@ -4,6 +4,8 @@ use jsonrpsee::RpcModule;
 use std::{collections::BTreeMap, sync::Arc};
 // Substrate
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+// Gafi
+use pallet_pool_rpc::{GafiPool, GafiPoolApiServer};
 use sc_client_api::{
 	backend::{AuxStore, Backend, StateBackend, StorageProvider},
 	client::BlockchainEvents,
@@ -141,6 +143,7 @@ where
 		+ 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, u32>
 		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
+		+ pallet_pool_rpc::GafiPoolRuntimeApi<Block, AccountId, Balance>
 		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 		+ fp_rpc::EthereumRuntimeRPCApi<Block>
 		+ BlockBuilder<Block>,
@@ -168,6 +171,7 @@ where
 
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(GafiPool::new(client.clone()).into_rpc())?;
 
 	let no_tx_converter: Option<fp_rpc::NoTransactionConverter> = None;
 
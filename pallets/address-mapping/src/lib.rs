@@ -20,11 +20,12 @@
 use frame_support::{
 	dispatch::DispatchResult,
 	pallet_prelude::*,
-	traits::{Currency, Get, ReservableCurrency},
+	traits::{Currency, Get, NamedReservableCurrency, StorageVersion},
 	Twox64Concat,
 	transactional,
 };
 use frame_system::pallet_prelude::*;
+use gafi_primitives::constant::{ReserveIdentifier, ADDRESS_MAPPING_RESERVE_ID};
 pub use pallet::*;
 use pallet_evm::AddressMapping;
 use sp_core::crypto::AccountId32;
@@ -43,6 +44,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
@@ -51,8 +54,12 @@ pub mod pallet {
 	use super::*;
 	use crate::weights::WeightInfo;
 
+	/// The current storage version
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	pub type NegativeImbalanceOf<C, T> =
@@ -67,8 +74,12 @@ pub mod pallet {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency mechanism, the reservation fee is reserved with
+		/// ADDRESS_MAPPING_RESERVE_ID
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
@@ -149,7 +160,11 @@ pub mod pallet {
 				<Error<T>>::SignatureOrAddressNotCorrect,
 			);
 
-			<T as pallet::Config>::Currency::reserve(&sender, T::ReservationFee::get())?;
+			<T as pallet::Config>::Currency::reserve_named(
+				&ADDRESS_MAPPING_RESERVE_ID,
+				&sender,
+				T::ReservationFee::get(),
+			)?;
 
 			if withdraw {
 				let id = Self::into_account_id(address);
@@ -182,7 +197,11 @@ pub mod pallet {
 			let id32_address = <H160Mapping<T>>::get(evm_address.unwrap());
 			ensure!(id32_address.is_some(), <Error<T>>::NonbondAccount);
 
-			<T as pallet::Config>::Currency::unreserve(&sender, T::ReservationFee::get());
+			<T as pallet::Config>::Currency::unreserve_named(
+				&ADDRESS_MAPPING_RESERVE_ID,
+				&sender,
+				T::ReservationFee::get(),
+			);
 
 			Self::remove_pair_bond(evm_address.unwrap(), id32_address.unwrap());
 			Self::deposit_event(Event::Unbonded { sender, address: evm_address.unwrap() });
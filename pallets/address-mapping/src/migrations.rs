@@ -0,0 +1,54 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Move the reservation fee of the bonded accounts to ADDRESS_MAPPING_RESERVE_ID
+	pub struct MigrateToNamedReserve<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToNamedReserve<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut accounts = 0_u64;
+			for (account_id, _) in Id32Mapping::<T>::iter() {
+				let id: [u8; 32] = account_id.into();
+				if let Some(sender) = into_account::<T::AccountId>(id) {
+					gu_currency::move_to_named_reserve::<
+						T::AccountId,
+						<T as pallet::Config>::Currency,
+					>(&ADDRESS_MAPPING_RESERVE_ID, &sender, T::ReservationFee::get());
+				}
+				accounts = accounts.saturating_add(1);
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				accounts.saturating_mul(2).saturating_add(1),
+				accounts.saturating_add(1),
+			)
+		}
+	}
+}
@@ -20,21 +20,30 @@
 use frame_support::traits::tokens::{ExistenceRequirement, WithdrawReasons};
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, Imbalance, OnUnbalanced},
+	traits::{fungibles, Currency, Imbalance, OnUnbalanced},
 };
 use frame_system::pallet_prelude::*;
 use gafi_primitives::{
 	constant::ID,
+	currency::AssetId,
+	custom_services::{AssetConversion, SponsoredAsset, SponsoredBudget},
 	game_creator::GetGameCreator,
-	ticket::{CustomTicket, PlayerTicket, TicketType},
+	ticket::{CustomTicket, PlayerTicket, TicketTarget, TicketType},
 };
 use gu_convertor::{into_account, u128_to_balance};
 pub use pallet::*;
 use pallet_evm::FeeCalculator;
 use pallet_evm::OnChargeEVMTransaction;
-use pallet_evm::{AddressMapping, GasWeightMapping};
-use sp_core::{H160, U256};
-use sp_runtime::Permill;
+use pallet_evm::{
+	runner::RunnerError, AddressMapping, CallInfo, CreateInfo, EvmConfig, GasWeightMapping, Runner,
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+	transaction_validity::TransactionValidityError,
+	FixedPointNumber, FixedU128, Permill, SaturatedConversion,
+};
 use sp_std::vec::Vec;
 
 #[cfg(test)]
@@ -49,6 +58,9 @@ mod benchmarking;
 pub mod weights;
 pub use weights::*;
 
+// the function selector of the EVM call executed by `GafiRunner`
+environmental::environmental!(call_selector: Option<[u8; 4]>);
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -78,6 +90,18 @@ pub mod pallet {
 		/// To get and use player's tickets
 		type PlayerTicket: PlayerTicket<Self::AccountId>;
 
+		/// Check and record the spending caps of the sponsored pools
+		type SponsoredBudget: SponsoredBudget<Self::AccountId>;
+
+		/// Get the asset of the sponsored pools funded with an asset
+		type SponsoredAsset: SponsoredAsset;
+
+		/// Pay the sponsored fee with the asset of the pool
+		type AssetConversion: AssetConversion<Self::AccountId>;
+
+		/// The origin approving the conversion rates of the assets
+		type RateOrigin: EnsureOrigin<Self::Origin>;
+
 		/// percentage of transaction fee reward to game-creator
 		#[pallet::constant]
 		type GameCreatorReward: Get<Permill>;
@@ -92,6 +116,11 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type GasPrice<T: Config> = StorageValue<_, U256, ValueQuery>;
 
+	/// Holding the approved amount of the asset paid for one unit of the native fee
+	#[pallet::storage]
+	#[pallet::getter(fn asset_rate)]
+	pub type AssetRates<T: Config> = StorageMap<_, Twox64Concat, AssetId, FixedU128>;
+
 	//** Genesis Conguration **//
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
@@ -126,6 +155,7 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		SetGasPrice { value: U256 },
+		AssetRateSet { asset: AssetId, rate: Option<FixedU128> },
 	}
 
 	#[pallet::call]
@@ -147,11 +177,37 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Set Asset Rate
+		///
+		/// The origin must be `RateOrigin`
+		///
+		/// Parameters:
+		/// - `asset`: the asset funding the sponsored pools
+		/// - `rate`: the amount of the asset paid for one unit of the native fee,
+		///   `None` to stop paying the fee with the asset
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_asset_rate(
+			origin: OriginFor<T>,
+			asset: AssetId,
+			rate: Option<FixedU128>,
+		) -> DispatchResult {
+			T::RateOrigin::ensure_origin(origin)?;
+			match rate {
+				Some(rate) => AssetRates::<T>::insert(asset, rate),
+				None => AssetRates::<T>::remove(asset),
+			}
+			Self::deposit_event(Event::<T>::AssetRateSet { asset, rate });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
 		pub fn correct_and_deposit_fee_sponsored(
 			pool_id: ID,
+			player: &T::AccountId,
 			targets: Vec<H160>,
 			target: H160,
 			service_fee: u128,
@@ -161,22 +217,70 @@ pub mod pallet {
 				return None;
 			}
 
+			let sponsor_fee = discount * service_fee;
+			if Self::withdraw_sponsored_fee(pool_id, player, sponsor_fee) {
+				return Some(service_fee.saturating_sub(sponsor_fee));
+			}
+			None
+		}
+
+		/// Withdraw the sponsored part of the transaction fee from the pool account,
+		/// the pool funded with an asset pays it with the asset through `AssetConversion`,
+		/// the player pays the whole fee when the pool caps are hit or the pool is paused
+		pub fn withdraw_sponsored_fee(
+			pool_id: ID,
+			player: &T::AccountId,
+			sponsor_fee: u128,
+		) -> bool {
+			if !T::SponsoredBudget::check_budget(pool_id, player, sponsor_fee) {
+				return false;
+			}
 			if let Some(sponsor) = into_account::<T::AccountId>(pool_id) {
-				let sponsor_fee = discount * service_fee;
-
-				let fee =
-					u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(sponsor_fee);
-
-				if <T as pallet::Config>::Currency::withdraw(
-					&sponsor,
-					fee,
-					WithdrawReasons::FEE,
-					ExistenceRequirement::KeepAlive,
-				).is_ok() {
-					return Some(service_fee.saturating_sub(sponsor_fee));
+				let is_paid = match T::SponsoredAsset::pool_asset(pool_id) {
+					Some(asset) =>
+						T::AssetConversion::pay_fee(asset, &sponsor, sponsor_fee).is_some(),
+					None => <T as pallet::Config>::Currency::withdraw(
+						&sponsor,
+						u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(
+							sponsor_fee,
+						),
+						WithdrawReasons::FEE,
+						ExistenceRequirement::KeepAlive,
+					)
+					.is_ok(),
+				};
+				if is_paid {
+					T::SponsoredBudget::spend(pool_id, player, sponsor_fee);
+				} else {
+					T::SponsoredBudget::insufficient_balance(pool_id, sponsor_fee);
 				}
+				return is_paid;
 			}
-			None
+			false
+		}
+
+		/// Get the pallet index and call index of the native call
+		pub fn call_target<Call: Encode>(call: &Call) -> Option<TicketTarget> {
+			let mut index = CallIndex::default();
+			call.encode_to(&mut index);
+			match index.len {
+				2 => Some(TicketTarget::Call(index.bytes[0], index.bytes[1])),
+				_ => None,
+			}
+		}
+
+		/// Get the smart-contract address and the function selector of the EVM call,
+		/// the selector is only known in the scope of the call executed by `GafiRunner`
+		pub fn contract_target(contract: H160) -> TicketTarget {
+			match call_selector::with(|selector| *selector).flatten() {
+				Some(selector) => TicketTarget::Function(contract, selector),
+				None => TicketTarget::Contract(contract),
+			}
+		}
+
+		/// The amount of the asset worth `fee` in the native currency at the approved rate
+		pub fn asset_amount(asset: AssetId, fee: u128) -> Option<u128> {
+			AssetRates::<T>::get(asset).map(|rate| rate.saturating_mul_int(fee))
 		}
 
 		fn is_target(targets: Vec<H160>, target: &H160) -> bool {
@@ -197,6 +301,49 @@ pub mod pallet {
 	}
 }
 
+/// Keep the pallet index and the call index, the first two bytes of the encoded call,
+/// the rest of the call is dropped instead of being copied into a buffer
+#[derive(Default)]
+struct CallIndex {
+	bytes: [u8; 2],
+	len: usize,
+}
+
+impl codec::Output for CallIndex {
+	fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes.iter().take(self.bytes.len().saturating_sub(self.len)) {
+			self.bytes[self.len] = *byte;
+			self.len += 1;
+		}
+	}
+}
+
+/// Pay the sponsored fee by burning the asset of the pool at the approved rate
+pub struct BurnAsset<T, A>(sp_std::marker::PhantomData<(T, A)>);
+
+impl<T, A> AssetConversion<T::AccountId> for BurnAsset<T, A>
+where
+	T: Config,
+	A: fungibles::Mutate<T::AccountId, AssetId = AssetId, Balance = u128>,
+{
+	fn to_asset(asset: AssetId, fee: u128) -> Option<u128> {
+		Pallet::<T>::asset_amount(asset, fee)
+	}
+
+	fn pay_fee(asset: AssetId, payer: &T::AccountId, fee: u128) -> Option<u128> {
+		let amount = Pallet::<T>::asset_amount(asset, fee)?;
+		if A::reducible_balance(asset, payer, true) < amount {
+			return None;
+		}
+		A::burn_from(asset, payer, amount).ok()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_rate(asset: AssetId, rate: FixedU128) {
+		AssetRates::<T>::insert(asset, rate);
+	}
+}
+
 pub struct GafiEVMCurrencyAdapter<C, OU>(sp_std::marker::PhantomData<(C, OU)>);
 
 impl<T, C, OU> OnChargeEVMTransaction<T> for GafiEVMCurrencyAdapter<C, OU>
@@ -234,7 +381,9 @@ where
 		// get mapping account id
 		let account_id: T::AccountId = <T as pallet::Config>::AddressMapping::into_account_id(*who);
 		// get transaction service based on player's service
-		if let Some((ticket_type, pool_id)) = T::PlayerTicket::use_ticket(account_id, target) {
+		if let Some((ticket_type, pool_id)) =
+			T::PlayerTicket::use_ticket(account_id.clone(), target.map(Pallet::<T>::contract_target))
+		{
 			if let Some(service) = T::PlayerTicket::get_service(pool_id) {
 				match ticket_type {
 					TicketType::System(_) => {
@@ -248,6 +397,7 @@ where
 						if let Some(contract) = target {
 							if let Some(fee) = Pallet::<T>::correct_and_deposit_fee_sponsored(
 								pool_id,
+								&account_id,
 								targets,
 								contract,
 								service_fee,
@@ -287,6 +437,216 @@ where
 	}
 }
 
+/// Pass the function selector of the EVM call to `GafiEVMCurrencyAdapter` in the scope of
+/// the call executed by the `R` runner
+pub struct GafiRunner<T, R>(sp_std::marker::PhantomData<(T, R)>);
+
+impl<T, R> Runner<T> for GafiRunner<T, R>
+where
+	T: Config,
+	R: Runner<T>,
+{
+	type Error = R::Error;
+
+	fn validate(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		evm_config: &EvmConfig,
+	) -> Result<(), RunnerError<Self::Error>> {
+		R::validate(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			evm_config,
+		)
+	}
+
+	fn call(
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CallInfo, RunnerError<Self::Error>> {
+		let mut selector = match input[..] {
+			[a, b, c, d, ..] => Some([a, b, c, d]),
+			_ => None,
+		};
+		call_selector::using(&mut selector, || {
+			R::call(
+				source,
+				target,
+				input,
+				value,
+				gas_limit,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list,
+				is_transactional,
+				validate,
+				config,
+			)
+		})
+	}
+
+	fn create(
+		source: H160,
+		init: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		R::create(
+			source,
+			init,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			validate,
+			config,
+		)
+	}
+
+	fn create2(
+		source: H160,
+		init: Vec<u8>,
+		salt: H256,
+		value: U256,
+		gas_limit: u64,
+		max_fee_per_gas: Option<U256>,
+		max_priority_fee_per_gas: Option<U256>,
+		nonce: Option<U256>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		is_transactional: bool,
+		validate: bool,
+		config: &EvmConfig,
+	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+		R::create2(
+			source,
+			init,
+			salt,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			nonce,
+			access_list,
+			is_transactional,
+			validate,
+			config,
+		)
+	}
+}
+
+/// Apply the pool discounts to the fee of native extrinsics
+///
+/// The whole fee is withdrawn by `OCT`, the discount is given back when the fee is corrected
+pub struct GafiCurrencyAdapter<OCT>(sp_std::marker::PhantomData<OCT>);
+
+impl<T, OCT> OnChargeTransaction<T> for GafiCurrencyAdapter<OCT>
+where
+	T: Config + pallet_transaction_payment::Config,
+	<T as frame_system::Config>::Call: Encode,
+	OCT: OnChargeTransaction<T>,
+{
+	type Balance = OCT::Balance;
+	type LiquidityInfo = (Option<(TicketType, ID)>, OCT::LiquidityInfo);
+
+	/// Steps
+	/// 1. Withdraw the whole transaction fee
+	/// 2. Use player ticket for the call
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let already_withdrawn = OCT::withdraw_fee(who, call, dispatch_info, fee, tip)?;
+		if fee.is_zero() {
+			return Ok((None, already_withdrawn));
+		}
+
+		let ticket = T::PlayerTicket::use_ticket(who.clone(), Pallet::<T>::call_target(call));
+		Ok((ticket, already_withdrawn))
+	}
+
+	/// Steps
+	/// 1. Reduce the transaction fee (except the tip) by the service discount,
+	///    the sponsored part is paid by the pool
+	/// 2. Refund the rest
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let (ticket, already_withdrawn) = already_withdrawn;
+		let mut corrected_fee = corrected_fee;
+
+		if let Some((ticket_type, pool_id)) = ticket {
+			if let Some(service) = T::PlayerTicket::get_service(pool_id) {
+				let discount_fee = service.discount * corrected_fee.saturating_sub(tip);
+				let is_paid = match ticket_type {
+					TicketType::System(_) => true,
+					TicketType::Custom(_) => Pallet::<T>::withdraw_sponsored_fee(
+						pool_id,
+						who,
+						discount_fee.saturated_into::<u128>(),
+					),
+				};
+				if is_paid {
+					corrected_fee = corrected_fee.saturating_sub(discount_fee);
+				}
+			}
+		}
+
+		OCT::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
 pub struct GafiGasWeightMapping;
 
 impl GasWeightMapping for GafiGasWeightMapping {
@@ -3,9 +3,16 @@ use frame_support::{
 	dispatch::Vec,
 	traits::{OnFinalize, OnInitialize},
 };
-use frame_support::{parameter_types};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32},
+};
 use frame_system as system;
-use gafi_primitives::currency::{unit, NativeToken::GAKI};
+use gafi_primitives::{
+	constant::ID,
+	currency::{unit, AssetId, NativeToken::GAKI},
+	custom_services::SponsoredAsset,
+};
 pub use pallet_balances::Call as BalancesCall;
 use pallet_evm::{EVMCurrencyAdapter, EnsureAddressNever, EnsureAddressTruncated};
 use sp_core::{H256, U256};
@@ -30,6 +37,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		GameCreator: game_creator::{Pallet, Storage, Event<T>},
 		ProofAddressMapping: proof_address_mapping::{Pallet, Storage, Event<T>},
@@ -53,7 +61,7 @@ impl pallet_evm::Config for Test {
 	type AddressMapping = ProofAddressMapping;
 	type Currency = Balances;
 	type Event = Event;
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = gafi_tx::GafiRunner<Self, pallet_evm::runner::stack::Runner<Self>>;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
 	type ChainId = ChainId;
@@ -77,6 +85,10 @@ impl gafi_tx::Config for Test {
 	type OnChargeEVMTxHandler = ();
 	type AddressMapping = ProofAddressMapping;
 	type PlayerTicket = ();
+	type SponsoredBudget = ();
+	type SponsoredAsset = MockSponsoredAsset;
+	type AssetConversion = gafi_tx::BurnAsset<Self, Assets>;
+	type RateOrigin = frame_system::EnsureRoot<AccountId32>;
 	type GameCreatorReward = GameCreatorReward;
 	type GetGameCreator = ();
 }
@@ -102,7 +114,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -112,6 +124,39 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u128;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+pub const ASSET_ID: AssetId = 1;
+pub const ASSET_POOL_ID: ID = [2_u8; 32];
+
+/// Only the pool `ASSET_POOL_ID` is funded with the asset `ASSET_ID`
+pub struct MockSponsoredAsset;
+
+impl SponsoredAsset for MockSponsoredAsset {
+	fn pool_asset(pool_id: ID) -> Option<AssetId> {
+		if pool_id == ASSET_POOL_ID {
+			Some(ASSET_ID)
+		} else {
+			None
+		}
+	}
+}
+
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
 pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
 pub const INIT_TIMESTAMP: u64 = 30_000;
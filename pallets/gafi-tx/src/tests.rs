@@ -1,11 +1,16 @@
-use crate::{mock::*, Pallet};
-use frame_support::{traits::Currency};
+use crate::{call_selector, mock::*, Pallet};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungibles, Currency},
+};
 use gafi_primitives::constant::ID;
 use gafi_primitives::currency::{unit, NativeToken::GAKI};
+use gafi_primitives::ticket::TicketTarget;
 use sp_core::{
     H160,
 };
-use sp_runtime::{AccountId32, Permill};
+use sp_runtime::{traits::BadOrigin, AccountId32, FixedPointNumber, FixedU128, Permill};
 use sp_std::str::FromStr;
 
 fn make_deposit(account: &AccountId32, balance: u128) {
@@ -32,8 +37,11 @@ fn correct_and_deposit_fee_sponsored_works() {
         let target: H160 = H160::from_str("0x0A6617b82B594C83240092BDc86E2e16354d1456").unwrap();
         let discount = Permill::from_percent(40);
 
+        let player = AccountId32::from([1_u8; 32]);
+
         let sponsored_fee = Pallet::<Test>::correct_and_deposit_fee_sponsored(
             pool_id,
+            &player,
             targets,
             target,
             service_fee,
@@ -45,3 +53,119 @@ fn correct_and_deposit_fee_sponsored_works() {
         assert_eq!(Balances::free_balance(&pool), 96 * unit(GAKI));
     })
 }
+
+#[test]
+fn correct_and_deposit_fee_sponsored_asset_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pool = AccountId32::from(ASSET_POOL_ID);
+        let service_fee = 10 * unit(GAKI);
+        assert_ok!(<Assets as fungibles::Create<AccountId32>>::create(
+            ASSET_ID,
+            pool.clone(),
+            true,
+            1
+        ));
+        assert_ok!(<Assets as fungibles::Mutate<AccountId32>>::mint_into(
+            ASSET_ID,
+            &pool,
+            100 * unit(GAKI)
+        ));
+
+        let targets = vec![H160::from_str("0x0A6617b82B594C83240092BDc86E2e16354d1456").unwrap()];
+        let target: H160 = H160::from_str("0x0A6617b82B594C83240092BDc86E2e16354d1456").unwrap();
+        let discount = Permill::from_percent(40);
+        let player = AccountId32::from([1_u8; 32]);
+        let sponsored_fee = || {
+            Pallet::<Test>::correct_and_deposit_fee_sponsored(
+                ASSET_POOL_ID,
+                &player,
+                targets.clone(),
+                target,
+                service_fee,
+                discount,
+            )
+        };
+
+        // no approved rate
+        assert_eq!(sponsored_fee(), None);
+        assert_noop!(
+            GafiTX::set_asset_rate(
+                Origin::signed(player.clone()),
+                ASSET_ID,
+                Some(FixedU128::from(2))
+            ),
+            BadOrigin
+        );
+
+        assert_ok!(GafiTX::set_asset_rate(
+            Origin::root(),
+            ASSET_ID,
+            Some(FixedU128::from(2))
+        ));
+        assert_eq!(sponsored_fee(), Some(6 * unit(GAKI)));
+        assert_eq!(Assets::balance(ASSET_ID, &pool), 92 * unit(GAKI));
+
+        // the rate changes
+        assert_ok!(GafiTX::set_asset_rate(
+            Origin::root(),
+            ASSET_ID,
+            Some(FixedU128::saturating_from_rational(1, 2))
+        ));
+        assert_eq!(sponsored_fee(), Some(6 * unit(GAKI)));
+        assert_eq!(Assets::balance(ASSET_ID, &pool), 90 * unit(GAKI));
+
+        // the pool can not afford the fee at the new rate
+        assert_ok!(GafiTX::set_asset_rate(
+            Origin::root(),
+            ASSET_ID,
+            Some(FixedU128::from(100))
+        ));
+        assert_eq!(sponsored_fee(), None);
+        assert_eq!(Assets::balance(ASSET_ID, &pool), 90 * unit(GAKI));
+
+        assert_ok!(GafiTX::set_asset_rate(Origin::root(), ASSET_ID, None));
+        assert_eq!(GafiTX::asset_rate(ASSET_ID), None);
+        assert_eq!(sponsored_fee(), None);
+        assert_eq!(Assets::balance(ASSET_ID, &pool), 90 * unit(GAKI));
+    })
+}
+
+#[test]
+fn contract_target_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        let contract: H160 = H160::from_str("0x0A6617b82B594C83240092BDc86E2e16354d1456").unwrap();
+        assert_eq!(
+            Pallet::<Test>::contract_target(contract),
+            TicketTarget::Contract(contract)
+        );
+
+        call_selector::using(&mut Some([0x0d, 0xc1, 0x3e, 0x87]), || {
+            assert_eq!(
+                Pallet::<Test>::contract_target(contract),
+                TicketTarget::Function(contract, [0x0d, 0xc1, 0x3e, 0x87])
+            );
+        });
+        assert_eq!(
+            Pallet::<Test>::contract_target(contract),
+            TicketTarget::Contract(contract)
+        );
+    })
+}
+
+#[test]
+fn call_target_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        let remark = |remark: Vec<u8>| Call::System(frame_system::Call::remark { remark });
+        let expected = remark(vec![]).using_encoded(|encoded| (encoded[0], encoded[1]));
+
+        assert_eq!(
+            Pallet::<Test>::call_target(&remark(vec![])),
+            Some(TicketTarget::Call(expected.0, expected.1))
+        );
+        assert_eq!(
+            Pallet::<Test>::call_target(&remark(vec![1_u8; 1_000_000])),
+            Some(TicketTarget::Call(expected.0, expected.1))
+        );
+        assert_eq!(Pallet::<Test>::call_target(&()), None);
+    })
+}
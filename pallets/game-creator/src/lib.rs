@@ -19,11 +19,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::{
 	pallet_prelude::*,
-	traits::{BalanceStatus, Currency, ReservableCurrency},
+	traits::{BalanceStatus, Currency, NamedReservableCurrency, StorageVersion},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
-use gafi_primitives::game_creator::GetGameCreator;
+use gafi_primitives::{
+	constant::{ReserveIdentifier, GAME_CREATOR_RESERVE_ID},
+	game_creator::GetGameCreator,
+};
 pub use pallet::*;
 use pallet_evm::{AddressMapping, ContractCreator};
 use sp_core::H160;
@@ -37,6 +40,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
@@ -59,8 +64,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxContractOwned: Get<u32>;
 
-		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency mechanism, the reservation fee is reserved with GAME_CREATOR_RESERVE_ID
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 
 		/// The verify origin contract owner function from Frontier
 		type ContractCreator: ContractCreator;
@@ -73,9 +81,13 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	//** STORAGE  **//
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Holing the contract owner
@@ -132,7 +144,11 @@ pub mod pallet {
 				<Error<T>>::ContractClaimed
 			);
 			Self::verify_owner(&sender, &contract)?;
-			<T as pallet::Config>::Currency::reserve(&sender, T::ReservationFee::get())?;
+			<T as pallet::Config>::Currency::reserve_named(
+				&GAME_CREATOR_RESERVE_ID,
+				&sender,
+				T::ReservationFee::get(),
+			)?;
 			ContractOwner::<T>::insert(contract, sender.clone());
 
 			Self::deposit_event(Event::Claimed {
@@ -163,7 +179,8 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 			Self::verify_owner(&sender, &contract)?;
 
-			<T as pallet::Config>::Currency::repatriate_reserved(
+			<T as pallet::Config>::Currency::repatriate_reserved_named(
+				&GAME_CREATOR_RESERVE_ID,
 				&sender,
 				&new_owner,
 				T::ReservationFee::get(),
@@ -195,7 +212,11 @@ pub mod pallet {
 			Self::verify_owner(&sender, &contract)?;
 
 			ContractOwner::<T>::remove(contract);
-			<T as pallet::Config>::Currency::unreserve(&sender, T::ReservationFee::get());
+			<T as pallet::Config>::Currency::unreserve_named(
+				&GAME_CREATOR_RESERVE_ID,
+				&sender,
+				T::ReservationFee::get(),
+			);
 			Self::deposit_event(Event::Withdrew {
 				contract,
 				owner: sender
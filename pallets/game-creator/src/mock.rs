@@ -86,7 +86,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -179,6 +179,21 @@ pub mod pallet {
 				.unwrap_or_default();
 			_now
 		}
+
+		/// Translate the cached data stored in the `Old` encoding, used by the storage
+		/// migrations of the pallets caching their data
+		///
+		/// Return the number of the data translated
+		pub fn translate_data<Old: Decode>(f: impl Fn(Old) -> T::Data) -> u64 {
+			let mut count = 0_u64;
+			let mut translate = |_: T::AccountId, _: T::Action, old: WrapData<Old>| {
+				count = count.saturating_add(1);
+				Some(WrapData::new(f(old.data), old.timestamp))
+			};
+			DataLeft::<T, I>::translate::<WrapData<Old>, _>(&mut translate);
+			DataRight::<T, I>::translate::<WrapData<Old>, _>(&mut translate);
+			count
+		}
 	}
 
 	impl<T: Config<I>,  I: 'static> Cache<T::AccountId, T::Action, T::Data> for Pallet<T, I> {
@@ -29,6 +29,7 @@ fn insert_data_works() {
         let data = TicketInfo {
             ticket_type:  TicketType::System(SystemTicket::Upfront(TicketLevel::Basic)),
             tickets: 100_u32,
+            epoch: 0_u32,
         };
         Pallet::<Test>::insert(&account, data.ticket_type, data);
 
@@ -51,6 +52,7 @@ fn get_data_insert_early_work() {
         let data = TicketInfo {
             ticket_type: TicketType::System(SystemTicket::Upfront(TicketLevel::Basic)),
             tickets: 100_u32,
+            epoch: 0_u32,
         };
         Pallet::<Test>::insert(&account, data.ticket_type, data);
 
@@ -70,6 +72,7 @@ fn get_data_insert_late_work() {
         let data = TicketInfo {
             ticket_type: TicketType::System(SystemTicket::Upfront(TicketLevel::Basic)),
             tickets: 100_u32,
+            epoch: 0_u32,
         };
         Pallet::<Test>::insert(&account, data.ticket_type, data);
 
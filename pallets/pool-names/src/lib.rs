@@ -1,12 +1,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::{Currency, OnUnbalanced, ReservableCurrency};
+use frame_support::traits::{Currency, NamedReservableCurrency, OnUnbalanced, StorageVersion};
 
 pub use pallet::*;
 use sp_runtime::traits::Zero;
 use sp_std::prelude::*;
 pub use gafi_primitives::{
-	constant::ID,
+	constant::{ReserveIdentifier, ID, POOL_NAMES_RESERVE_ID},
 	name::Name
 };
 
@@ -21,6 +21,8 @@ mod mocks;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -31,8 +33,11 @@ pub mod pallet {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The currency trait.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency trait, the deposit is reserved with POOL_NAMES_RESERVE_ID.
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 
 		/// Reservation fee.
 		#[pallet::constant]
@@ -78,11 +83,15 @@ pub mod pallet {
 	/// The lookup table for names.
 	#[pallet::storage]
 	#[pallet::getter(fn name_of)]
-	pub(super) type NameOf<T: Config> =
+	pub(crate) type NameOf<T: Config> =
 		StorageMap<_, Twox64Concat, ID, (BoundedVec<u8, T::MaxLength>, BalanceOf<T>)>;
 
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::call]
@@ -116,7 +125,7 @@ pub mod pallet {
 				deposit
 			} else {
 				let deposit = T::ReservationFee::get();
-				T::Currency::reserve(&account_id, deposit)?;
+				T::Currency::reserve_named(&POOL_NAMES_RESERVE_ID, &account_id, deposit)?;
 				Self::deposit_event(Event::<T>::NameSet { pool: pool_id });
 				deposit
 			};
@@ -136,7 +145,8 @@ pub mod pallet {
 		fn clear_name(account_id: AccountIdOf<T>, pool_id: ID) -> DispatchResult {
 			let deposit = <NameOf<T>>::take(&pool_id).ok_or(Error::<T>::Unnamed)?.1;
 
-			let err_amount = T::Currency::unreserve(&account_id, deposit);
+			let err_amount =
+				T::Currency::unreserve_named(&POOL_NAMES_RESERVE_ID, &account_id, deposit);
 			debug_assert!(err_amount.is_zero());
 
 			Self::deposit_event(Event::<T>::NameCleared { pool: pool_id, deposit });
@@ -161,7 +171,9 @@ pub mod pallet {
 			// Grab their deposit (and check that they have one).
 			let deposit = <NameOf<T>>::take(&target).ok_or(Error::<T>::Unnamed)?.1;
 			// Slash their deposit from them.
-			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&account_id, deposit).0);
+			T::Slashed::on_unbalanced(
+				T::Currency::slash_reserved_named(&POOL_NAMES_RESERVE_ID, &account_id, deposit).0,
+			);
 
 			Self::deposit_event(Event::<T>::NameKilled { target, deposit });
 			Ok(())
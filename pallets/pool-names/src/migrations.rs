@@ -0,0 +1,47 @@
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+use sp_runtime::traits::Convert;
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Move the deposits of the pool names to `POOL_NAMES_RESERVE_ID`.
+	///
+	/// The names don't store the account paid the deposit, `Owner` converts the pool to its
+	/// owner.
+	pub struct MigrateToNamedReserve<T, Owner>(PhantomData<(T, Owner)>);
+
+	impl<T, Owner> OnRuntimeUpgrade for MigrateToNamedReserve<T, Owner>
+	where
+		T: Config,
+		Owner: Convert<ID, Option<AccountIdOf<T>>>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut names = 0_u64;
+			for (pool_id, (_, deposit)) in NameOf::<T>::iter() {
+				if let Some(owner) = Owner::convert(pool_id) {
+					gu_currency::move_to_named_reserve::<AccountIdOf<T>, T::Currency>(
+						&POOL_NAMES_RESERVE_ID,
+						&owner,
+						deposit,
+					);
+				}
+				names = names.saturating_add(1);
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				names.saturating_mul(3).saturating_add(1),
+				names.saturating_add(1),
+			)
+		}
+	}
+}
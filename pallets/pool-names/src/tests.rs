@@ -3,6 +3,7 @@ use crate::mocks::*;
 
 use frame_support::{
 	assert_noop, assert_ok,
+	traits::NamedReservableCurrency,
 };
 use sp_runtime::AccountId32;
 use gafi_primitives::currency::{unit, NativeToken::GAKI};
@@ -30,6 +31,10 @@ fn normal_operation_should_work() {
 
 		assert_ok!(PoolNames::set_name(account.clone(), asset_id, b"Test pool".to_vec()));
 		assert_eq!(Balances::reserved_balance(account.clone()), RESERVATION_FEE * unit(GAKI));
+		assert_eq!(
+			Balances::reserved_balance_named(&POOL_NAMES_RESERVE_ID, &account),
+			RESERVATION_FEE * unit(GAKI)
+		);
 		assert_eq!(Balances::free_balance(account.clone()),free_balance );
 		assert_eq!(<NameOf<Test>>::get(asset_id).unwrap().0, b"Test pool".to_vec());
 
@@ -0,0 +1,57 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the Gafi pools and tickets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use gafi_primitives::{
+	constant::ID, custom_services::SponsoredPoolInfo, pool::Service, ticket::TicketInfo,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait GafiPoolApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// All the pools the player joined with the tickets remaining in each pool
+		fn joined_pools(player: AccountId) -> Vec<(ID, TicketInfo)>;
+
+		/// The tickets remaining of the player in the pool
+		fn tickets_remaining(player: AccountId, pool_id: ID) -> Option<u32>;
+
+		/// The service behind the pool id, whatever the pool type is
+		fn pool_service(pool_id: ID) -> Option<Service>;
+
+		/// The sponsored pool detail with its targets and balance
+		fn sponsored_pool(pool_id: ID) -> Option<SponsoredPoolInfo<AccountId, Balance>>;
+
+		/// The time (in milliseconds) until the tickets are renewed
+		fn next_renewal_in() -> u128;
+
+		/// The staking rewards the player can claim
+		fn pending_rewards(player: AccountId) -> Balance;
+
+		/// The position of the player in the staking waitlist, starting from 1
+		fn waitlist_position(player: AccountId) -> Option<u32>;
+
+		/// The beneficiaries whose staking tickets are funded by the sponsor
+		fn beneficiaries(sponsor: AccountId) -> Vec<AccountId>;
+	}
+}
@@ -0,0 +1,232 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the Gafi pools and tickets, served under the `gafi` namespace.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_pool_rpc_runtime_api::GafiPoolApi as GafiPoolRuntimeApi;
+use pallet_pool_rpc_runtime_api::{Service, SponsoredPoolInfo, TicketInfo};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(client, server)]
+pub trait GafiPoolApi<BlockHash, AccountId, Balance> {
+	/// All the pools the player joined with the tickets remaining in each pool
+	#[method(name = "gafi_joinedPools")]
+	fn joined_pools(
+		&self,
+		player: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(H256, TicketInfo)>>;
+
+	/// The tickets remaining of the player in the pool
+	#[method(name = "gafi_ticketsRemaining")]
+	fn tickets_remaining(
+		&self,
+		player: AccountId,
+		pool_id: H256,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<u32>>;
+
+	/// The service behind the pool id
+	#[method(name = "gafi_poolService")]
+	fn pool_service(&self, pool_id: H256, at: Option<BlockHash>) -> RpcResult<Option<Service>>;
+
+	/// The sponsored pool detail with its targets and balance
+	#[method(name = "gafi_sponsoredPool")]
+	fn sponsored_pool(
+		&self,
+		pool_id: H256,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SponsoredPoolInfo<AccountId, Balance>>>;
+
+	/// The time (in milliseconds) until the tickets are renewed
+	#[method(name = "gafi_nextRenewalIn")]
+	fn next_renewal_in(&self, at: Option<BlockHash>) -> RpcResult<u128>;
+
+	/// The staking rewards the player can claim
+	#[method(name = "gafi_pendingRewards")]
+	fn pending_rewards(&self, player: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+	/// The position of the player in the staking waitlist, starting from 1
+	#[method(name = "gafi_waitlistPosition")]
+	fn waitlist_position(&self, player: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u32>>;
+
+	/// The beneficiaries whose staking tickets are funded by the sponsor
+	#[method(name = "gafi_beneficiaries")]
+	fn beneficiaries(&self, sponsor: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: sp_api::ApiError) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		message,
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+/// Provides RPC methods to query the pools and tickets.
+pub struct GafiPool<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> GafiPool<C, Block> {
+	/// Creates a new instance of the GafiPool Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance>
+	GafiPoolApiServer<<Block as BlockT>::Hash, AccountId, Balance> for GafiPool<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: GafiPoolRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Serialize + Send + Sync + 'static,
+	Balance: Codec + Serialize + Send + Sync + 'static,
+	for<'de> AccountId: serde::Deserialize<'de>,
+	for<'de> Balance: serde::Deserialize<'de>,
+{
+	fn joined_pools(
+		&self,
+		player: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(H256, TicketInfo)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let joined_pools = api
+			.joined_pools(&at, player)
+			.map_err(|e| runtime_error("Unable to query joined pools.", e))?;
+
+		Ok(joined_pools
+			.into_iter()
+			.map(|(pool_id, ticket_info)| (H256::from(pool_id), ticket_info))
+			.collect())
+	}
+
+	fn tickets_remaining(
+		&self,
+		player: AccountId,
+		pool_id: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.tickets_remaining(&at, player, pool_id.into())
+			.map_err(|e| runtime_error("Unable to query tickets remaining.", e))
+	}
+
+	fn pool_service(
+		&self,
+		pool_id: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Service>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pool_service(&at, pool_id.into())
+			.map_err(|e| runtime_error("Unable to query pool service.", e))
+	}
+
+	fn sponsored_pool(
+		&self,
+		pool_id: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SponsoredPoolInfo<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.sponsored_pool(&at, pool_id.into())
+			.map_err(|e| runtime_error("Unable to query sponsored pool.", e))
+	}
+
+	fn next_renewal_in(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u128> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.next_renewal_in(&at)
+			.map_err(|e| runtime_error("Unable to query next renewal.", e))
+	}
+
+	fn pending_rewards(
+		&self,
+		player: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pending_rewards(&at, player)
+			.map_err(|e| runtime_error("Unable to query pending rewards.", e))
+	}
+
+	fn waitlist_position(
+		&self,
+		player: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.waitlist_position(&at, player)
+			.map_err(|e| runtime_error("Unable to query waitlist position.", e))
+	}
+
+	fn beneficiaries(
+		&self,
+		sponsor: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.beneficiaries(&at, sponsor)
+			.map_err(|e| runtime_error("Unable to query beneficiaries.", e))
+	}
+}
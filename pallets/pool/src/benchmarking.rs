@@ -26,6 +26,18 @@ fn new_funded_account<T: Config>(index: u32, seed: u32, amount: u128) -> T::Acco
 	return user;
 }
 
+/// Join the sponsored pools so the tickets of the player are full
+fn join_sponsored_pools<T: Config>(player: T::AccountId, count: u32) {
+	for i in 1 ..= count {
+		let pool_id = [i as u8; 32];
+		T::SponsoredPool::add_default(player.clone(), pool_id);
+		let _ = Pallet::<T>::join(
+			RawOrigin::Signed(player.clone()).into(),
+			TicketType::Custom(CustomTicket::Sponsored(pool_id)),
+		);
+	}
+}
+
 const MAX_TICKETS: usize = 7;
 const POOL_ID: ID = [0_u8; 32];
 
@@ -63,19 +75,85 @@ benchmarks! {
 
 
 	leave_all {
-		let s in 0 .. (MAX_TICKETS - 1) as u32;
+		let s in 1 .. T::MaxJoinedSponsoredPool::get();
 		let caller = new_funded_account::<T>(s, s, 100_000_000u128 * UNIT);
-		let pool_id =  match TICKETS[s as usize] {
-			TicketType::System(system_ticket) => {
-				system_ticket.using_encoded(blake2_256)
-			}
-			TicketType::Custom(CustomTicket::Sponsored(joined_pool_id)) => {
-				joined_pool_id
-			}
-		};
-		T::SponsoredPool::add_default(caller.clone(), POOL_ID);
-		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[s as usize]);
+		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[0]);
+		for i in 1 .. s {
+			let pool_id = [i as u8; 32];
+			T::SponsoredPool::add_default(caller.clone(), pool_id);
+			let _ = Pallet::<T>::join(
+				RawOrigin::Signed(caller.clone()).into(),
+				TicketType::Custom(CustomTicket::Sponsored(pool_id)),
+			);
+		}
 	}: _(RawOrigin::Signed(caller))
 
+	set_preferred_pool {
+		let caller = new_funded_account::<T>(0, 0, 100_000_000u128 * UNIT);
+		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[0]);
+		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[3]);
+		let mut pool_id = POOL_ID;
+		for i in 1 ..= T::MaxJoinedSponsoredPool::get() {
+			pool_id = [i as u8; 32];
+			T::SponsoredPool::add_default(caller.clone(), pool_id);
+			let _ = Pallet::<T>::join(
+				RawOrigin::Signed(caller.clone()).into(),
+				TicketType::Custom(CustomTicket::Sponsored(pool_id)),
+			);
+		}
+	}: _(RawOrigin::Signed(caller), Some(pool_id))
+
+	// the system ticket to move is searched in the tickets of the sponsored pools joined
+	change_level_upfront {
+		let s in 0 .. T::MaxJoinedSponsoredPool::get();
+		let caller = new_funded_account::<T>(0, 0, 100_000_000u128 * UNIT);
+		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[0]);
+		join_sponsored_pools::<T>(caller.clone(), s);
+	}: change_level(RawOrigin::Signed(caller.clone()), TICKETS[2])
+	verify {
+		let pool_id = SystemTicket::Upfront(TicketLevel::Advance).using_encoded(blake2_256);
+		assert!(Tickets::<T>::contains_key(caller, pool_id));
+	}
+
+	change_level_staking {
+		let s in 0 .. T::MaxJoinedSponsoredPool::get();
+		let caller = new_funded_account::<T>(0, 0, 100_000_000u128 * UNIT);
+		let _ = Pallet::<T>::join(RawOrigin::Signed(caller.clone()).into(), TICKETS[3]);
+		join_sponsored_pools::<T>(caller.clone(), s);
+	}: change_level(RawOrigin::Signed(caller.clone()), TICKETS[5])
+	verify {
+		let pool_id = SystemTicket::Staking(TicketLevel::Advance).using_encoded(blake2_256);
+		assert!(Tickets::<T>::contains_key(caller, pool_id));
+	}
+
+	join_for {
+		let caller = new_funded_account::<T>(0, 0, 1000_000_000u128 * UNIT);
+		let beneficiary = new_funded_account::<T>(1, 0, 1000_000_000u128 * UNIT);
+		let pool_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+	}: _(RawOrigin::Signed(caller), beneficiary.clone(), TICKETS[3])
+	verify {
+		assert!(Tickets::<T>::contains_key(beneficiary, pool_id));
+	}
+
+	leave_for {
+		let caller = new_funded_account::<T>(0, 0, 1000_000_000u128 * UNIT);
+		let beneficiary = new_funded_account::<T>(1, 0, 1000_000_000u128 * UNIT);
+		let _ = Pallet::<T>::join_for(
+			RawOrigin::Signed(caller.clone()).into(),
+			beneficiary.clone(),
+			TICKETS[3],
+		);
+		let pool_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+	}: _(RawOrigin::Signed(caller), beneficiary.clone(), pool_id)
+	verify {
+		assert!(!Tickets::<T>::contains_key(beneficiary, pool_id));
+	}
+
+	set_time_service {
+	}: _(RawOrigin::Root, 60_000u128)
+	verify {
+		assert_eq!(NextTimeService::<T>::get(), Some(60_000u128));
+	}
+
 	impl_benchmark_test_suite!(Pool, crate::mock::new_test_ext(), crate::mock::Test);
 }
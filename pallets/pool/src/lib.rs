@@ -17,15 +17,19 @@
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::{pallet_prelude::*, traits::Currency, transactional};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, StorageVersion},
+	transactional,
+};
 use frame_system::pallet_prelude::*;
 use gafi_primitives::{
 	constant::ID,
 	custom_services::CustomPool,
-	pool::{MasterPool, PoolType, Service},
-	system_services::SystemPool,
+	pool::{MasterPool, Service},
+	system_services::{SponsoredJoin, SystemPool},
 	ticket::TicketInfo,
-	ticket::{CustomTicket, PlayerTicket, SystemTicket, TicketType},
+	ticket::{CustomTicket, PlayerTicket, SystemTicket, TicketTarget, TicketType},
 };
 use pallet_timestamp::{self as timestamp};
 
@@ -34,6 +38,7 @@ use gafi_primitives::cache::Cache;
 pub use pallet::*;
 use sp_core::H160;
 use sp_io::hashing::blake2_256;
+use sp_runtime::Permill;
 use sp_std::vec::Vec;
 
 #[cfg(test)]
@@ -45,6 +50,7 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
@@ -65,8 +71,8 @@ pub mod pallet {
 		/// Add upfront pool
 		type UpfrontPool: SystemPool<Self::AccountId>;
 
-		/// Add Staking Pool
-		type StakingPool: SystemPool<Self::AccountId>;
+		/// Add Staking Pool, the sponsors can stake for the beneficiaries
+		type StakingPool: SystemPool<Self::AccountId> + SponsoredJoin<Self::AccountId>;
 
 		/// Add Sponsored Pool
 		type SponsoredPool: CustomPool<Self::AccountId>;
@@ -77,12 +83,19 @@ pub mod pallet {
 		#[pallet::constant]
 		type TimeServiceStorage: Get<u128>;
 
+		/// The origin which may change the TimeService
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Add Cache
 		type Cache: Cache<Self::AccountId, TicketType, TicketInfo>;
 	}
 
+	/// The current storage version
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// /// Holding all the tickets in the network
@@ -96,6 +109,11 @@ pub mod pallet {
 	pub type Tickets<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ID, TicketInfo>;
 
+	/// Holding the pool that player prefers to use ticket first
+	#[pallet::storage]
+	#[pallet::getter(fn preferred_pool)]
+	pub type PreferredPool<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ID>;
+
 	/// Holding the mark time to check if correct time to charge service fee
 	/// The default value is at the time chain launched
 	#[pallet::type_value]
@@ -117,16 +135,43 @@ pub mod pallet {
 	#[pallet::getter(fn time_service)]
 	pub type TimeService<T: Config> = StorageValue<_, u128, ValueQuery, DefaultTimeService>;
 
+	/// Holding the new TimeService that takes effect at the next MarkTime
+	#[pallet::storage]
+	#[pallet::getter(fn next_time_service)]
+	pub type NextTimeService<T: Config> = StorageValue<_, u128>;
+
+	/// Holding the mark time when the current TimeService took effect
+	#[pallet::storage]
+	#[pallet::getter(fn time_service_start)]
+	pub type TimeServiceStart<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// Holding the current renewal epoch, increased every time service
+	/// Tickets renewed in an older epoch are refilled lazily when touched
+	#[pallet::storage]
+	#[pallet::getter(fn epoch)]
+	pub type Epoch<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// on_finalize following by steps:
-	/// 1. renew tickets
+	/// 1. renew tickets by starting a new epoch
 	/// 2. Update new Marktime
+	/// 3. Switch to the scheduled TimeService if any
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
+			// reserve the weight of on_finalize
+			T::DbWeight::get().reads_writes(4, 5)
+		}
+
 		fn on_finalize(_block_number: BlockNumberFor<T>) {
 			let _now: u128 = <timestamp::Pallet<T>>::get().try_into().ok().unwrap();
 			if _now - Self::mark_time() >= Self::time_service() {
 				Self::renew_tickets();
 				MarkTime::<T>::put(_now);
+				if let Some(time_service) = NextTimeService::<T>::take() {
+					TimeService::<T>::put(time_service);
+					TimeServiceStart::<T>::put(_now);
+					Self::deposit_event(Event::<T>::TimeServiceChanged { time_service });
+				}
 			}
 		}
 	}
@@ -161,13 +206,34 @@ pub mod pallet {
 			sender: T::AccountId,
 			pool_id: ID,
 		},
+		JoinedFor {
+			sponsor: T::AccountId,
+			beneficiary: T::AccountId,
+			pool_id: ID,
+		},
 		Leaved {
 			sender: T::AccountId,
 			ticket: TicketType,
 		},
-		LeavedAll {
+		SetPreferredPool {
+			sender: T::AccountId,
+			pool_id: Option<ID>,
+		},
+		UsedTicket {
+			sender: T::AccountId,
+			pool_id: ID,
+			ticket: TicketType,
+		},
+		ChangedLevel {
 			sender: T::AccountId,
-			pool_type: PoolType,
+			ticket: TicketType,
+		},
+		TimeServiceScheduled {
+			time_service: u128,
+			start_at: u128,
+		},
+		TimeServiceChanged {
+			time_service: u128,
 		},
 	}
 
@@ -178,6 +244,11 @@ pub mod pallet {
 		TicketNotFound,
 		ComingSoon,
 		ExceedJoinedPool,
+		NotSystemTicket,
+		InvalidTimeService,
+		NotSupportedTicket,
+		NotSponsor,
+		SponsoredTicket,
 	}
 
 	#[pallet::call]
@@ -232,8 +303,85 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Join For
+		///
+		/// Join the staking pool for the beneficiary, the stake is reserved from the sender
+		/// who is the only one that can leave the ticket with `leave_for`
+		///
+		/// The origin must be Signed
+		///
+		/// Parameters:
+		/// - `beneficiary`: the account getting the ticket
+		/// - `ticket`: the staking ticket
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::join_for())]
+		#[transactional]
+		pub fn join_for(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+			ticket: TicketType,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let pool_id = match ticket {
+				TicketType::System(SystemTicket::Staking(level)) => {
+					SystemTicket::Staking(level).using_encoded(blake2_256)
+				}
+				_ => return Err(Error::<T>::NotSupportedTicket.into()),
+			};
+
+			let ticket_info = Self::get_ticket_info(&beneficiary, ticket, pool_id)?;
+			ensure!(
+				Self::is_joined_pool(beneficiary.clone(), pool_id) == false,
+				<Error<T>>::AlreadyJoined
+			);
+
+			T::StakingPool::join_for(sender.clone(), beneficiary.clone(), pool_id)?;
+			Tickets::<T>::insert(beneficiary.clone(), pool_id, ticket_info);
+
+			Self::deposit_event(Event::<T>::JoinedFor {
+				sponsor: sender,
+				beneficiary,
+				pool_id,
+			});
+			Ok(())
+		}
+
+		/// Leave For
+		///
+		/// Leave the staking ticket funded by the sender, the stake is returned to the sender
+		///
+		/// The origin must be Signed
+		///
+		/// Parameters:
+		/// - `beneficiary`: the account holding the ticket
+		/// - `pool_id`: the staking pool id
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::leave_for())]
+		#[transactional]
+		pub fn leave_for(
+			origin: OriginFor<T>,
+			beneficiary: T::AccountId,
+			pool_id: ID,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let ticket =
+				Tickets::<T>::get(beneficiary.clone(), pool_id).ok_or(<Error<T>>::NotFoundInPool)?;
+			ensure!(
+				T::StakingPool::sponsor_of(&beneficiary) == Some(sender),
+				<Error<T>>::NotSponsor
+			);
+
+			Self::leave_pool(&beneficiary, pool_id, ticket)?;
+			Self::remove_preferred_pool(&beneficiary, pool_id);
+			Ok(())
+		}
+
 		/// leave pool
 		///
+		/// The staking ticket funded by a sponsor can only be left by the sponsor
+		///
 		/// The origin must be Signed
 		///
 		/// Weight: `O(1)`
@@ -243,23 +391,9 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 
 			if let Some(ticket) = Tickets::<T>::get(sender.clone(), pool_id) {
-				match ticket.ticket_type {
-					TicketType::System(SystemTicket::Upfront(_)) => {
-						T::UpfrontPool::leave(sender.clone())?
-					}
-					TicketType::System(SystemTicket::Staking(_)) => {
-						T::StakingPool::leave(sender.clone())?
-					}
-					TicketType::Custom(CustomTicket::Sponsored(_)) => {
-						T::SponsoredPool::leave(sender.clone())?
-					}
-				}
-				Self::insert_cache(&sender, ticket.ticket_type, ticket);
-				Tickets::<T>::remove(sender.clone(), pool_id);
-				Self::deposit_event(Event::<T>::Leaved {
-					sender,
-					ticket: ticket.ticket_type,
-				});
+				ensure!(!Self::is_sponsored_stake(&sender, ticket), <Error<T>>::SponsoredTicket);
+				Self::leave_pool(&sender, pool_id, ticket)?;
+				Self::remove_preferred_pool(&sender, pool_id);
 				Ok(())
 			} else {
 				Err(Error::<T>::NotFoundInPool.into())
@@ -268,38 +402,153 @@ pub mod pallet {
 
 		/// Leave Pool
 		///
-		/// Leave all the pools that player joined
+		/// Leave all the pools that player joined, except the staking ticket funded by a sponsor
+		///
+		/// The origin must be Signed
+		///
+		/// Weight: `O(n)` where n is the number of the joined pools
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::leave_all(
+			T::MaxJoinedSponsoredPool::get().saturating_add(1)
+		))]
+		#[transactional]
+		pub fn leave_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let joined_pools: Vec<(ID, TicketInfo)> = Tickets::<T>::iter_prefix(sender.clone())
+				.filter(|(_, ticket)| !Self::is_sponsored_stake(&sender, *ticket))
+				.collect();
+			for (pool_id, ticket) in joined_pools.iter() {
+				Self::leave_pool(&sender, *pool_id, *ticket)?;
+				Self::remove_preferred_pool(&sender, *pool_id);
+			}
+
+			Ok(Some(<T as pallet::Config>::WeightInfo::leave_all(joined_pools.len() as u32)).into())
+		}
+
+		/// Set Preferred Pool
+		///
+		/// The ticket of the preferred pool is used first whenever it is usable,
+		/// `None` to clear the preferred pool
 		///
 		/// The origin must be Signed
 		///
+		/// Parameters:
+		/// - `pool_id`: the id of the joined pool
+		///
 		/// Weight: `O(1)`
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::leave_all(50u32))]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_preferred_pool())]
+		pub fn set_preferred_pool(origin: OriginFor<T>, pool_id: Option<ID>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			match pool_id {
+				Some(pool_id) => {
+					ensure!(
+						Tickets::<T>::contains_key(sender.clone(), pool_id),
+						<Error<T>>::NotFoundInPool
+					);
+					PreferredPool::<T>::insert(sender.clone(), pool_id);
+				}
+				None => PreferredPool::<T>::remove(sender.clone()),
+			}
+
+			Self::deposit_event(Event::<T>::SetPreferredPool { sender, pool_id });
+			Ok(())
+		}
+
+		/// Change Level
+		///
+		/// Upgrade or downgrade the level of the joined system ticket without leaving the pool,
+		/// the tickets already used in the current period are kept
+		///
+		/// The origin must be Signed
+		///
+		/// Parameters:
+		/// - `ticket`: the system ticket with the new level
+		///
+		/// Weight: `O(n)` where n is the number of the joined pools
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::change_level(
+			T::MaxJoinedSponsoredPool::get(),
+			*ticket
+		))]
 		#[transactional]
-		pub fn leave_all(origin: OriginFor<T>) -> DispatchResult {
+		pub fn change_level(origin: OriginFor<T>, ticket: TicketType) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
+			let system_ticket = match ticket {
+				TicketType::System(system_ticket) => system_ticket,
+				TicketType::Custom(_) => return Err(Error::<T>::NotSystemTicket.into()),
+			};
+			let pool_id = system_ticket.using_encoded(blake2_256);
+
+			let (old_pool_id, _) = Tickets::<T>::iter_prefix(sender.clone())
+				.find(|(_, ticket_info)| {
+					match (ticket_info.ticket_type, system_ticket) {
+						(
+							TicketType::System(SystemTicket::Upfront(_)),
+							SystemTicket::Upfront(_),
+						) => true,
+						(
+							TicketType::System(SystemTicket::Staking(_)),
+							SystemTicket::Staking(_),
+						) => true,
+						_ => false,
+					}
+				})
+				.ok_or(<Error<T>>::NotFoundInPool)?;
+			ensure!(old_pool_id != pool_id, <Error<T>>::AlreadyJoined);
 
-			if let Ok(_) = T::UpfrontPool::leave(sender.clone()) {
-				Self::deposit_event(Event::LeavedAll {
-					sender: sender.clone(),
-					pool_type: PoolType::Upfront,
-				});
-			} else if let Ok(_) = T::StakingPool::leave(sender.clone()) {
-				Self::deposit_event(Event::LeavedAll {
-					sender: sender.clone(),
-					pool_type: PoolType::Staking,
-				});
-			} else if let Ok(_) = T::SponsoredPool::leave(sender.clone()) {
-				Self::deposit_event(Event::LeavedAll {
-					sender: sender.clone(),
-					pool_type: PoolType::Sponsored,
-				});
+			match system_ticket {
+				SystemTicket::Upfront(_) => T::UpfrontPool::change_level(sender.clone(), pool_id)?,
+				SystemTicket::Staking(_) => T::StakingPool::change_level(sender.clone(), pool_id)?,
 			}
-			Tickets::<T>::remove_prefix(sender, None);
+			Self::move_ticket(&sender, old_pool_id, pool_id, ticket)?;
+
+			Self::deposit_event(Event::<T>::ChangedLevel { sender, ticket });
+			Ok(())
+		}
+
+		/// Set TimeService
+		///
+		/// Schedule a new TimeService, it takes effect at the next MarkTime so the current
+		/// period is charged and renewed with the old one
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `time_service`: the new period of time (in milliseconds) to charge service fee
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_time_service())]
+		pub fn set_time_service(origin: OriginFor<T>, time_service: u128) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(time_service > 0, <Error<T>>::InvalidTimeService);
+
+			NextTimeService::<T>::put(time_service);
+			Self::deposit_event(Event::<T>::TimeServiceScheduled {
+				time_service,
+				start_at: Self::mark_time().saturating_add(Self::time_service()),
+			});
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn leave_pool(sender: &T::AccountId, pool_id: ID, ticket: TicketInfo) -> DispatchResult {
+			match ticket.ticket_type {
+				TicketType::System(SystemTicket::Upfront(_)) => T::UpfrontPool::leave(sender.clone())?,
+				TicketType::System(SystemTicket::Staking(_)) => T::StakingPool::leave(sender.clone())?,
+				TicketType::Custom(CustomTicket::Sponsored(_)) => {
+					T::SponsoredPool::leave(sender.clone(), pool_id)?
+				}
+			}
+			Self::insert_cache(sender, ticket.ticket_type, ticket);
+			Tickets::<T>::remove(sender.clone(), pool_id);
+			Self::deposit_event(Event::<T>::Leaved {
+				sender: sender.clone(),
+				ticket: ticket.ticket_type,
+			});
+			Ok(())
+		}
+
 		fn insert_cache(sender: &T::AccountId, ticket: TicketType, data: TicketInfo) {
 			match ticket {
 				TicketType::System(SystemTicket::Upfront(_)) => {}
@@ -317,15 +566,20 @@ pub mod pallet {
 		) -> Result<TicketInfo, Error<T>> {
 			let service = Self::get_ticket_service(pool_id)?;
 			if let Some(cache) = Self::get_cache(&sender, ticket) {
-				return Ok(TicketInfo {
-					ticket_type: ticket,
-					tickets: cache.tickets,
-				});
+				return Ok(Self::refresh_ticket(
+					pool_id,
+					TicketInfo {
+						ticket_type: ticket,
+						tickets: cache.tickets,
+						epoch: cache.epoch,
+					},
+				));
 			}
 
 			Ok(TicketInfo {
 				ticket_type: ticket,
 				tickets: service.tx_limit,
+				epoch: Self::epoch(),
 			})
 		}
 
@@ -342,15 +596,22 @@ pub mod pallet {
 			None
 		}
 
+		/// Start a new epoch, all the tickets will be refilled when they are touched
+		///
+		/// Weight: `O(1)`
 		pub fn renew_tickets() {
-			let _ = Tickets::<T>::iter().for_each(|player| {
-				if let Some(ticket_info) = Tickets::<T>::get(player.0.clone(), player.1.clone()) {
-					if let Some(service) = Self::get_service(player.1.clone()) {
-						let new_ticket = ticket_info.renew_ticket(service.tx_limit);
-						Tickets::<T>::insert(player.0, player.1, new_ticket);
-					}
+			Epoch::<T>::mutate(|epoch| *epoch = epoch.saturating_add(1));
+		}
+
+		/// Refill the tickets if they were not renewed in the current epoch
+		pub(crate) fn refresh_ticket(pool_id: ID, ticket_info: TicketInfo) -> TicketInfo {
+			let epoch = Self::epoch();
+			if ticket_info.epoch < epoch {
+				if let Some(service) = Self::get_service(pool_id) {
+					return ticket_info.renew_ticket(service.tx_limit, epoch);
 				}
-			});
+			}
+			ticket_info
 		}
 
 		pub(crate) fn get_ticket_service(pool_id: ID) -> Result<Service, Error<T>> {
@@ -360,6 +621,164 @@ pub mod pallet {
 			}
 		}
 
+		/// All the pools the player joined with the tickets remaining in each pool
+		pub fn joined_pools(player: &T::AccountId) -> Vec<(ID, TicketInfo)> {
+			Tickets::<T>::iter_prefix(player)
+				.map(|(pool_id, ticket_info)| (pool_id, Self::refresh_ticket(pool_id, ticket_info)))
+				.collect()
+		}
+
+		/// The tickets remaining of the player in the pool
+		pub fn tickets_remaining(player: &T::AccountId, pool_id: ID) -> Option<u32> {
+			Tickets::<T>::get(player, pool_id)
+				.map(|ticket_info| Self::refresh_ticket(pool_id, ticket_info).tickets)
+		}
+
+		/// The time (in milliseconds) until the tickets are renewed
+		pub fn next_renewal_in() -> u128 {
+			let now: u128 = <timestamp::Pallet<T>>::get()
+				.try_into()
+				.ok()
+				.unwrap_or_default();
+			Self::mark_time()
+				.saturating_add(Self::time_service())
+				.saturating_sub(now)
+		}
+
+		/// Move the ticket of the player to the new pool, the tickets used in the current
+		/// period are kept
+		fn move_ticket(
+			player: &T::AccountId,
+			old_pool_id: ID,
+			new_pool_id: ID,
+			ticket: TicketType,
+		) -> Result<(), Error<T>> {
+			let old_ticket_info =
+				Tickets::<T>::get(player, old_pool_id).ok_or(<Error<T>>::NotFoundInPool)?;
+			let old_ticket_info = Self::refresh_ticket(old_pool_id, old_ticket_info);
+			let old_service = Self::get_ticket_service(old_pool_id)?;
+			let new_service = Self::get_ticket_service(new_pool_id)?;
+
+			let used_tickets = old_service.tx_limit.saturating_sub(old_ticket_info.tickets);
+			let ticket_info = TicketInfo {
+				ticket_type: ticket,
+				tickets: new_service.tx_limit.saturating_sub(used_tickets),
+				epoch: old_ticket_info.epoch,
+			};
+			Tickets::<T>::remove(player, old_pool_id);
+			Tickets::<T>::insert(player, new_pool_id, ticket_info);
+			if PreferredPool::<T>::get(player) == Some(old_pool_id) {
+				PreferredPool::<T>::insert(player, new_pool_id);
+			}
+			Ok(())
+		}
+
+		/// The staking ticket of the player is funded by a sponsor
+		fn is_sponsored_stake(player: &T::AccountId, ticket: TicketInfo) -> bool {
+			match ticket.ticket_type {
+				TicketType::System(SystemTicket::Staking(_)) => {
+					T::StakingPool::sponsor_of(player).is_some()
+				}
+				_ => false,
+			}
+		}
+
+		fn remove_preferred_pool(sender: &T::AccountId, pool_id: ID) {
+			if PreferredPool::<T>::get(sender) == Some(pool_id) {
+				PreferredPool::<T>::remove(sender);
+			}
+		}
+
+		/// Select the best ticket of the player for the target, following by order:
+		/// 1. the ticket of the preferred pool
+		/// 2. the sponsored ticket that matches the target
+		/// 3. the ticket with the highest discount
+		pub(crate) fn select_ticket(
+			player: &T::AccountId,
+			target: Option<TicketTarget>,
+		) -> Option<(ID, TicketInfo)> {
+			if let Some(pool_id) = PreferredPool::<T>::get(player) {
+				if let Some(ticket_info) = Tickets::<T>::get(player, pool_id) {
+					if let Some(ticket) = Self::usable_ticket(player, pool_id, ticket_info, target) {
+						return Some(ticket);
+					}
+				}
+			}
+
+			Tickets::<T>::iter_prefix(player)
+				.filter_map(|(pool_id, ticket_info)| {
+					Self::usable_ticket(player, pool_id, ticket_info, target)
+				})
+				.max_by_key(|(pool_id, ticket_info)| {
+					let is_sponsored = matches!(ticket_info.ticket_type, TicketType::Custom(_));
+					let discount = match Self::get_service(*pool_id) {
+						Some(service) => service.discount,
+						None => Permill::from_percent(0),
+					};
+					(is_sponsored, discount)
+				})
+		}
+
+		/// Refill the ticket and check if it can be used for the target
+		fn usable_ticket(
+			player: &T::AccountId,
+			pool_id: ID,
+			ticket_info: TicketInfo,
+			target: Option<TicketTarget>,
+		) -> Option<(ID, TicketInfo)> {
+			let ticket_info = Self::refresh_ticket(pool_id, ticket_info);
+			if ticket_info.tickets == 0 {
+				return None;
+			}
+
+			match ticket_info.ticket_type {
+				// the discount is suspended until the service fee is paid
+				TicketType::System(SystemTicket::Upfront(_)) => {
+					if T::UpfrontPool::is_suspended(player) {
+						return None;
+					}
+					Some((pool_id, ticket_info))
+				}
+				// the player in the waitlist has no staking ticket yet
+				TicketType::System(SystemTicket::Staking(_)) => {
+					if T::StakingPool::is_suspended(player) {
+						return None;
+					}
+					Some((pool_id, ticket_info))
+				}
+				// the paused pool and the pool hit its cap can't sponsor the transaction
+				TicketType::Custom(CustomTicket::Sponsored(_)) => {
+					if T::SponsoredPool::is_paused(pool_id) ||
+						T::SponsoredPool::is_capped(pool_id, player)
+					{
+						return None;
+					}
+					match target {
+						Some(target) if Self::is_sponsored_target(pool_id, target) => {
+							Some((pool_id, ticket_info))
+						}
+						_ => None,
+					}
+				}
+			}
+		}
+
+		/// Check if the sponsored pool covers the target of the transaction
+		fn is_sponsored_target(pool_id: ID, target: TicketTarget) -> bool {
+			match T::SponsoredPool::get_service(pool_id) {
+				Some(service) => match target {
+					TicketTarget::Contract(contract) => service.is_sponsored_function(&contract, None),
+					TicketTarget::Call(pallet_index, call_index) => {
+						service.call_targets.contains(&(pallet_index, call_index))
+					}
+					TicketTarget::Function(contract, selector) => {
+						service.is_sponsored_function(&contract, Some(selector))
+					}
+				},
+				None => false,
+			}
+		}
+
 		fn is_joined_pool(sender: T::AccountId, pool_id: ID) -> bool {
 			let joined_pools = Tickets::<T>::iter_prefix_values(sender.clone());
 			let mut is_joined = false;
@@ -382,38 +801,20 @@ pub mod pallet {
 	}
 
 	impl<T: Config> PlayerTicket<T::AccountId> for Pallet<T> {
-		fn use_ticket(player: T::AccountId, target: Option<H160>) -> Option<(TicketType, ID)> {
-			let ticket_infos = Tickets::<T>::iter_prefix_values(player.clone());
-
-			for ticket_info in ticket_infos {
-				match ticket_info.ticket_type {
-					TicketType::System(hello) => {
-						if let Some(new_ticket_info) = ticket_info.withdraw_ticket() {
-							Tickets::<T>::insert(
-								player,
-								hello.using_encoded(blake2_256),
-								new_ticket_info,
-							);
-							return Some((
-								new_ticket_info.ticket_type,
-								hello.using_encoded(blake2_256),
-							));
-						}
-					}
-					TicketType::Custom(CustomTicket::Sponsored(pool_id)) => {
-						if let Some(contract) = target {
-							let targets = Self::get_targets(pool_id);
-							if targets.contains(&contract) {
-								if let Some(new_ticket_info) = ticket_info.withdraw_ticket() {
-									Tickets::<T>::insert(player, pool_id, new_ticket_info);
-									return Some((new_ticket_info.ticket_type, pool_id));
-								}
-							}
-						}
-					}
-				}
-			}
-			None
+		fn use_ticket(
+			player: T::AccountId,
+			target: Option<TicketTarget>,
+		) -> Option<(TicketType, ID)> {
+			let (pool_id, ticket_info) = Self::select_ticket(&player, target)?;
+			let new_ticket_info = ticket_info.withdraw_ticket()?;
+			Tickets::<T>::insert(player.clone(), pool_id, new_ticket_info);
+
+			Self::deposit_event(Event::<T>::UsedTicket {
+				sender: player,
+				pool_id,
+				ticket: new_ticket_info.ticket_type,
+			});
+			Some((new_ticket_info.ticket_type, pool_id))
 		}
 
 		fn get_service(pool_id: ID) -> Option<Service> {
@@ -444,7 +845,8 @@ pub mod pallet {
 
 	impl<T: Config> MasterPool<T::AccountId> for Pallet<T> {
 		fn remove_player(player: &T::AccountId, pool_id: ID) {
-			Tickets::<T>::remove(&player, pool_id)
+			Tickets::<T>::remove(&player, pool_id);
+			Self::remove_preferred_pool(player, pool_id);
 		}
 
 		fn get_timeservice() -> u128 {
@@ -454,5 +856,17 @@ pub mod pallet {
 		fn get_marktime() -> u128 {
 			MarkTime::<T>::get()
 		}
+
+		fn get_timeservice_start() -> u128 {
+			TimeServiceStart::<T>::get()
+		}
+
+		fn change_pool(player: &T::AccountId, pool_id: ID, new_ticket: TicketType) {
+			let new_pool_id = match new_ticket {
+				TicketType::System(system_ticket) => system_ticket.using_encoded(blake2_256),
+				TicketType::Custom(CustomTicket::Sponsored(joined_pool_id)) => joined_pool_id,
+			};
+			let _ = Self::move_ticket(player, pool_id, new_pool_id, new_ticket);
+		}
 	}
 }
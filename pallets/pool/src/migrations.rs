@@ -0,0 +1,97 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// The tickets stored before the renewal epoch
+	#[derive(Decode)]
+	pub struct OldTicketInfo {
+		pub ticket_type: TicketType,
+		pub tickets: u32,
+	}
+
+	impl From<OldTicketInfo> for TicketInfo {
+		fn from(old: OldTicketInfo) -> Self {
+			TicketInfo { ticket_type: old.ticket_type, tickets: old.tickets, epoch: 0 }
+		}
+	}
+
+	/// Add the renewal epoch to the tickets of the players and to the tickets cached by
+	/// the `I` instance of pallet-cache, the tickets renewed before are refilled at the next
+	/// touch as they are in the epoch `0`
+	pub struct MigrateToEpochTickets<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T, I> OnRuntimeUpgrade for MigrateToEpochTickets<T, I>
+	where
+		T: Config + pallet_cache::Config<I, Data = TicketInfo>,
+		I: 'static,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut tickets = 0_u64;
+			Tickets::<T>::translate::<OldTicketInfo, _>(|_, _, old| {
+				tickets = tickets.saturating_add(1);
+				Some(old.into())
+			});
+			let cached = pallet_cache::Pallet::<T, I>::translate_data(TicketInfo::from);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			let entries = tickets.saturating_add(cached);
+			T::DbWeight::get().reads_writes(entries.saturating_add(1), entries.saturating_add(1))
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Store the players holding a sponsored ticket as the members of the sponsored pool,
+	/// the members are used to remove the tickets when the pool is closed
+	pub struct BackfillSponsoredMembers<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for BackfillSponsoredMembers<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1_u64;
+			let mut writes = 1_u64;
+			for (player, _, ticket) in Tickets::<T>::iter() {
+				reads = reads.saturating_add(1);
+				if let TicketType::Custom(CustomTicket::Sponsored(pool_id)) = ticket.ticket_type {
+					reads = reads.saturating_add(1);
+					if T::SponsoredPool::backfill_member(pool_id, &player) {
+						writes = writes.saturating_add(1);
+					}
+				}
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
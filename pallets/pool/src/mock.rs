@@ -5,7 +5,7 @@
 
 use crate::{self as pallet_pool};
 use codec::Encode;
-use frame_support::{parameter_types, traits::{ConstU32, GenesisBuild}};
+use frame_support::{parameter_types, traits::{ConstU128, ConstU32, GenesisBuild}};
 use frame_system as system;
 
 use frame_support::{
@@ -13,7 +13,7 @@ use frame_support::{
 	traits::{OnFinalize, OnInitialize},
 };
 use gafi_primitives::{
-	currency::{unit, NativeToken::GAKI},
+	currency::{unit, AssetId, NativeToken::GAKI},
 	ticket::{TicketInfo, TicketType, SystemTicket, TicketLevel},
 	system_services::{SystemService, SystemDefaultServices},
 	constant::ID
@@ -44,6 +44,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Pool: pallet_pool::{Pallet, Storage, Event<T>},
 		StakingPool: staking_pool::{Pallet, Storage, Event<T>},
@@ -66,7 +67,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -76,6 +77,23 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u128;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
 pub const INIT_TIMESTAMP: u64 = 30_000;
 pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
@@ -113,15 +131,25 @@ parameter_types! {
 	pub MaxTxLimit: u32 = 100;
 	pub MaxPoolOwned: u32 =  10;
 	pub MaxPoolTarget: u32 =  10;
+	pub MaxExpiringPools: u32 = 2;
+	pub CleanupBatch: u32 = 2;
+	pub MaxTargetSelector: u32 = 10;
+	pub MaxAllowlist: u32 = 5;
 }
 
 impl sponsored_pool::Config for Test {
 	type Event = Event;
-	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
+	type Assets = Assets;
+	type AssetConversion = ();
 	type PoolName = PoolNames;
+	type MasterPool = ();
 	type MaxPoolOwned = MaxPoolOwned;
 	type MaxPoolTarget = MaxPoolTarget;
+	type MaxExpiringPools = MaxExpiringPools;
+	type CleanupBatch = CleanupBatch;
+	type MaxTargetSelector = MaxTargetSelector;
+	type MaxAllowlist = MaxAllowlist;
 	type MinDiscountPercent = MinDiscountPercent;
 	type MaxDiscountPercent = MaxDiscountPercent;
 	type MinTxLimit = MinTxLimit;
@@ -157,6 +185,7 @@ impl pallet_pool::Config for Test {
 	type SponsoredPool = SponsoredPool;
 	type Cache = PalletCache;
 	type TimeServiceStorage = TimeServiceStorage;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
 }
 
 pub struct StakingPoolDefaultServices {}
@@ -180,11 +209,24 @@ impl SystemDefaultServices for StakingPoolDefaultServices {
 	}
 }
 
+parameter_types! {
+	pub UnbondingPeriod: u64 = 10;
+	pub MaxUnlockingChunks: u32 = 5;
+	pub MaxWaitlist: u32 = 5;
+	pub MaxBeneficiaries: u32 = 2;
+}
+
 impl staking_pool::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = ();
 	type StakingServices = StakingPoolDefaultServices;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
+	type MasterPool = ();
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxWaitlist = MaxWaitlist;
+	type MaxBeneficiaries = MaxBeneficiaries;
 }
 
 pub struct UpfrontPoolDefaultServices {}
@@ -210,6 +252,10 @@ impl SystemDefaultServices for UpfrontPoolDefaultServices {
 
 parameter_types! {
 	pub MaxPlayerStorage: u32 = 1000;
+	pub MaxChargePerBlock: u32 = 10;
+	pub GracePeriods: u32 = 1;
+	pub MaxPrepaidPeriods: u32 = 10;
+	pub MaxPrepaidTiers: u32 = 5;
 }
 
 impl upfront_pool::Config for Test {
@@ -219,6 +265,11 @@ impl upfront_pool::Config for Test {
 	type MaxPlayerStorage = MaxPlayerStorage;
 	type MasterPool = ();
 	type UpfrontServices = UpfrontPoolDefaultServices;
+	type MaxChargePerBlock = MaxChargePerBlock;
+	type GracePeriods = GracePeriods;
+	type MaxPrepaidPeriods = MaxPrepaidPeriods;
+	type MaxPrepaidTiers = MaxPrepaidTiers;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
 }
 
 parameter_types! {
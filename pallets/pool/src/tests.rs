@@ -1,13 +1,27 @@
-use crate::{mock::*, Error, Tickets};
-use frame_support::{assert_ok, traits::Currency};
+use crate::{
+    migrations::{v1::MigrateToEpochTickets, v2::BackfillSponsoredMembers},
+    mock::*,
+    Error, MarkTime, PreferredPool, Tickets,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{storage_prefix, unhashed},
+    traits::{Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+    StorageHasher, Twox64Concat,
+};
 use gafi_primitives::{
+    cache::Cache,
     constant::ID,
     currency::{unit, NativeToken::GAKI},
-    ticket::{CustomTicket, SystemTicket, TicketLevel, TicketType},
+    ticket::{
+        CustomTicket, PlayerTicket, SystemTicket, TicketInfo, TicketLevel, TicketTarget, TicketType,
+    },
 };
 use sp_core::H160;
-use sp_runtime::{AccountId32, Permill};
-use sponsored_pool::{PoolOwned, Pools};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::BadOrigin, AccountId32, Permill};
+use sponsored_pool::{PoolOwned, Pools, SponsoredBudget};
 use std::str::FromStr;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -73,6 +87,80 @@ fn leave_all_system_pool_works() {
     })
 }
 
+#[test]
+fn joined_pools_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        assert_eq!(Pool::joined_pools(&account), vec![]);
+
+        assert_ok!(Pool::join(Origin::signed(account.clone()), ticket));
+
+        let joined_pools = Pool::joined_pools(&account);
+        assert_eq!(joined_pools.len(), 1);
+        let (pool_id, ticket_info) = joined_pools[0];
+        assert_eq!(ticket_info.ticket_type, ticket);
+        assert_eq!(
+            Pool::tickets_remaining(&account, pool_id),
+            Some(ticket_info.tickets)
+        );
+        assert_eq!(Pool::tickets_remaining(&account, [0_u8; 32]), None);
+    })
+}
+
+#[test]
+fn next_renewal_in_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let now = Timestamp::get() as u128;
+        MarkTime::<Test>::put(now);
+        assert_eq!(Pool::next_renewal_in(), Pool::time_service());
+
+        run_to_block(10);
+        assert_eq!(
+            Pool::next_renewal_in(),
+            Pool::time_service() - 9 * MILLISECS_PER_BLOCK as u128
+        );
+
+        MarkTime::<Test>::put(0);
+        run_to_block(1000);
+        assert_eq!(Pool::next_renewal_in(), 0);
+    })
+}
+
+#[test]
+fn renew_tickets_lazily_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        assert_ok!(Pool::join(Origin::signed(account.clone()), ticket));
+
+        let (pool_id, ticket_info) = Pool::joined_pools(&account)[0];
+        let tx_limit = Pool::get_service(pool_id).unwrap().tx_limit;
+        assert_eq!(ticket_info.epoch, 0);
+        for _ in 0..tx_limit {
+            assert_eq!(Pool::use_ticket(account.clone(), None), Some((ticket, pool_id)));
+        }
+        assert_eq!(Pool::use_ticket(account.clone(), None), None);
+
+        Pool::renew_tickets();
+        assert_eq!(Pool::epoch(), 1);
+
+        // storage is untouched until the ticket is used
+        assert_eq!(Tickets::<Test>::get(account.clone(), pool_id).unwrap().tickets, 0);
+        assert_eq!(Pool::tickets_remaining(&account, pool_id), Some(tx_limit));
+
+        assert_eq!(Pool::use_ticket(account.clone(), None), Some((ticket, pool_id)));
+        let ticket_info = Tickets::<Test>::get(account.clone(), pool_id).unwrap();
+        assert_eq!(ticket_info.tickets, tx_limit - 1);
+        assert_eq!(ticket_info.epoch, 1);
+    })
+}
+
 fn create_pool(
     account: AccountId32,
     targets: Vec<H160>,
@@ -86,7 +174,9 @@ fn create_pool(
         targets,
         pool_value,
         discount,
-        tx_limit
+        tx_limit,
+        None,
+        None
     ));
 
     assert_eq!(
@@ -164,3 +254,461 @@ fn get_ticket_service_works() {
         assert_eq!(service.discount, Permill::from_percent(0));
     })
 }
+
+fn join_sponsored_pools(account: &AccountId32, player: &AccountId32, discounts: &[u8]) -> Vec<ID> {
+    let mut pool_ids = vec![];
+    for (i, discount) in discounts.iter().enumerate() {
+        run_to_block(i as u64 + 2);
+        let pool_id = create_pool(
+            account.clone(),
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(*discount as u32),
+        );
+        assert_ok!(Pool::join(
+            Origin::signed(player.clone()),
+            TicketType::Custom(CustomTicket::Sponsored(pool_id))
+        ));
+        pool_ids.push(pool_id);
+    }
+    pool_ids
+}
+
+#[test]
+fn use_ticket_prefers_sponsored_ticket_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let player = new_account([1_u8; 32], account_balance);
+        let target = TicketTarget::Contract(
+            H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+        );
+        let staking_ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        let staking_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+
+        assert_ok!(Pool::join(Origin::signed(player.clone()), staking_ticket));
+        let pool_ids = join_sponsored_pools(&account, &player, &[10, 70]);
+
+        // no target, only the system ticket can be used
+        assert_eq!(Pool::use_ticket(player.clone(), None), Some((staking_ticket, staking_id)));
+
+        // the sponsored ticket with the highest discount
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(target)),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[1])), pool_ids[1]))
+        );
+        System::assert_last_event(Event::Pool(crate::Event::UsedTicket {
+            sender: player.clone(),
+            pool_id: pool_ids[1],
+            ticket: TicketType::Custom(CustomTicket::Sponsored(pool_ids[1])),
+        }));
+    })
+}
+
+#[test]
+fn use_ticket_skips_paused_and_capped_pools_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let player = new_account([1_u8; 32], account_balance);
+        let target = TicketTarget::Contract(
+            H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+        );
+        let staking_ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        let staking_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+
+        assert_ok!(Pool::join(Origin::signed(player.clone()), staking_ticket));
+        let pool_ids = join_sponsored_pools(&account, &player, &[10, 70]);
+
+        // the pool with the highest discount can't fund the fee
+        SponsoredPool::insufficient_balance(pool_ids[1], account_balance);
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(target)),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[0])), pool_ids[0]))
+        );
+
+        // the other pool has spent the cap of the player
+        assert_ok!(SponsoredPool::set_budget(
+            Origin::signed(account.clone()),
+            pool_ids[0],
+            Some(unit(GAKI)),
+            None,
+            None
+        ));
+        SponsoredPool::spend(pool_ids[0], &player, unit(GAKI));
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(target)),
+            Some((staking_ticket, staking_id))
+        );
+    })
+}
+
+#[test]
+fn use_ticket_function_selector_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let player = new_account([1_u8; 32], account_balance);
+        let contract = H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap();
+        let move_selector = [0x0d, 0xc1, 0x3e, 0x87];
+        let withdraw_selector = [0x2e, 0x1a, 0x7d, 0x4d];
+
+        let pool_ids = join_sponsored_pools(&account, &player, &[10, 70]);
+        assert_ok!(SponsoredPool::set_target_selectors(
+            Origin::signed(account.clone()),
+            pool_ids[1],
+            contract,
+            vec![move_selector]
+        ));
+
+        // only the selected function is sponsored by the restricted pool
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(TicketTarget::Function(contract, move_selector))),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[1])), pool_ids[1]))
+        );
+        assert_eq!(
+            Pool::use_ticket(
+                player.clone(),
+                Some(TicketTarget::Function(contract, withdraw_selector))
+            ),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[0])), pool_ids[0]))
+        );
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(TicketTarget::Contract(contract))),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[0])), pool_ids[0]))
+        );
+    })
+}
+
+#[test]
+fn set_preferred_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let player = new_account([1_u8; 32], account_balance);
+        let target = TicketTarget::Contract(
+            H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+        );
+
+        let pool_ids = join_sponsored_pools(&account, &player, &[10, 70]);
+        assert_noop!(
+            Pool::set_preferred_pool(Origin::signed(player.clone()), Some([0_u8; 32])),
+            Error::<Test>::NotFoundInPool
+        );
+
+        assert_ok!(Pool::set_preferred_pool(Origin::signed(player.clone()), Some(pool_ids[0])));
+        assert_eq!(PreferredPool::<Test>::get(player.clone()), Some(pool_ids[0]));
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(target)),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[0])), pool_ids[0]))
+        );
+
+        // fall back when the preferred ticket runs out
+        for _ in 1..10 {
+            Pool::use_ticket(player.clone(), Some(target));
+        }
+        assert_eq!(Pool::tickets_remaining(&player, pool_ids[0]), Some(0));
+        assert_eq!(
+            Pool::use_ticket(player.clone(), Some(target)),
+            Some((TicketType::Custom(CustomTicket::Sponsored(pool_ids[1])), pool_ids[1]))
+        );
+
+        assert_ok!(Pool::leave(Origin::signed(player.clone()), pool_ids[0]));
+        assert_eq!(PreferredPool::<Test>::get(player.clone()), None);
+
+        assert_ok!(Pool::set_preferred_pool(Origin::signed(player.clone()), Some(pool_ids[1])));
+        assert_ok!(Pool::set_preferred_pool(Origin::signed(player.clone()), None));
+        assert_eq!(PreferredPool::<Test>::get(player.clone()), None);
+    })
+}
+
+#[test]
+fn change_level_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account = new_account([0_u8; 32], 1_000_000 * unit(GAKI));
+        let basic_ticket = TicketType::System(SystemTicket::Upfront(TicketLevel::Basic));
+        let advance_ticket = TicketType::System(SystemTicket::Upfront(TicketLevel::Advance));
+        let basic_id = SystemTicket::Upfront(TicketLevel::Basic).using_encoded(blake2_256);
+        let advance_id = SystemTicket::Upfront(TicketLevel::Advance).using_encoded(blake2_256);
+
+        assert_ok!(Pool::join(Origin::signed(account.clone()), basic_ticket));
+        assert_ok!(Pool::set_preferred_pool(Origin::signed(account.clone()), Some(basic_id)));
+        Pool::use_ticket(account.clone(), None);
+        Pool::use_ticket(account.clone(), None);
+
+        assert_ok!(Pool::change_level(Origin::signed(account.clone()), advance_ticket));
+        assert_eq!(Tickets::<Test>::get(account.clone(), basic_id), None);
+        assert_eq!(Pool::tickets_remaining(&account, advance_id), Some(8));
+        assert_eq!(PreferredPool::<Test>::get(account.clone()), Some(advance_id));
+        assert_eq!(
+            upfront_pool::Tickets::<Test>::get(account.clone()).unwrap().ticket_type,
+            advance_ticket
+        );
+        System::assert_last_event(Event::Pool(crate::Event::ChangedLevel {
+            sender: account.clone(),
+            ticket: advance_ticket,
+        }));
+    })
+}
+
+#[test]
+fn change_level_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account = new_account([0_u8; 32], 1_000_000 * unit(GAKI));
+        let basic_ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+
+        assert_noop!(
+            Pool::change_level(Origin::signed(account.clone()), basic_ticket),
+            Error::<Test>::NotFoundInPool
+        );
+        assert_ok!(Pool::join(Origin::signed(account.clone()), basic_ticket));
+        assert_noop!(
+            Pool::change_level(Origin::signed(account.clone()), basic_ticket),
+            Error::<Test>::AlreadyJoined
+        );
+        assert_noop!(
+            Pool::change_level(
+                Origin::signed(account.clone()),
+                TicketType::System(SystemTicket::Upfront(TicketLevel::Medium))
+            ),
+            Error::<Test>::NotFoundInPool
+        );
+        assert_noop!(
+            Pool::change_level(
+                Origin::signed(account.clone()),
+                TicketType::Custom(CustomTicket::Sponsored([0_u8; 32]))
+            ),
+            Error::<Test>::NotSystemTicket
+        );
+    })
+}
+
+#[test]
+fn leave_all_joined_pools_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let player = new_account([1_u8; 32], account_balance);
+        let staking_ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+
+        assert_ok!(Pool::join(Origin::signed(player.clone()), staking_ticket));
+        let pool_ids = join_sponsored_pools(&account, &player, &[10, 70]);
+        assert_eq!(Tickets::<Test>::iter_prefix_values(player.clone()).count(), 3);
+
+        assert_ok!(Pool::leave_all(Origin::signed(player.clone())));
+        assert_eq!(Tickets::<Test>::iter_prefix_values(player.clone()).count(), 0);
+        assert_eq!(staking_pool::Tickets::<Test>::get(player.clone()), None);
+        assert_eq!(Balances::reserved_balance(&player), 0);
+
+        let leaved_events = System::events()
+            .into_iter()
+            .filter(|record| matches!(record.event, Event::Pool(crate::Event::Leaved { .. })))
+            .count();
+        assert_eq!(leaved_events, 3);
+
+        // the sponsored tickets are cached
+        for pool_id in pool_ids {
+            let ticket = TicketType::Custom(CustomTicket::Sponsored(pool_id));
+            assert!(<PalletCache as Cache<AccountId32, TicketType, TicketInfo>>::get(
+                &player, ticket
+            )
+            .is_some());
+        }
+    })
+}
+
+#[test]
+fn set_time_service_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        MarkTime::<Test>::put(0);
+        let time_service = Pool::time_service();
+        let new_time_service = 60_000_u128;
+        let account = new_account([0_u8; 32], 1_000_000 * unit(GAKI));
+
+        assert_noop!(
+            Pool::set_time_service(Origin::signed(account), new_time_service),
+            BadOrigin
+        );
+        assert_noop!(
+            Pool::set_time_service(Origin::root(), 0),
+            Error::<Test>::InvalidTimeService
+        );
+
+        assert_ok!(Pool::set_time_service(Origin::root(), new_time_service));
+        assert_eq!(Pool::next_time_service(), Some(new_time_service));
+        System::assert_last_event(Event::Pool(crate::Event::TimeServiceScheduled {
+            time_service: new_time_service,
+            start_at: time_service,
+        }));
+
+        // keep the current period until the next MarkTime
+        Pool::on_finalize(1);
+        assert_eq!(Pool::time_service(), time_service);
+
+        Timestamp::set_timestamp(time_service as u64);
+        Pool::on_finalize(2);
+        assert_eq!(Pool::time_service(), new_time_service);
+        assert_eq!(Pool::next_time_service(), None);
+        assert_eq!(Pool::mark_time(), time_service);
+        assert_eq!(Pool::time_service_start(), time_service);
+        assert_eq!(Pool::epoch(), 1);
+        System::assert_last_event(Event::Pool(crate::Event::TimeServiceChanged {
+            time_service: new_time_service,
+        }));
+    })
+}
+
+#[test]
+fn join_for_staking_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let sponsor = new_account([0_u8; 32], account_balance);
+        let beneficiary = new_account([1_u8; 32], account_balance);
+        let ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        let pool_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+
+        assert_noop!(
+            Pool::join_for(
+                Origin::signed(sponsor.clone()),
+                beneficiary.clone(),
+                TicketType::System(SystemTicket::Upfront(TicketLevel::Basic)),
+            ),
+            Error::<Test>::NotSupportedTicket
+        );
+        assert_ok!(Pool::join_for(
+            Origin::signed(sponsor.clone()),
+            beneficiary.clone(),
+            ticket
+        ));
+
+        // the stake is reserved from the sponsor, the ticket is issued to the beneficiary
+        assert_eq!(
+            Balances::free_balance(&sponsor),
+            account_balance - 1000 * unit(GAKI)
+        );
+        assert_eq!(Balances::free_balance(&beneficiary), account_balance);
+        assert!(Tickets::<Test>::get(beneficiary.clone(), pool_id).is_some());
+        assert_eq!(StakingPool::beneficiaries(&sponsor).to_vec(), vec![beneficiary.clone()]);
+        System::assert_last_event(Event::Pool(crate::Event::JoinedFor {
+            sponsor: sponsor.clone(),
+            beneficiary: beneficiary.clone(),
+            pool_id,
+        }));
+    })
+}
+
+#[test]
+fn leave_for_staking_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let sponsor = new_account([0_u8; 32], account_balance);
+        let beneficiary = new_account([1_u8; 32], account_balance);
+        let other = new_account([2_u8; 32], account_balance);
+        let ticket = TicketType::System(SystemTicket::Staking(TicketLevel::Basic));
+        let pool_id = SystemTicket::Staking(TicketLevel::Basic).using_encoded(blake2_256);
+        assert_ok!(Pool::join_for(
+            Origin::signed(sponsor.clone()),
+            beneficiary.clone(),
+            ticket
+        ));
+
+        // only the sponsor can leave the ticket
+        assert_noop!(
+            Pool::leave(Origin::signed(beneficiary.clone()), pool_id),
+            Error::<Test>::SponsoredTicket
+        );
+        assert_ok!(Pool::leave_all(Origin::signed(beneficiary.clone())));
+        assert!(Tickets::<Test>::get(beneficiary.clone(), pool_id).is_some());
+        assert_noop!(
+            Pool::leave_for(Origin::signed(other), beneficiary.clone(), pool_id),
+            Error::<Test>::NotSponsor
+        );
+
+        assert_ok!(Pool::leave_for(
+            Origin::signed(sponsor.clone()),
+            beneficiary.clone(),
+            pool_id
+        ));
+        assert!(Tickets::<Test>::get(beneficiary.clone(), pool_id).is_none());
+        assert_eq!(StakingPool::beneficiaries(&sponsor).len(), 0);
+        // the stake is unbonded back to the sponsor
+        assert_eq!(StakingPool::unlocking(&sponsor)[0].amount, 1000 * unit(GAKI));
+    })
+}
+
+#[test]
+fn migrate_to_epoch_tickets_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let player = AccountId32::from([1_u8; 32]);
+        let pool_id = [2_u8; 32];
+        let ticket_type = TicketType::Custom(CustomTicket::Sponsored(pool_id));
+
+        // the tickets stored before the renewal epoch
+        unhashed::put(
+            &Tickets::<Test>::hashed_key_for(&player, pool_id),
+            &(ticket_type, 5_u32),
+        );
+        let cache_key = [
+            storage_prefix(b"PalletCache", b"DataLeft").to_vec(),
+            Twox64Concat::hash(&player.encode()),
+            Twox64Concat::hash(&ticket_type.encode()),
+        ]
+        .concat();
+        unhashed::put(&cache_key, &(ticket_type, 3_u32, PalletCache::get_timestamp()));
+        StorageVersion::new(0).put::<Pool>();
+        assert_eq!(Tickets::<Test>::get(&player, pool_id), None);
+
+        MigrateToEpochTickets::<Test>::on_runtime_upgrade();
+        assert_eq!(Pool::on_chain_storage_version(), 1);
+        assert_eq!(
+            Tickets::<Test>::get(&player, pool_id),
+            Some(TicketInfo { ticket_type, tickets: 5, epoch: 0 })
+        );
+        assert_eq!(
+            PalletCache::get(&player, ticket_type),
+            Some(TicketInfo { ticket_type, tickets: 3, epoch: 0 })
+        );
+    })
+}
+
+#[test]
+fn backfill_sponsored_members_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let owner = new_account([0_u8; 32], 1_000_000 * unit(GAKI));
+        let pool_id = create_pool(
+            owner,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let removed_pool_id = [9_u8; 32];
+        let player = AccountId32::from([1_u8; 32]);
+
+        // the sponsored tickets stored before the members
+        for id in [pool_id, removed_pool_id] {
+            let ticket_type = TicketType::Custom(CustomTicket::Sponsored(id));
+            Tickets::<Test>::insert(&player, id, TicketInfo { ticket_type, tickets: 5, epoch: 0 });
+        }
+        StorageVersion::new(1).put::<Pool>();
+        assert_eq!(SponsoredPool::members(pool_id, &player), None);
+
+        BackfillSponsoredMembers::<Test>::on_runtime_upgrade();
+        assert_eq!(Pool::on_chain_storage_version(), 2);
+        assert_eq!(SponsoredPool::members(pool_id, &player), Some(()));
+        assert_eq!(SponsoredPool::members(removed_pool_id, &player), None);
+    })
+}
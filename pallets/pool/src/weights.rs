@@ -36,6 +36,11 @@ pub trait WeightInfo {
 	fn join(s: u32, ticket: TicketType ) -> Weight;
 	fn leave(s: u32, ) -> Weight;
 	fn leave_all(s: u32, ) -> Weight;
+	fn set_preferred_pool() -> Weight;
+	fn change_level(s: u32, ticket: TicketType ) -> Weight;
+	fn set_time_service() -> Weight;
+	fn join_for() -> Weight;
+	fn leave_for() -> Weight;
 }
 
 /// Weight functions for `pallet_pool`.
@@ -162,14 +167,116 @@ impl<T: frame_system::Config> WeightInfo for PoolWeight<T> {
 	// Storage: UpfrontPool PlayerCount (r:1 w:1)
 	// Storage: UpfrontPool IngamePlayers (r:1 w:1)
 	// Storage: UpfrontPool NewPlayers (r:1 w:1)
-	// Storage: Pool Tickets (r:0 w:1)
+	// Storage: Pool Tickets (r:1 w:1)
+	// Storage: Pool PreferredPool (r:0 w:1)
 	// Storage: StakingPool Tickets (r:1 w:1)
 	// Storage: StakingPool PlayerCount (r:1 w:1)
 	// Storage: StakingPool Services (r:1 w:0)
-	fn leave_all(_s: u32, ) -> Weight {
-		(57_524_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(14 as Weight))
-			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	// Storage: PalletCache DataFlag (r:1 w:0)
+	// Storage: PalletCache DataLeft (r:0 w:1)
+	fn leave_all(s: u32, ) -> Weight {
+		(12_310_000 as Weight)
+			.saturating_add((45_214_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(s as Weight)))
+	}
+
+	// Storage: Pool Tickets (r:1 w:0)
+	// Storage: Pool PreferredPool (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_preferred_pool() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+
+	// Storage: Pool Tickets (r:1 w:2)
+	// Storage: Pool Epoch (r:1 w:0)
+	// Storage: Pool PreferredPool (r:1 w:1)
+	// Storage: UpfrontPool Tickets (r:1 w:1)
+	// Storage: UpfrontPool Services (r:2 w:0)
+	// Storage: Pool TimeService (r:1 w:0)
+	// Storage: Pool MarkTime (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: StakingPool Tickets (r:1 w:1)
+	// Storage: StakingPool Services (r:2 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn change_level(s: u32, ticket: TicketType) -> Weight {
+		let weight = (38_000_000 as Weight)
+			.saturating_add((2_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight));
+
+		match ticket {
+			TicketType::System(SystemTicket::Upfront(_)) => weight
+				.saturating_add(T::DbWeight::get().reads(6 as Weight))
+				.saturating_add(T::DbWeight::get().writes(1 as Weight)),
+			TicketType::System(SystemTicket::Staking(_)) => weight
+				.saturating_add(T::DbWeight::get().reads(3 as Weight))
+				.saturating_add(T::DbWeight::get().writes(1 as Weight)),
+			TicketType::Custom(CustomTicket::Sponsored(_)) => weight,
+		}
+	}
+
+	// Storage: Pool MarkTime (r:1 w:0)
+	// Storage: Pool TimeService (r:1 w:0)
+	// Storage: Pool NextTimeService (r:0 w:1)
+	fn set_time_service() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Pool Tickets (r:2 w:1)
+	// Storage: StakingPool Services (r:1 w:0)
+	// Storage: StakingPool RetiredServices (r:1 w:0)
+	// Storage: StakingPool Beneficiaries (r:1 w:1)
+	// Storage: StakingPool Sponsors (r:0 w:1)
+	// Storage: StakingPool MaxPlayer (r:1 w:0)
+	// Storage: StakingPool LevelPlayerCount (r:1 w:1)
+	// Storage: StakingPool Waitlist (r:1 w:0)
+	// Storage: StakingPool PlayerCount (r:1 w:1)
+	// Storage: StakingPool Reserved (r:0 w:1)
+	// Storage: StakingPool TotalStaked (r:1 w:1)
+	// Storage: StakingPool StakerRewards (r:1 w:1)
+	// Storage: StakingPool RewardPerStake (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: StakingPool Tickets (r:0 w:1)
+	fn join_for() -> Weight {
+		(68_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	// Storage: Pool Tickets (r:1 w:1)
+	// Storage: Pool PreferredPool (r:1 w:0)
+	// Storage: StakingPool Sponsors (r:1 w:1)
+	// Storage: StakingPool Beneficiaries (r:1 w:1)
+	// Storage: StakingPool Waitlisted (r:1 w:0)
+	// Storage: StakingPool Tickets (r:1 w:1)
+	// Storage: StakingPool PlayerCount (r:1 w:1)
+	// Storage: StakingPool Reserved (r:1 w:1)
+	// Storage: StakingPool Unlocking (r:1 w:1)
+	// Storage: StakingPool TotalStaked (r:1 w:1)
+	// Storage: StakingPool StakerRewards (r:1 w:1)
+	// Storage: StakingPool RewardPerStake (r:1 w:0)
+	// Storage: StakingPool LevelPlayerCount (r:1 w:1)
+	// Storage: StakingPool MaxPlayer (r:1 w:0)
+	// Storage: StakingPool Waitlist (r:1 w:0)
+	fn leave_for() -> Weight {
+		(61_208_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 }
 
@@ -229,8 +336,53 @@ impl WeightInfo for () {
 	}
 
 	fn leave_all(s: u32, ) -> Weight {
-		(57_524_000 as Weight).saturating_mul(s.into())
-			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+		(12_310_000 as Weight)
+			.saturating_add((45_214_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(s as Weight)))
+	}
+
+	fn set_preferred_pool() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn change_level(s: u32, ticket: TicketType) -> Weight {
+		let weight = (38_000_000 as Weight)
+			.saturating_add((2_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight));
+
+		match ticket {
+			TicketType::System(SystemTicket::Upfront(_)) => weight
+				.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+				.saturating_add(RocksDbWeight::get().writes(1 as Weight)),
+			TicketType::System(SystemTicket::Staking(_)) => weight
+				.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+				.saturating_add(RocksDbWeight::get().writes(1 as Weight)),
+			TicketType::Custom(CustomTicket::Sponsored(_)) => weight,
+		}
+	}
+
+	fn set_time_service() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn join_for() -> Weight {
+		(68_512_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+
+	fn leave_for() -> Weight {
+		(61_208_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
 	}
 }
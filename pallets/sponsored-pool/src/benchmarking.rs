@@ -6,13 +6,13 @@ use crate::Pallet as SponsoredPool;
 use crate::{Call, Config};
 use frame_benchmarking::Box;
 use frame_benchmarking::{account, benchmarks};
-use frame_support::traits::Currency;
+use frame_support::traits::{fungibles, Currency};
 use frame_system::RawOrigin;
 use scale_info::prelude::format;
 use scale_info::prelude::string::String;
 use sp_core::H160;
-use sp_std::{str::FromStr, vec};
-use sp_runtime::Permill;
+use sp_std::{str::FromStr, vec, vec::Vec};
+use sp_runtime::{FixedU128, Permill};
 
 const UNIT: u128 = 1_000_000_000_000_000_000u128;
 
@@ -29,7 +29,14 @@ fn new_funded_account<T: Config>(index: u32, seed: u32, amount: u128) -> T::Acco
 	return user;
 }
 
+const ASSET_ID: AssetId = 1;
+
 benchmarks! {
+	where_clause {
+		where
+			T::Assets: fungibles::Create<T::AccountId> + fungibles::Mutate<T::AccountId>,
+	}
+
 	create_pool {
 		let s in 0 .. 10 as u32;
 		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
@@ -39,8 +46,21 @@ benchmarks! {
 		let value = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-	}: _(RawOrigin::Signed(caller), targets, value, discount, tx_limit)
+	}: _(RawOrigin::Signed(caller), targets, value, discount, tx_limit, None, None)
 
+	create_asset_pool {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		T::AssetConversion::set_rate(ASSET_ID, FixedU128::from(1));
+		let value = T::AssetConversion::to_asset(ASSET_ID, 1000_u128 * UNIT).unwrap();
+		let _ = T::Assets::create(ASSET_ID, caller.clone(), true, 1);
+		let _ = T::Assets::mint_into(ASSET_ID, &caller, value.saturating_mul(2));
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+	}: _(RawOrigin::Signed(caller), targets, ASSET_ID, value, discount, tx_limit, None, None)
 
 	withdraw_pool {
 		let s in 0 .. 10 as u32;
@@ -51,10 +71,54 @@ benchmarks! {
 		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit);
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
 		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
 	}: _(RawOrigin::Signed(caller), pool_id)
 
+	deposit {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+		let amount = 500_u128 * UNIT;
+	}: _(RawOrigin::Signed(caller), pool_id, amount)
+
+	withdraw {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+		let amount = 500_u128 * UNIT;
+		let _ = SponsoredPool::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pool_id, amount);
+	}: _(RawOrigin::Signed(caller), pool_id, amount)
+
+	update_pool {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+		let discount = Permill::from_percent(50);
+		let tx_limit = 50_u32;
+	}: _(RawOrigin::Signed(caller), pool_id, discount, tx_limit)
+
 	new_targets {
 		let s in 0 .. 10 as u32;
 		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
@@ -64,7 +128,7 @@ benchmarks! {
 		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit);
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
 		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
 
 		let targets = vec![
@@ -72,6 +136,108 @@ benchmarks! {
 		];
 	}: _(RawOrigin::Signed(caller), pool_id, targets)
 
+	new_call_targets {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+
+		let call_targets = vec![(10_u8, 0_u8)];
+	}: _(RawOrigin::Signed(caller), pool_id, call_targets)
+
+	set_target_selectors {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let target = H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap();
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), vec![target], value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+
+		let selectors: Vec<[u8; 4]> = (0..T::MaxTargetSelector::get()).map(|i| i.to_be_bytes()).collect();
+	}: _(RawOrigin::Signed(caller), pool_id, target, selectors)
+
+	set_budget {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+	}: _(RawOrigin::Signed(caller), pool_id, Some(UNIT), Some(UNIT), Some(UNIT))
+
+	set_low_balance_threshold {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+	}: _(RawOrigin::Signed(caller), pool_id, Some(2000_u128 * UNIT))
+
+	set_allowlist {
+		let s in 0 .. T::MaxAllowlist::get();
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+
+		let accounts: Vec<T::AccountId> = (0..s).map(|i| account("player", i, s)).collect();
+	}: _(RawOrigin::Signed(caller), pool_id, accounts)
+
+	set_allowlist_root {
+		let s in 0 .. 10 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+	}: _(RawOrigin::Signed(caller), pool_id, Some(H256::repeat_byte(1)))
+
+	verify_allowlist {
+		let s in 0 .. 32 as u32;
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let discount = Permill::from_percent(30);
+		let tx_limit = 100_u32;
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+
+		let proof: Vec<H256> = (0..s).map(|i| H256::repeat_byte(i as u8)).collect();
+		let root = proof.iter().fold(H256::from(caller.using_encoded(blake2_256)), |node, sibling| {
+			let pair = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+			H256::from(pair.using_encoded(blake2_256))
+		});
+		let _ = SponsoredPool::<T>::set_allowlist_root(RawOrigin::Signed(caller.clone()).into(), pool_id, Some(root));
+	}: _(RawOrigin::Signed(caller), pool_id, proof)
+
 	set_pool_name {
 		let s in 0 .. 10 as u32;
 		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
@@ -81,7 +247,7 @@ benchmarks! {
 		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit);
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
 		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
 	}: _(RawOrigin::Signed(caller), pool_id, b"Test pool".to_vec())
 
@@ -94,7 +260,7 @@ benchmarks! {
 		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit);
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
 		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
 		let _ = SponsoredPool::<T>::set_pool_name(RawOrigin::Signed(caller.clone()).into(), pool_id, b"Test pool".to_vec());
 	}: _(RawOrigin::Signed(caller), pool_id)
@@ -108,8 +274,41 @@ benchmarks! {
 		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
 		let discount = Permill::from_percent(30);
 		let tx_limit = 100_u32;
-		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit);
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, discount, tx_limit, None, None);
 		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
 		let _ = SponsoredPool::<T>::set_pool_name(RawOrigin::Signed(caller.clone()).into(), pool_id, b"Test pool".to_vec());
 	}: _(RawOrigin::Root, pool_id)
+
+	expire_pools {
+		let s in 0 .. T::MaxExpiringPools::get();
+		let end_block: T::BlockNumber = (s + 1).into();
+		for i in 0 .. s {
+			frame_system::Pallet::<T>::set_block_number(i.into());
+			let caller = new_funded_account::<T>(i, s, 1000_000_000u128 * UNIT);
+			let targets = vec![
+				H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+			];
+			let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+			let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller).into(), targets, value, Permill::from_percent(30), 100_u32, Some(end_block), None);
+		}
+	}: {
+		SponsoredPool::<T>::expire_pools(end_block);
+	}
+
+	clean_closed_pools {
+		let s in 0 .. T::CleanupBatch::get();
+		let caller = new_funded_account::<T>(s, s, 1000_000_000u128 * UNIT);
+		let targets = vec![
+			H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap(),
+		];
+		let value: BalanceOf<T> = (1000_u128 * UNIT).try_into().ok().unwrap();
+		let _ = SponsoredPool::<T>::create_pool(RawOrigin::Signed(caller.clone()).into(), targets, value, Permill::from_percent(30), 100_u32, None, None);
+		let pool_id: ID = *PoolOwned::<T>::get(caller.clone()).last().unwrap();
+		for i in 0 .. s {
+			let _ = SponsoredPool::<T>::join(account("player", i, s), pool_id);
+		}
+		let _ = SponsoredPool::<T>::withdraw_pool(RawOrigin::Signed(caller).into(), pool_id);
+	}: {
+		SponsoredPool::<T>::clean_closed_pools(s);
+	}
 }
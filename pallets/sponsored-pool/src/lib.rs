@@ -21,25 +21,31 @@
 use crate::weights::WeightInfo;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement, Randomness, ReservableCurrency},
+	traits::{
+		fungible::Inspect, fungibles, Currency, ExistenceRequirement, ReservableCurrency,
+	},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
 pub use gafi_primitives::{
 	constant::ID,
-	custom_services::{CustomPool, CustomService},
+	currency::AssetId,
+	custom_services::{
+		sponsored_pool_account, sponsored_pool_id, AssetConversion, CustomPool, CustomService,
+		PoolIdSeed, SponsoredAsset, SponsoredBudget, SponsoredPoolInfo,
+	},
 	name::Name,
-	pool::Service,
+	pool::{MasterPool, Service},
 	ticket::TicketLevel,
 };
-use gu_convertor::{balance_try_to_u128, into_account};
+use gu_convertor::{balance_try_to_u128, into_account, u128_to_balance, u128_try_to_balance};
 use gu_currency::transfer_all;
 pub use pallet::*;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_io::hashing::blake2_256;
-use sp_runtime::Permill;
+use sp_runtime::{Permill, SaturatedConversion};
 use sp_std::vec::Vec;
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -54,6 +60,60 @@ pub struct SponsoredPool<AccountId> {
 	pub tx_limit: u32,
 }
 
+/// The spending caps of the sponsored pool in the currency of the pool, `None` means no cap
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct PoolBudget {
+	/// the maximum sponsored amount per player per period
+	pub player_cap: Option<u128>,
+	/// the maximum sponsored amount of the pool per period
+	pub pool_cap: Option<u128>,
+	/// the maximum sponsored fee per transaction
+	pub tx_cap: Option<u128>,
+}
+
+/// The amount sponsored in the period started at `marktime`
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct Spent {
+	pub amount: u128,
+	pub marktime: u128,
+}
+
+impl Spent {
+	/// The amount sponsored in the period started at `marktime`
+	pub fn current(&self, marktime: u128) -> u128 {
+		if self.marktime == marktime {
+			self.amount
+		} else {
+			0
+		}
+	}
+}
+
+/// The cap that stopped the pool sponsoring a transaction
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum BudgetCap {
+	Player,
+	Pool,
+	Transaction,
+}
+
+/// The new service of the pool that takes effect from the next ticket renewal
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct PoolUpdate {
+	pub discount: Permill,
+	pub tx_limit: u32,
+	/// the mark time of the period when the update was requested
+	pub marktime: u128,
+}
+
 #[cfg(test)]
 mod mock;
 
@@ -87,12 +147,18 @@ pub mod pallet {
 		/// The currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
-		/// To make the random pool id
-		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// The assets the sponsored pools can be funded with
+		type Assets: fungibles::Transfer<Self::AccountId, AssetId = AssetId, Balance = u128>;
+
+		/// Convert the native amounts into the asset of the pool
+		type AssetConversion: AssetConversion<Self::AccountId>;
 
 		/// Manage pool name
 		type PoolName: Name<Self::AccountId>;
 
+		/// To get the current period of the spending caps
+		type MasterPool: MasterPool<Self::AccountId>;
+
 		/// The minimum balance owner have to deposit when creating the pool
 		#[pallet::constant]
 		type MinPoolBalance: Get<u128>;
@@ -121,6 +187,22 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxPoolTarget: Get<u32>;
 
+		/// The maximum number of pools can expire at the same block
+		#[pallet::constant]
+		type MaxExpiringPools: Get<u32>;
+
+		/// The maximum number of player records of the closed pools removed per block
+		#[pallet::constant]
+		type CleanupBatch: Get<u32>;
+
+		/// The maximum number of function selectors can added to each contract address
+		#[pallet::constant]
+		type MaxTargetSelector: Get<u32>;
+
+		/// The maximum number of accounts can added to the pool allowlist
+		#[pallet::constant]
+		type MaxAllowlist: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -140,21 +222,134 @@ pub mod pallet {
 	pub type PoolOwned<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<ID, T::MaxPoolOwned>, ValueQuery>;
 
+	/// Holding the number of the pools created by the owner without a salt
+	#[pallet::storage]
+	#[pallet::getter(fn pool_nonce)]
+	pub type PoolNonce<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
 	/// Holding the contract addresses
 	#[pallet::storage]
 	pub(super) type Targets<T: Config> =
 		StorageMap<_, Twox64Concat, ID, BoundedVec<H160, T::MaxPoolTarget>, ValueQuery>;
 
+	/// Holding the pallet index and call index of the sponsored native extrinsics
+	#[pallet::storage]
+	pub(super) type CallTargets<T: Config> =
+		StorageMap<_, Twox64Concat, ID, BoundedVec<(u8, u8), T::MaxPoolTarget>, ValueQuery>;
+
+	/// Holding the block that the pool expires
+	#[pallet::storage]
+	#[pallet::getter(fn pool_end)]
+	pub type PoolEnd<T: Config> = StorageMap<_, Twox64Concat, ID, T::BlockNumber>;
+
+	/// Holding the pools expire at the block
+	#[pallet::storage]
+	pub(super) type Expiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<ID, T::MaxExpiringPools>,
+		ValueQuery,
+	>;
+
+	/// Holding the players joined the pool
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub(super) type Members<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ID, Twox64Concat, T::AccountId, ()>;
+
+	/// Holding the closed pools whose player records are being removed
+	#[pallet::storage]
+	pub(super) type ClosedPools<T: Config> = StorageMap<_, Twox64Concat, ID, ()>;
+
+	/// Holding the sponsored function selectors of the contract addresses,
+	/// the contract addresses without selectors are sponsored for any call
+	#[pallet::storage]
+	pub(super) type TargetSelectors<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		ID,
+		BoundedVec<(H160, BoundedVec<[u8; 4], T::MaxTargetSelector>), T::MaxPoolTarget>,
+		ValueQuery,
+	>;
+
+	/// Holding the spending caps of the pool
+	#[pallet::storage]
+	#[pallet::getter(fn budgets)]
+	pub type Budgets<T: Config> = StorageMap<_, Twox64Concat, ID, PoolBudget>;
+
+	/// Holding the asset of the pools funded with an asset instead of the native currency
+	#[pallet::storage]
+	pub(super) type PoolAsset<T: Config> = StorageMap<_, Twox64Concat, ID, AssetId>;
+
+	/// Holding the service update of the pool waiting for the next ticket renewal
+	#[pallet::storage]
+	#[pallet::getter(fn pending_update)]
+	pub type PendingUpdates<T: Config> = StorageMap<_, Twox64Concat, ID, PoolUpdate>;
+
+	/// Holding the amount the pool sponsored in the current period
+	#[pallet::storage]
+	pub(super) type PoolSpent<T: Config> = StorageMap<_, Twox64Concat, ID, Spent, ValueQuery>;
+
+	/// Holding the amount the pool sponsored for the player in the current period
+	#[pallet::storage]
+	pub(super) type PlayerSpent<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ID, Twox64Concat, T::AccountId, Spent, ValueQuery>;
+
+	/// Holding the balance of the pool that the sponsor wants to be alerted at
+	#[pallet::storage]
+	#[pallet::getter(fn low_balance_threshold)]
+	pub type LowBalanceThreshold<T: Config> = StorageMap<_, Twox64Concat, ID, u128>;
+
+	/// Holding the pools alerted for the low balance, until the balance is topped up
+	#[pallet::storage]
+	pub(super) type LowBalanceAlerted<T: Config> = StorageMap<_, Twox64Concat, ID, ()>;
+
+	/// Holding the paused pools with the sponsored fee that the pool could not fund
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	pub type Paused<T: Config> = StorageMap<_, Twox64Concat, ID, u128>;
+
+	/// Holding the accounts allowed to join the private pool
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist)]
+	pub type Allowlist<T: Config> =
+		StorageMap<_, Twox64Concat, ID, BoundedVec<T::AccountId, T::MaxAllowlist>, ValueQuery>;
+
+	/// Holding the merkle root of the accounts allowed to join the private pool
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_root)]
+	pub type AllowlistRoot<T: Config> = StorageMap<_, Twox64Concat, ID, H256>;
+
+	/// Holding the accounts verified against the merkle root, keyed with the verified root
+	#[pallet::storage]
+	pub(super) type Verified<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ID, Twox64Concat, T::AccountId, H256>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		CreatedPool { id: ID },
 		Withdrew { id: ID },
+		PoolExpired { id: ID },
+		Deposited { id: ID, amount: u128 },
+		WithdrewAmount { id: ID, amount: u128 },
+		PoolUpdated { id: ID, discount: Permill, tx_limit: u32 },
+		LowBalanceThresholdSet { id: ID, threshold: Option<u128> },
+		LowBalance { id: ID, balance: u128 },
+		PoolPaused { id: ID },
+		PoolResumed { id: ID },
+		TargetSelectorsSet { id: ID, target: H160 },
+		BudgetSet { id: ID, budget: PoolBudget },
+		CapReached { id: ID, player: T::AccountId, cap: BudgetCap },
+		AllowlistSet { id: ID },
+		AllowlistRootSet { id: ID, root: Option<H256> },
+		AllowlistVerified { id: ID, account: T::AccountId },
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Generate the pool id that duplicated
+		/// The pool id is taken by a pool or a closed pool being cleaned up
 		PoolIdExisted,
 		/// Can not convert pool id to account
 		IntoAccountFail,
@@ -169,6 +364,20 @@ pub mod pallet {
 		GreaterThanMaxTxLimit,
 		LessThanMinDiscountPercent,
 		GreaterThanMinDiscountPercent,
+		/// The end block must be greater than the current block
+		InvalidEndBlock,
+		ExceedMaxExpiringPools,
+		/// The contract address is not the target of the pool
+		TargetNotExist,
+		ExceedMaxTargetSelector,
+		ExceedMaxAllowlist,
+		/// The pool has no merkle root to verify against
+		AllowlistRootNotSet,
+		InvalidProof,
+		/// The account is not allowed to join the private pool
+		NotInAllowlist,
+		/// The asset has no approved rate to the native currency
+		AssetRateNotSet,
 	}
 
 	#[pallet::call]
@@ -183,6 +392,10 @@ pub mod pallet {
 		/// - `value`: the amount token deposit to the pool
 		/// - `discount`: transaction fee discount
 		/// - `tx_limit`: the number of discounted transaction per period of time
+		/// - `end_block`: the block that the pool expires and the remain balances are returned
+		///   to the owner, `None` means the pool never expires
+		/// - `salt`: the pool id is derived from the owner and the salt, or from the owner and
+		///   the number of the pools the owner created without a salt when it is `None`
 		///
 		/// Weight: `O(1)`
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_pool(50u32))]
@@ -193,114 +406,211 @@ pub mod pallet {
 			value: BalanceOf<T>,
 			discount: Permill,
 			tx_limit: u32,
+			end_block: Option<T::BlockNumber>,
+			salt: Option<H256>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let pool_config = Self::new_pool()?;
-			ensure!(
-				Pools::<T>::get(pool_config.id).is_none(),
-				<Error<T>>::PoolIdExisted
-			);
 			ensure!(
 				T::Currency::free_balance(&sender) > value,
 				pallet_balances::Error::<T>::InsufficientBalance
 			);
+			let value =
+				balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(value)?;
+			let service = Service { tx_limit, discount };
+			Self::do_create_pool(sender, targets, None, value, service, end_block, salt)
+		}
+
+		/// Create Asset Pool
+		///
+		/// Create new pool funded with the asset instead of the native currency,
+		/// the sponsored fees are paid with the asset at the approved rate,
+		/// the origin must be Signed
+		///
+		/// Parameters:
+		/// - `targets`: smart-contract addresses
+		/// - `asset`: the asset funding the pool
+		/// - `value`: the amount of the asset deposit to the pool
+		/// - `discount`: transaction fee discount
+		/// - `tx_limit`: the number of discounted transaction per period of time
+		/// - `end_block`: the block that the pool expires and the remain balances are returned
+		///   to the owner, `None` means the pool never expires
+		/// - `salt`: the pool id is derived from the owner and the salt, or from the owner and
+		///   the number of the pools the owner created without a salt when it is `None`
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_asset_pool(50u32))]
+		#[transactional]
+		#[allow(clippy::too_many_arguments)]
+		pub fn create_asset_pool(
+			origin: OriginFor<T>,
+			targets: Vec<H160>,
+			asset: AssetId,
+			value: u128,
+			discount: Permill,
+			tx_limit: u32,
+			end_block: Option<T::BlockNumber>,
+			salt: Option<H256>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let service = Service { tx_limit, discount };
+			Self::do_create_pool(sender, targets, Some(asset), value, service, end_block, salt)
+		}
+
+		/// Withdraw Pool
+		///
+		/// withdraw all the balances remain in the pool and destroy the pool,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_pool(50u32))]
+		#[transactional]
+		pub fn withdraw_pool(origin: OriginFor<T>, pool_id: ID) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
 			ensure!(
-				balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(value)?
-					>= T::MinPoolBalance::get(),
-				Error::<T>::NotReachMinPoolBalance
-			);
-			ensure!(
-				tx_limit >= T::MinTxLimit::get(),
-				Error::<T>::LessThanMinTxLimit
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
 			);
+
+			Self::close_pool(pool_id, &sender)?;
+			Self::deposit_event(Event::Withdrew { id: pool_id });
+			Ok(())
+		}
+
+		/// Deposit
+		///
+		/// top up an existing pool with amount of `amount`,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `amount`: the amount token deposit to the pool, in the currency of the pool
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::deposit(50u32))]
+		#[transactional]
+		pub fn deposit(origin: OriginFor<T>, pool_id: ID, amount: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
 			ensure!(
-				tx_limit <= T::MaxTxLimit::get(),
-				Error::<T>::GreaterThanMaxTxLimit
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
 			);
+			let pool_account =
+				into_account::<T::AccountId>(pool_id).ok_or(<Error<T>>::IntoAccountFail)?;
+
+			Self::transfer_value(PoolAsset::<T>::get(pool_id), &sender, &pool_account, amount)?;
+			Pools::<T>::try_mutate(pool_id, |pool| -> DispatchResult {
+				let pool = pool.as_mut().ok_or(<Error<T>>::PoolNotExist)?;
+				pool.value = pool.value.saturating_add(amount);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Deposited { id: pool_id, amount });
+			Self::check_low_balance(pool_id);
+			Self::try_resume(pool_id);
+			Ok(())
+		}
+
+		/// Withdraw
+		///
+		/// withdraw amount of `amount` from the pool while keeping the pool alive,
+		/// the balance remain in the pool must not be less than `MinPoolBalance`,
+		/// converted at the approved rate for the pool funded with an asset,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `amount`: the amount token withdraw from the pool, in the currency of the pool
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw(50u32))]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>, pool_id: ID, amount: u128) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
 			ensure!(
-				discount >= T::MinDiscountPercent::get(),
-				Error::<T>::LessThanMinDiscountPercent
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
 			);
+			let pool_account =
+				into_account::<T::AccountId>(pool_id).ok_or(<Error<T>>::IntoAccountFail)?;
+			let asset = PoolAsset::<T>::get(pool_id);
+			let pool_balance = Self::free_balance(asset, &pool_account)?;
+			let min_pool_balance = Self::min_pool_balance(asset)?;
 			ensure!(
-				discount <= T::MaxDiscountPercent::get(),
-				Error::<T>::GreaterThanMinDiscountPercent
+				pool_balance.checked_sub(amount).map_or(false, |remain| remain >= min_pool_balance),
+				Error::<T>::NotReachMinPoolBalance
 			);
-			ensure! {
-				Self::usize_try_to_u32(targets.len())? <= T::MaxPoolTarget::get(),
-				<Error<T>>::ExceedPoolTarget
-			}
-
-			let new_pool = SponsoredPool {
-				id: pool_config.id,
-				owner: sender.clone(),
-				value: balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(value)?,
-				discount,
-				tx_limit,
-			};
-
-			<T as pallet::Config>::Currency::transfer(
-				&sender,
-				&pool_config.account,
-				value,
-				ExistenceRequirement::KeepAlive,
-			)?;
 
-			PoolOwned::<T>::try_mutate(&sender, |pool_vec| pool_vec.try_push(pool_config.id))
-				.map_err(|_| <Error<T>>::ExceedMaxPoolOwned)?;
-			Targets::<T>::try_mutate(pool_config.id, |target_vec| {
-				for target in targets {
-					if target_vec.try_push(target).is_err() {
-						return Err(());
-					}
-				}
+			Self::transfer_value(asset, &pool_account, &sender, amount)?;
+			Pools::<T>::try_mutate(pool_id, |pool| -> DispatchResult {
+				let pool = pool.as_mut().ok_or(<Error<T>>::PoolNotExist)?;
+				pool.value = pool.value.saturating_sub(amount);
 				Ok(())
-			})
-			.map_err(|_| <Error<T>>::ExceedMaxPoolOwned)?;
-
-			Pools::<T>::insert(pool_config.id, new_pool);
+			})?;
 
-			Self::deposit_event(Event::CreatedPool { id: pool_config.id });
+			Self::deposit_event(Event::WithdrewAmount { id: pool_id, amount });
+			Self::check_low_balance(pool_id);
 			Ok(())
 		}
 
-		/// Withdraw Pool
+		/// Update Pool
 		///
-		/// withdraw all the balances remain in the pool and destroy the pool,
+		/// change the discount and the transaction limit of the pool, the change takes effect
+		/// from the next ticket renewal so the players keep the current service until then,
 		/// the origin as the owner of the pool must be Signed
 		///
 		/// Parameters:
 		/// - `pool_id`: the id of the pool
+		/// - `discount`: new transaction fee discount
+		/// - `tx_limit`: new number of discounted transaction per period of time
 		///
 		/// Weight: `O(1)`
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_pool(50u32))]
-		#[transactional]
-		pub fn withdraw_pool(origin: OriginFor<T>, pool_id: ID) -> DispatchResult {
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::update_pool(50u32))]
+		pub fn update_pool(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			discount: Permill,
+			tx_limit: u32,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
 			ensure!(
 				Self::is_pool_owner(&pool_id, &sender)?,
 				<Error<T>>::NotTheOwner
 			);
+			ensure!(
+				tx_limit >= T::MinTxLimit::get(),
+				Error::<T>::LessThanMinTxLimit
+			);
+			ensure!(
+				tx_limit <= T::MaxTxLimit::get(),
+				Error::<T>::GreaterThanMaxTxLimit
+			);
+			ensure!(
+				discount >= T::MinDiscountPercent::get(),
+				Error::<T>::LessThanMinDiscountPercent
+			);
+			ensure!(
+				discount <= T::MaxDiscountPercent::get(),
+				Error::<T>::GreaterThanMinDiscountPercent
+			);
 
-			if let Some(pool) = into_account::<T::AccountId>(pool_id) {
-				transfer_all::<T, <T as pallet::Config>::Currency>(&pool, &sender, false)?;
-				PoolOwned::<T>::try_mutate(&sender, |pool_owned| {
-					if let Some(ind) = pool_owned.iter().position(|&id| id == pool_id) {
-						pool_owned.swap_remove(ind);
-						return Ok(());
-					}
-					Err(())
-				})
-				.map_err(|_| <Error<T>>::PoolNotExist)?;
-				Pools::<T>::remove(pool_id);
-				Targets::<T>::remove(pool_id);
-				Self::deposit_event(Event::Withdrew { id: pool_id });
-				Ok(())
-			} else {
-				Err(Error::<T>::IntoAccountFail.into())
-			}
+			// fold the update already in effect before replacing it with the new one
+			Self::current_pool(pool_id).ok_or(<Error<T>>::PoolNotExist)?;
+			PendingUpdates::<T>::insert(
+				pool_id,
+				PoolUpdate { discount, tx_limit, marktime: T::MasterPool::get_marktime() },
+			);
+
+			Self::deposit_event(Event::PoolUpdated { id: pool_id, discount, tx_limit });
+			Ok(())
 		}
 
 		/// New Targets
@@ -341,58 +651,315 @@ pub mod pallet {
 			})
 			.map_err(|_| <Error<T>>::ExceedPoolTarget)?;
 
+			let targets = Targets::<T>::get(pool_id);
+			TargetSelectors::<T>::mutate(pool_id, |target_selectors| {
+				target_selectors.retain(|(target, _)| targets.contains(target))
+			});
+
 			Ok(())
 		}
 
-		/// Set a pool's name. The name should be a UTF-8-encoded string by convention, though
-		/// we don't check it. Fail if the pool is not exist or the origin is not the owner of the pool.
-		///
-		/// The name may not be more than `T::MaxLength` bytes, nor less than `T::MinLength` bytes which
-		/// defined in the name pallet's config.
+		/// New Call Targets
 		///
-		/// If the pool doesn't already have a name, then a fee of `ReservationFee` is reserved
-		/// in the account.
+		/// change the native extrinsics sponsored by the pool by replace old call targets
+		/// with the new one, the origin as the owner of the pool must be Signed
 		///
-		/// The dispatch origin for this call must be _Signed_.
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `call_targets`: new pairs of pallet index and call index
 		///
-		/// # <weight>
-		/// - O(1).
-		/// - At most one balance operation.
-		/// - One storage read/write.
-		/// - One event.
-		/// # </weight>
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_pool_name(50u32))]
-		pub fn set_pool_name(origin: OriginFor<T>, pool_id: ID, name: Vec<u8>) -> DispatchResult {
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::new_call_targets(50u32))]
+		pub fn new_call_targets(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			call_targets: Vec<(u8, u8)>,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
 			ensure!(
 				Self::is_pool_owner(&pool_id, &sender)?,
 				<Error<T>>::NotTheOwner
 			);
 
-			T::PoolName::set_name(sender, pool_id, name)?;
+			let call_targets: BoundedVec<(u8, u8), T::MaxPoolTarget> = call_targets
+				.try_into()
+				.map_err(|_| <Error<T>>::ExceedPoolTarget)?;
+			CallTargets::<T>::insert(pool_id, call_targets);
 
 			Ok(())
 		}
 
-		/// Clear a pool's name and return the deposit. Fails if the pool was not named, not exist
-		/// or the origin is not the owner of the pool.
+		/// Set Target Selectors
 		///
-		/// The dispatch origin for this call must be _Signed_.
+		/// restrict the sponsored calls to the contract address to the function selectors,
+		/// empty selectors sponsor any call to the contract address again,
+		/// the origin as the owner of the pool must be Signed
 		///
-		/// # <weight>
-		/// - O(1).
-		/// - One balance operation.
-		/// - One storage read/write.
-		/// - One event.
-		/// # </weight>
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::clear_pool_name(50u32))]
-		pub fn clear_pool_name(origin: OriginFor<T>, pool_id: ID) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-
-			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
-			ensure!(
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `target`: the smart-contract address, must be one of the pool targets
+		/// - `selectors`: the 4-byte function selectors
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_target_selectors(50u32))]
+		pub fn set_target_selectors(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			target: H160,
+			selectors: Vec<[u8; 4]>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+			ensure!(
+				Targets::<T>::get(pool_id).contains(&target),
+				<Error<T>>::TargetNotExist
+			);
+
+			let selectors: BoundedVec<[u8; 4], T::MaxTargetSelector> =
+				selectors.try_into().map_err(|_| <Error<T>>::ExceedMaxTargetSelector)?;
+			TargetSelectors::<T>::try_mutate(pool_id, |target_selectors| {
+				target_selectors.retain(|(contract, _)| *contract != target);
+				if selectors.is_empty() {
+					return Ok(());
+				}
+				target_selectors.try_push((target, selectors))
+			})
+			.map_err(|_| <Error<T>>::ExceedPoolTarget)?;
+
+			Self::deposit_event(Event::TargetSelectorsSet { id: pool_id, target });
+			Ok(())
+		}
+
+		/// Set Budget
+		///
+		/// cap the fee sponsored by the pool, the player pay the whole fee of the transaction
+		/// that hit any cap, the caps are reset every period of the pool service,
+		/// `None` remove the cap, the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `player_cap`: the maximum sponsored amount per player per period in the currency
+		///   of the pool
+		/// - `pool_cap`: the maximum sponsored amount of the pool per period in the currency of
+		///   the pool
+		/// - `tx_cap`: the maximum sponsored fee per transaction in the currency of the pool
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_budget(50u32))]
+		pub fn set_budget(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			player_cap: Option<u128>,
+			pool_cap: Option<u128>,
+			tx_cap: Option<u128>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+
+			let budget = PoolBudget { player_cap, pool_cap, tx_cap };
+			if budget == PoolBudget::default() {
+				Budgets::<T>::remove(pool_id);
+			} else {
+				Budgets::<T>::insert(pool_id, budget);
+			}
+
+			Self::deposit_event(Event::BudgetSet { id: pool_id, budget });
+			Ok(())
+		}
+
+		/// Set Low Balance Threshold
+		///
+		/// alert the sponsor with the `LowBalance` event once the balance of the pool falls
+		/// below the threshold, the alert is emitted again only after the pool is topped up,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `threshold`: the balance to be alerted at in the currency of the pool, `None` to
+		///   remove the alert
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_low_balance_threshold(50u32))]
+		pub fn set_low_balance_threshold(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			threshold: Option<u128>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+
+			LowBalanceAlerted::<T>::remove(pool_id);
+			match threshold {
+				Some(value) => LowBalanceThreshold::<T>::insert(pool_id, value),
+				None => LowBalanceThreshold::<T>::remove(pool_id),
+			}
+
+			Self::deposit_event(Event::LowBalanceThresholdSet { id: pool_id, threshold });
+			Self::check_low_balance(pool_id);
+			Ok(())
+		}
+
+		/// Set Allowlist
+		///
+		/// make the pool private by replace the allowlist with the new accounts,
+		/// the pool become public again when both the allowlist and the merkle root are empty,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `accounts`: the accounts allowed to join the pool
+		///
+		/// Weight: `O(n)` where n is the number of accounts
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_allowlist(accounts.len() as u32))]
+		pub fn set_allowlist(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			accounts: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+
+			let accounts: BoundedVec<T::AccountId, T::MaxAllowlist> =
+				accounts.try_into().map_err(|_| <Error<T>>::ExceedMaxAllowlist)?;
+			if accounts.is_empty() {
+				Allowlist::<T>::remove(pool_id);
+			} else {
+				Allowlist::<T>::insert(pool_id, accounts);
+			}
+
+			Self::deposit_event(Event::AllowlistSet { id: pool_id });
+			Ok(())
+		}
+
+		/// Set Allowlist Root
+		///
+		/// make the pool private by publish the merkle root of the allowed accounts,
+		/// the leaves are `blake2_256` hashes of the SCALE encoded accounts and each pair
+		/// of nodes is sorted before hashing, `None` remove the root,
+		/// the origin as the owner of the pool must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `root`: the merkle root of the allowed accounts
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_allowlist_root(50u32))]
+		pub fn set_allowlist_root(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			root: Option<H256>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+
+			AllowlistRoot::<T>::set(pool_id, root);
+
+			Self::deposit_event(Event::AllowlistRootSet { id: pool_id, root });
+			Ok(())
+		}
+
+		/// Verify Allowlist
+		///
+		/// prove the origin is one of the leaves of the pool merkle root so it can join the pool,
+		/// the verification is dropped when the owner publish a new root,
+		/// the origin must be Signed
+		///
+		/// Parameters:
+		/// - `pool_id`: the id of the pool
+		/// - `proof`: the sibling hashes from the leaf to the root
+		///
+		/// Weight: `O(n)` where n is the length of the proof
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::verify_allowlist(proof.len() as u32))]
+		pub fn verify_allowlist(
+			origin: OriginFor<T>,
+			pool_id: ID,
+			proof: Vec<H256>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pools::<T>::get(pool_id).is_some(), <Error<T>>::PoolNotExist);
+			let root = AllowlistRoot::<T>::get(pool_id).ok_or(<Error<T>>::AllowlistRootNotSet)?;
+			ensure!(
+				Self::verify_proof(&sender, &proof, root),
+				<Error<T>>::InvalidProof
+			);
+
+			Verified::<T>::insert(pool_id, &sender, root);
+
+			Self::deposit_event(Event::AllowlistVerified { id: pool_id, account: sender });
+			Ok(())
+		}
+
+		/// Set a pool's name. The name should be a UTF-8-encoded string by convention, though
+		/// we don't check it. Fail if the pool is not exist or the origin is not the owner of the pool.
+		///
+		/// The name may not be more than `T::MaxLength` bytes, nor less than `T::MinLength` bytes which
+		/// defined in the name pallet's config.
+		///
+		/// If the pool doesn't already have a name, then a fee of `ReservationFee` is reserved
+		/// in the account.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - At most one balance operation.
+		/// - One storage read/write.
+		/// - One event.
+		/// # </weight>
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_pool_name(50u32))]
+		pub fn set_pool_name(origin: OriginFor<T>, pool_id: ID, name: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
+			ensure!(
+				Self::is_pool_owner(&pool_id, &sender)?,
+				<Error<T>>::NotTheOwner
+			);
+
+			T::PoolName::set_name(sender, pool_id, name)?;
+
+			Ok(())
+		}
+
+		/// Clear a pool's name and return the deposit. Fails if the pool was not named, not exist
+		/// or the origin is not the owner of the pool.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - One balance operation.
+		/// - One storage read/write.
+		/// - One event.
+		/// # </weight>
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::clear_pool_name(50u32))]
+		pub fn clear_pool_name(origin: OriginFor<T>, pool_id: ID) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Pools::<T>::get(pool_id) != None, <Error<T>>::PoolNotExist);
+			ensure!(
 				Self::is_pool_owner(&pool_id, &sender)?,
 				<Error<T>>::NotTheOwner
 			);
@@ -427,22 +994,156 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
-		fn gen_id() -> Result<ID, Error<T>> {
-			let payload = (
-				T::Randomness::random(&b""[..]).0,
-				<frame_system::Pallet<T>>::block_number(),
+		/// Create the pool funded with the `asset`, or with the native currency when `asset` is
+		/// `None`
+		fn do_create_pool(
+			sender: T::AccountId,
+			targets: Vec<H160>,
+			asset: Option<AssetId>,
+			value: u128,
+			service: Service,
+			end_block: Option<T::BlockNumber>,
+			salt: Option<H256>,
+		) -> DispatchResult {
+			let Service { tx_limit, discount } = service;
+			let pool_config = Self::new_pool(&sender, salt)?;
+			ensure!(
+				Pools::<T>::get(pool_config.id).is_none() &&
+					!ClosedPools::<T>::contains_key(pool_config.id),
+				<Error<T>>::PoolIdExisted
+			);
+			ensure!(
+				value >= Self::min_pool_balance(asset)?,
+				Error::<T>::NotReachMinPoolBalance
+			);
+			ensure!(
+				tx_limit >= T::MinTxLimit::get(),
+				Error::<T>::LessThanMinTxLimit
+			);
+			ensure!(
+				tx_limit <= T::MaxTxLimit::get(),
+				Error::<T>::GreaterThanMaxTxLimit
 			);
-			Ok(payload.using_encoded(blake2_256))
+			ensure!(
+				discount >= T::MinDiscountPercent::get(),
+				Error::<T>::LessThanMinDiscountPercent
+			);
+			ensure!(
+				discount <= T::MaxDiscountPercent::get(),
+				Error::<T>::GreaterThanMinDiscountPercent
+			);
+			ensure! {
+				Self::usize_try_to_u32(targets.len())? <= T::MaxPoolTarget::get(),
+				<Error<T>>::ExceedPoolTarget
+			}
+			ensure!(
+				end_block.map_or(true, |end| end > <frame_system::Pallet<T>>::block_number()),
+				Error::<T>::InvalidEndBlock
+			);
+
+			let new_pool = SponsoredPool {
+				id: pool_config.id,
+				owner: sender.clone(),
+				value,
+				discount,
+				tx_limit,
+			};
+
+			if asset.is_some() {
+				// the pool account needs the native existential deposit to hold the asset
+				<T as pallet::Config>::Currency::transfer(
+					&sender,
+					&pool_config.account,
+					<T as pallet::Config>::Currency::minimum_balance(),
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			Self::transfer_value(asset, &sender, &pool_config.account, value)?;
+
+			PoolOwned::<T>::try_mutate(&sender, |pool_vec| pool_vec.try_push(pool_config.id))
+				.map_err(|_| <Error<T>>::ExceedMaxPoolOwned)?;
+			Targets::<T>::try_mutate(pool_config.id, |target_vec| {
+				for target in targets {
+					if target_vec.try_push(target).is_err() {
+						return Err(());
+					}
+				}
+				Ok(())
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxPoolOwned)?;
+
+			Pools::<T>::insert(pool_config.id, new_pool);
+			if let Some(end_block) = end_block {
+				Expiring::<T>::try_mutate(end_block, |pool_ids| pool_ids.try_push(pool_config.id))
+					.map_err(|_| <Error<T>>::ExceedMaxExpiringPools)?;
+				PoolEnd::<T>::insert(pool_config.id, end_block);
+			}
+			if let Some(asset) = asset {
+				PoolAsset::<T>::insert(pool_config.id, asset);
+			}
+			if salt.is_none() {
+				PoolNonce::<T>::mutate(&sender, |nonce| *nonce = nonce.saturating_add(1));
+			}
+
+			Self::deposit_event(Event::CreatedPool { id: pool_config.id });
+			Ok(())
+		}
+
+		/// The minimum balance of the pool in the currency of the pool
+		fn min_pool_balance(asset: Option<AssetId>) -> Result<u128, Error<T>> {
+			match asset {
+				Some(asset) => T::AssetConversion::to_asset(asset, T::MinPoolBalance::get())
+					.ok_or(<Error<T>>::AssetRateNotSet),
+				None => Ok(T::MinPoolBalance::get()),
+			}
 		}
 
-		pub(super) fn new_pool() -> Result<NewPool<T::AccountId>, Error<T>> {
-			let id = Self::gen_id()?;
-			match T::AccountId::decode(&mut &id[..]) {
-				Ok(account) => Ok(NewPool::<T::AccountId> { id, account }),
-				Err(_) => Err(<Error<T>>::IntoAccountFail),
+		/// Transfer `value` in the asset, or in the native currency when `asset` is `None`,
+		/// keeping the source alive
+		fn transfer_value(
+			asset: Option<AssetId>,
+			source: &T::AccountId,
+			dest: &T::AccountId,
+			value: u128,
+		) -> DispatchResult {
+			match asset {
+				Some(asset) => T::Assets::transfer(asset, source, dest, value, true).map(|_| ()),
+				None => <T as pallet::Config>::Currency::transfer(
+					source,
+					dest,
+					u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(value)?,
+					ExistenceRequirement::KeepAlive,
+				),
 			}
 		}
 
+		/// The free balance of the account in the asset, or in the native currency
+		fn free_balance(
+			asset: Option<AssetId>,
+			account: &T::AccountId,
+		) -> Result<u128, DispatchError> {
+			match asset {
+				Some(asset) => Ok(T::Assets::balance(asset, account)),
+				None => balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(
+					T::Currency::free_balance(account),
+				),
+			}
+		}
+
+		/// The id and the account of the next pool the `owner` creates with the `salt`
+		pub fn new_pool(
+			owner: &T::AccountId,
+			salt: Option<H256>,
+		) -> Result<NewPool<T::AccountId>, Error<T>> {
+			let seed = match salt {
+				Some(salt) => PoolIdSeed::Salt(salt),
+				None => PoolIdSeed::Nonce(PoolNonce::<T>::get(owner)),
+			};
+			let id = sponsored_pool_id(owner, seed);
+			let account = sponsored_pool_account(&id).ok_or(<Error<T>>::IntoAccountFail)?;
+			Ok(NewPool::<T::AccountId> { id, account })
+		}
+
 		fn usize_try_to_u32(input: usize) -> Result<u32, Error<T>> {
 			match input.try_into().ok() {
 				Some(val) => Ok(val),
@@ -456,29 +1157,337 @@ pub mod pallet {
 				None => Err(<Error<T>>::PoolNotExist),
 			}
 		}
+
+		/// Get the pool with the pending update applied once the tickets have been renewed,
+		/// the update in effect is folded into `Pools` and removed on the first read
+		pub(super) fn current_pool(pool_id: ID) -> Option<SponsoredPool<T::AccountId>> {
+			let mut pool = Pools::<T>::get(pool_id)?;
+			if let Some(update) = PendingUpdates::<T>::get(pool_id) {
+				if update.marktime != T::MasterPool::get_marktime() {
+					pool.discount = update.discount;
+					pool.tx_limit = update.tx_limit;
+					Pools::<T>::insert(pool_id, pool);
+					PendingUpdates::<T>::remove(pool_id);
+				}
+			}
+			Some(pool)
+		}
+
+		/// Whether the pool is private (has an allowlist or a merkle root)
+		pub fn is_private(pool_id: &ID) -> bool {
+			Allowlist::<T>::contains_key(pool_id) || AllowlistRoot::<T>::contains_key(pool_id)
+		}
+
+		/// Whether the account can join the pool, public pool allow everyone
+		pub fn is_allowed(pool_id: &ID, account: &T::AccountId) -> bool {
+			if !Self::is_private(pool_id) {
+				return true;
+			}
+			if Allowlist::<T>::get(pool_id).contains(account) {
+				return true;
+			}
+			match AllowlistRoot::<T>::get(pool_id) {
+				Some(root) => Verified::<T>::get(pool_id, account) == Some(root),
+				None => false,
+			}
+		}
+
+		pub(super) fn verify_proof(account: &T::AccountId, proof: &[H256], root: H256) -> bool {
+			let leaf = H256::from(account.using_encoded(blake2_256));
+			let computed = proof.iter().fold(leaf, |node, sibling| {
+				let pair = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+				H256::from(pair.using_encoded(blake2_256))
+			});
+			computed == root
+		}
+
+		/// Return the remain balances to the owner and remove the pool,
+		/// the player records are removed later by `clean_closed_pools`
+		fn close_pool(pool_id: ID, owner: &T::AccountId) -> DispatchResult {
+			let pool = into_account::<T::AccountId>(pool_id).ok_or(<Error<T>>::IntoAccountFail)?;
+			if let Some(asset) = PoolAsset::<T>::get(pool_id) {
+				let balance = T::Assets::reducible_balance(asset, &pool, false);
+				T::Assets::transfer(asset, &pool, owner, balance, false)?;
+			}
+			transfer_all::<T, <T as pallet::Config>::Currency>(&pool, owner, false)?;
+
+			PoolOwned::<T>::mutate(owner, |pool_owned| pool_owned.retain(|id| *id != pool_id));
+			Pools::<T>::remove(pool_id);
+			Targets::<T>::remove(pool_id);
+			CallTargets::<T>::remove(pool_id);
+			TargetSelectors::<T>::remove(pool_id);
+			PoolAsset::<T>::remove(pool_id);
+			PendingUpdates::<T>::remove(pool_id);
+			LowBalanceThreshold::<T>::remove(pool_id);
+			LowBalanceAlerted::<T>::remove(pool_id);
+			Paused::<T>::remove(pool_id);
+			Budgets::<T>::remove(pool_id);
+			PoolSpent::<T>::remove(pool_id);
+			Allowlist::<T>::remove(pool_id);
+			AllowlistRoot::<T>::remove(pool_id);
+			if let Some(end_block) = PoolEnd::<T>::take(pool_id) {
+				Expiring::<T>::mutate(end_block, |pool_ids| pool_ids.retain(|id| *id != pool_id));
+			}
+			ClosedPools::<T>::insert(pool_id, ());
+			Ok(())
+		}
+
+		/// Close the pools expire at the block, return the number of the expired pools
+		pub(super) fn expire_pools(block_number: T::BlockNumber) -> u32 {
+			let pool_ids = Expiring::<T>::take(block_number);
+			for pool_id in pool_ids.iter() {
+				if let Some(pool) = Pools::<T>::get(pool_id) {
+					if Self::close_pool(*pool_id, &pool.owner).is_ok() {
+						Self::deposit_event(Event::PoolExpired { id: *pool_id });
+					}
+				}
+			}
+			pool_ids.len() as u32
+		}
+
+		/// Remove at most `limit` tickets and spending records of the players
+		/// of the closed pools, return the number of the removed records
+		pub(super) fn clean_closed_pools(limit: u32) -> u32 {
+			let mut removed = 0_u32;
+			while removed < limit {
+				let pool_id = match ClosedPools::<T>::iter_keys().next() {
+					Some(pool_id) => pool_id,
+					None => break,
+				};
+				let batch = limit.saturating_sub(removed) as usize;
+
+				let members: Vec<T::AccountId> =
+					Members::<T>::iter_key_prefix(pool_id).take(batch).collect();
+				for player in members.iter() {
+					T::MasterPool::remove_player(player, pool_id);
+					Members::<T>::remove(pool_id, player);
+				}
+				let spenders: Vec<T::AccountId> = PlayerSpent::<T>::iter_key_prefix(pool_id)
+					.take(batch.saturating_sub(members.len()))
+					.collect();
+				for player in spenders.iter() {
+					PlayerSpent::<T>::remove(pool_id, player);
+				}
+				let verified: Vec<T::AccountId> = Verified::<T>::iter_key_prefix(pool_id)
+					.take(batch.saturating_sub(members.len()).saturating_sub(spenders.len()))
+					.collect();
+				for account in verified.iter() {
+					Verified::<T>::remove(pool_id, account);
+				}
+
+				let count = members
+					.len()
+					.saturating_add(spenders.len())
+					.saturating_add(verified.len());
+				if count < batch {
+					ClosedPools::<T>::remove(pool_id);
+				}
+				removed = removed.saturating_add(count as u32);
+			}
+			removed
+		}
+
+		/// The balance of the pool account that can fund the sponsored fees,
+		/// in the currency of the pool
+		fn pool_balance(pool_id: ID) -> u128 {
+			let account = match into_account::<T::AccountId>(pool_id) {
+				Some(account) => account,
+				None => return 0,
+			};
+			match PoolAsset::<T>::get(pool_id) {
+				Some(asset) => T::Assets::reducible_balance(asset, &account, true),
+				None => pallet_balances::Pallet::<T>::reducible_balance(&account, true)
+					.saturated_into::<u128>(),
+			}
+		}
+
+		/// The native sponsored `fee` in the currency of the pool,
+		/// the asset without an approved rate can not fund any fee
+		fn pool_fee(pool_id: ID, fee: u128) -> u128 {
+			match PoolAsset::<T>::get(pool_id) {
+				Some(asset) => T::AssetConversion::to_asset(asset, fee).unwrap_or(u128::MAX),
+				None => fee,
+			}
+		}
+
+		/// Alert once the balance of the pool falls below the threshold
+		fn check_low_balance(pool_id: ID) {
+			let threshold = match LowBalanceThreshold::<T>::get(pool_id) {
+				Some(threshold) => threshold,
+				None => return,
+			};
+			let balance = Self::pool_balance(pool_id);
+			if balance >= threshold {
+				LowBalanceAlerted::<T>::remove(pool_id);
+			} else if !LowBalanceAlerted::<T>::contains_key(pool_id) {
+				LowBalanceAlerted::<T>::insert(pool_id, ());
+				Self::deposit_event(Event::LowBalance { id: pool_id, balance });
+			}
+		}
+
+		/// Stop sponsoring until the pool can fund the `fee` again
+		fn pause(pool_id: ID, fee: u128) {
+			if !Paused::<T>::contains_key(pool_id) {
+				Paused::<T>::insert(pool_id, fee);
+				Self::deposit_event(Event::PoolPaused { id: pool_id });
+			}
+		}
+
+		/// Resume the paused pool once it can fund the fee that paused it,
+		/// return whether the pool is sponsoring
+		fn try_resume(pool_id: ID) -> bool {
+			match Paused::<T>::get(pool_id) {
+				Some(fee) if Self::pool_balance(pool_id) < Self::pool_fee(pool_id, fee) => false,
+				Some(_) => {
+					Paused::<T>::remove(pool_id);
+					Self::deposit_event(Event::PoolResumed { id: pool_id });
+					true
+				},
+				None => true,
+			}
+		}
+
+		/// Get the cap hit by the sponsored fee of the player, the caps are compared in the
+		/// currency of the pool
+		fn reached_cap(pool_id: ID, player: &T::AccountId, fee: u128) -> Option<BudgetCap> {
+			let budget = Budgets::<T>::get(pool_id)?;
+			let fee = Self::pool_fee(pool_id, fee);
+			let marktime = T::MasterPool::get_marktime();
+			let exceed = |cap: Option<u128>, spent: u128| {
+				cap.map_or(false, |cap| spent.saturating_add(fee) > cap)
+			};
+
+			if exceed(budget.tx_cap, 0) {
+				return Some(BudgetCap::Transaction);
+			}
+			if exceed(budget.player_cap, PlayerSpent::<T>::get(pool_id, player).current(marktime)) {
+				return Some(BudgetCap::Player);
+			}
+			if exceed(budget.pool_cap, PoolSpent::<T>::get(pool_id).current(marktime)) {
+				return Some(BudgetCap::Pool);
+			}
+			None
+		}
+
+		fn target_selectors(pool_id: ID) -> Vec<(H160, Vec<[u8; 4]>)> {
+			TargetSelectors::<T>::get(pool_id)
+				.iter()
+				.map(|(target, selectors)| (*target, selectors.to_vec()))
+				.collect()
+		}
+
+		/// Query the pool detail including the free balance of the pool account,
+		/// in the currency of the pool
+		pub fn pool_info(pool_id: ID) -> Option<SponsoredPoolInfo<T::AccountId, BalanceOf<T>>> {
+			let pool = Self::current_pool(pool_id)?;
+			let asset = PoolAsset::<T>::get(pool_id);
+			let balance = into_account::<T::AccountId>(pool_id)
+				.and_then(|account| Self::free_balance(asset, &account).ok())
+				.unwrap_or_default();
+
+			Some(SponsoredPoolInfo {
+				id: pool.id,
+				owner: pool.owner,
+				service: Service {
+					tx_limit: pool.tx_limit,
+					discount: pool.discount,
+				},
+				targets: Targets::<T>::get(pool_id).to_vec(),
+				call_targets: CallTargets::<T>::get(pool_id).to_vec(),
+				selectors: Self::target_selectors(pool_id),
+				asset,
+				balance: u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(balance),
+				paused: <Self as CustomPool<T::AccountId>>::is_paused(pool_id),
+			})
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+			let expired = Self::expire_pools(block_number);
+			let removed = Self::clean_closed_pools(T::CleanupBatch::get());
+
+			<T as pallet::Config>::WeightInfo::expire_pools(expired)
+				.saturating_add(<T as pallet::Config>::WeightInfo::clean_closed_pools(removed))
+		}
+
 		fn integrity_test() {
 			assert!(T::MinDiscountPercent::get() <= T::MaxDiscountPercent::get());
 		}
 	}
 
+	impl<T: Config> SponsoredBudget<T::AccountId> for Pallet<T> {
+		fn check_budget(pool_id: ID, player: &T::AccountId, fee: u128) -> bool {
+			// the balance may be topped up by a transfer to the pool account
+			if !Self::try_resume(pool_id) {
+				return false;
+			}
+			match Self::reached_cap(pool_id, player, fee) {
+				Some(cap) => {
+					Self::deposit_event(Event::CapReached {
+						id: pool_id,
+						player: player.clone(),
+						cap,
+					});
+					false
+				},
+				None => true,
+			}
+		}
+
+		fn spend(pool_id: ID, player: &T::AccountId, fee: u128) {
+			// the spending is only tracked for the pool with caps
+			if Budgets::<T>::contains_key(pool_id) {
+				let marktime = T::MasterPool::get_marktime();
+				let pool_fee = Self::pool_fee(pool_id, fee);
+				let add_fee = |spent: &mut Spent| {
+					*spent =
+						Spent { amount: spent.current(marktime).saturating_add(pool_fee), marktime };
+				};
+				PoolSpent::<T>::mutate(pool_id, add_fee);
+				PlayerSpent::<T>::mutate(pool_id, player, add_fee);
+			}
+
+			Self::check_low_balance(pool_id);
+			// the pool can not fund another transaction like this one
+			if Self::pool_balance(pool_id) < Self::pool_fee(pool_id, fee) {
+				Self::pause(pool_id, fee);
+			}
+		}
+
+		fn insufficient_balance(pool_id: ID, fee: u128) {
+			Self::check_low_balance(pool_id);
+			Self::pause(pool_id, fee);
+		}
+	}
+
+	impl<T: Config> SponsoredAsset for Pallet<T> {
+		fn pool_asset(pool_id: ID) -> Option<AssetId> {
+			PoolAsset::<T>::get(pool_id)
+		}
+	}
+
 	impl<T: Config> CustomPool<T::AccountId> for Pallet<T> {
-		fn join(_sender: T::AccountId, pool_id: ID) -> DispatchResult {
+		fn join(sender: T::AccountId, pool_id: ID) -> DispatchResult {
 			ensure!(Pools::<T>::get(pool_id).is_some(), Error::<T>::PoolNotExist);
+			ensure!(Self::is_allowed(&pool_id, &sender), Error::<T>::NotInAllowlist);
+			Members::<T>::insert(pool_id, sender, ());
 			Ok(())
 		}
-		fn leave(_sender: T::AccountId) -> DispatchResult {
+		fn leave(sender: T::AccountId, pool_id: ID) -> DispatchResult {
+			Members::<T>::remove(pool_id, sender);
 			Ok(())
 		}
 
 		fn get_service(pool_id: ID) -> Option<CustomService<T::AccountId>> {
-			if let Some(pool) = Pools::<T>::get(pool_id) {
+			if let Some(pool) = Self::current_pool(pool_id) {
 				let targets = Targets::<T>::get(pool_id);
+				let call_targets = CallTargets::<T>::get(pool_id);
 				return Some(CustomService::new(
 					targets.to_vec(),
+					call_targets.to_vec(),
+					Self::target_selectors(pool_id),
 					pool.tx_limit,
 					pool.discount,
 					pool.owner,
@@ -487,6 +1496,27 @@ pub mod pallet {
 			None
 		}
 
+		fn is_paused(pool_id: ID) -> bool {
+			// the pool topped up is resumed when it sponsors the next transaction
+			matches!(
+				Paused::<T>::get(pool_id),
+				Some(fee) if Self::pool_balance(pool_id) < Self::pool_fee(pool_id, fee)
+			)
+		}
+
+		fn is_capped(pool_id: ID, player: &T::AccountId) -> bool {
+			// even the smallest fee exceeds the cap
+			Self::reached_cap(pool_id, player, 1).is_some()
+		}
+
+		fn backfill_member(pool_id: ID, player: &T::AccountId) -> bool {
+			if !Pools::<T>::contains_key(pool_id) {
+				return false;
+			}
+			Members::<T>::insert(pool_id, player, ());
+			true
+		}
+
 		/// Add new sponsored-pool with default values, return pool_id
 		///
 		/// ** Should be used for benchmarking only!!! **
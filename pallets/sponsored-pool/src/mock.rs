@@ -4,14 +4,20 @@
 */
 
 use crate::{self as sponsored_pool};
-use frame_support::{parameter_types, traits::{ConstU32}};
+use frame_support::{parameter_types, traits::{ConstU128, ConstU32}};
 use frame_system as system;
 
 use frame_support::{
 	dispatch::Vec,
 	traits::{OnFinalize, OnInitialize},
 };
-use gafi_primitives::currency::{unit, NativeToken::GAKI};
+use gafi_primitives::{
+	constant::ID,
+	currency::{unit, AssetId, NativeToken::GAKI},
+	custom_services::AssetConversion,
+	pool::MasterPool,
+	ticket::TicketType,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -34,15 +40,13 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Sponsored: sponsored_pool::{Pallet, Storage, Event<T>},
 		PoolNames: pallet_pool_names::{Pallet, Storage, Event<T>},
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
 	}
 );
 
-impl pallet_randomness_collective_flip::Config for Test {}
-
 pub const EXISTENTIAL_DEPOSIT: u128 = 1000;
 
 parameter_types! {
@@ -51,7 +55,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -61,6 +65,23 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u128;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
 pub const INIT_TIMESTAMP: u64 = 30_000;
 pub const SLOT_DURATION: u64 = MILLISECS_PER_BLOCK;
@@ -118,15 +139,59 @@ parameter_types! {
 	pub MaxTxLimit: u32 = 100;
 	pub MaxPoolOwned: u32 =  10;
 	pub MaxPoolTarget: u32 =  10;
+	pub MaxExpiringPools: u32 = 2;
+	pub CleanupBatch: u32 = 2;
+	pub MaxTargetSelector: u32 = 10;
+	pub MaxAllowlist: u32 = 5;
+	pub static MarkTime: u128 = 0;
+	pub static AssetRate: u128 = 2;
+}
+
+/// Convert the native amounts with the rate controlled by the tests, `0` means no rate
+pub struct MockAssetConversion;
+
+impl AssetConversion<AccountId32> for MockAssetConversion {
+	fn to_asset(_asset: AssetId, fee: u128) -> Option<u128> {
+		match AssetRate::get() {
+			0 => None,
+			rate => Some(fee.saturating_mul(rate)),
+		}
+	}
+	fn pay_fee(_asset: AssetId, _payer: &AccountId32, _fee: u128) -> Option<u128> {
+		None
+	}
+}
+
+/// MasterPool with the mark time controlled by the tests
+pub struct MockMasterPool;
+
+impl MasterPool<AccountId32> for MockMasterPool {
+	fn remove_player(_player: &AccountId32, _pool_id: ID) {}
+	fn get_timeservice() -> u128 {
+		TIME_SERVICE
+	}
+	fn get_marktime() -> u128 {
+		MarkTime::get()
+	}
+	fn get_timeservice_start() -> u128 {
+		u128::default()
+	}
+	fn change_pool(_player: &AccountId32, _pool_id: ID, _new_ticket: TicketType) {}
 }
 
 impl sponsored_pool::Config for Test {
 	type Event = Event;
-	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
+	type Assets = Assets;
+	type AssetConversion = MockAssetConversion;
 	type PoolName = PoolNames;
+	type MasterPool = MockMasterPool;
 	type MaxPoolOwned = MaxPoolOwned;
 	type MaxPoolTarget = MaxPoolTarget;
+	type MaxExpiringPools = MaxExpiringPools;
+	type CleanupBatch = CleanupBatch;
+	type MaxTargetSelector = MaxTargetSelector;
+	type MaxAllowlist = MaxAllowlist;
 	type MinDiscountPercent = MinDiscountPercent;
 	type MaxDiscountPercent = MaxDiscountPercent;
 	type MinTxLimit = MinTxLimit;
@@ -162,6 +227,7 @@ pub fn run_to_block(n: u64) {
 		}
 		System::set_block_number(System::block_number() + 1);
 		System::on_initialize(System::block_number());
+		Sponsored::on_initialize(System::block_number());
 		Timestamp::set_timestamp((System::block_number() as u64 * MILLISECS_PER_BLOCK) + INIT_TIMESTAMP);
 	}
 }
@@ -1,10 +1,20 @@
-use crate::{mock::*, Error, PoolOwned, Pools, Targets};
+use crate::{
+    mock::*, BudgetCap, Budgets, CallTargets, ClosedPools, Error, Expiring, LowBalanceAlerted,
+    Members, PoolAsset, PoolBudget, PoolEnd, PoolOwned, Pools, TargetSelectors, Targets, Verified,
+};
+use codec::Encode;
 use frame_support::assert_err;
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{fungibles, Currency},
+};
 use gafi_primitives::constant::ID;
-use gafi_primitives::currency::{unit, NativeToken::GAKI};
-use gafi_primitives::custom_services::CustomPool;
-use sp_core::H160;
+use gafi_primitives::currency::{unit, AssetId, NativeToken::GAKI};
+use gafi_primitives::custom_services::{
+    sponsored_pool_account, sponsored_pool_id, CustomPool, PoolIdSeed, SponsoredBudget,
+};
+use sp_core::{H160, H256};
+use sp_io::hashing::blake2_256;
 use sp_runtime::{AccountId32, Permill};
 use sp_std::str::FromStr;
 use sp_std::vec::Vec;
@@ -25,8 +35,94 @@ fn new_pool_works() {
     ExtBuilder::default().build_and_execute(|| {
         run_to_block(1);
 
-        let new_pool = Sponsored::new_pool();
-        assert_eq!(new_pool.unwrap().id.len(), 32);
+        let account = AccountId32::from([0_u8; 32]);
+        let new_pool = Sponsored::new_pool(&account, None).unwrap();
+        assert_eq!(new_pool.id, sponsored_pool_id(&account, PoolIdSeed::Nonce(0)));
+        assert_eq!(Some(new_pool.account), sponsored_pool_account(&new_pool.id));
+
+        let salt = H256::from([1_u8; 32]);
+        let new_pool = Sponsored::new_pool(&account, Some(salt)).unwrap();
+        assert_eq!(new_pool.id, sponsored_pool_id(&account, PoolIdSeed::Salt(salt)));
+    })
+}
+
+#[test]
+fn create_pool_with_predictable_id_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let targets = vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()];
+        let pool_value = 1000 * unit(GAKI);
+        let salt = H256::from([1_u8; 32]);
+
+        // two pools in the same block
+        for nonce in 0..2 {
+            assert_ok!(Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                None,
+                None
+            ));
+            let pool_id = sponsored_pool_id(&account, PoolIdSeed::Nonce(nonce));
+            assert_eq!(*PoolOwned::<Test>::get(account.clone()).last().unwrap(), pool_id);
+            let pool_account: AccountId32 = sponsored_pool_account(&pool_id).unwrap();
+            assert_eq!(Balances::free_balance(&pool_account), pool_value);
+        }
+        assert_eq!(Sponsored::pool_nonce(&account), 2);
+
+        assert_ok!(Sponsored::create_pool(
+            Origin::signed(account.clone()),
+            targets.clone(),
+            pool_value,
+            Permill::from_percent(10),
+            10,
+            None,
+            Some(salt)
+        ));
+        let pool_id = sponsored_pool_id(&account, PoolIdSeed::Salt(salt));
+        assert_eq!(*PoolOwned::<Test>::get(account.clone()).last().unwrap(), pool_id);
+        assert_eq!(Sponsored::pool_nonce(&account), 2);
+        assert_noop!(
+            Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                None,
+                Some(salt)
+            ),
+            Error::<Test>::PoolIdExisted
+        );
+
+        // the salt is reused once the closed pool is cleaned up
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account.clone()), pool_id));
+        assert_noop!(
+            Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                None,
+                Some(salt)
+            ),
+            Error::<Test>::PoolIdExisted
+        );
+        run_to_block(2);
+        assert_ok!(Sponsored::create_pool(
+            Origin::signed(account.clone()),
+            targets,
+            pool_value,
+            Permill::from_percent(10),
+            10,
+            None,
+            Some(salt)
+        ));
     })
 }
 
@@ -43,7 +139,9 @@ fn create_pool(
         targets,
         pool_value,
         discount,
-        tx_limit
+        tx_limit,
+        None,
+        None
     ));
 
     assert_eq!(
@@ -97,7 +195,9 @@ fn create_pool_fail() {
                 vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
                 pool_value,
                 Permill::from_percent(10),
-                100
+                100,
+                None,
+                None
             ),
             pallet_balances::Error::<Test>::InsufficientBalance
         );
@@ -137,7 +237,7 @@ fn withdraw_pool_fail() {
         let pool_value = 1000 * unit(GAKI);
         // pool_id not exist
         {
-            let new_pool = Sponsored::new_pool();
+            let new_pool = Sponsored::new_pool(&account, None);
             assert_err!(
                 Sponsored::withdraw_pool(Origin::signed(account.clone()), new_pool.unwrap().id),
                 Error::<Test>::PoolNotExist
@@ -164,7 +264,124 @@ fn withdraw_pool_fail() {
 }
 
 #[test]
-fn get_service_works() {
+fn create_pool_with_end_block_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(2);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let targets = vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()];
+        let pool_value = 1000 * unit(GAKI);
+
+        assert_noop!(
+            Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                Some(2),
+                None
+            ),
+            Error::<Test>::InvalidEndBlock
+        );
+
+        for block in 3..5 {
+            run_to_block(block);
+            assert_ok!(Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                Some(10),
+                None
+            ));
+        }
+        run_to_block(5);
+        assert_noop!(
+            Sponsored::create_pool(
+                Origin::signed(account.clone()),
+                targets,
+                pool_value,
+                Permill::from_percent(10),
+                10,
+                Some(10),
+                None
+            ),
+            Error::<Test>::ExceedMaxExpiringPools
+        );
+    })
+}
+
+#[test]
+fn pool_expire_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        assert_ok!(Sponsored::create_pool(
+            Origin::signed(account.clone()),
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            Permill::from_percent(10),
+            10,
+            Some(5),
+            None
+        ));
+        let pool_id: ID = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
+        assert_eq!(PoolEnd::<Test>::get(pool_id), Some(5));
+        for i in 1..4 {
+            assert_ok!(Sponsored::join(AccountId32::from([i; 32]), pool_id));
+        }
+
+        run_to_block(4);
+        assert!(Pools::<Test>::get(pool_id).is_some());
+
+        run_to_block(5);
+        System::assert_has_event(Event::Sponsored(crate::Event::PoolExpired { id: pool_id }));
+        assert_eq!(Pools::<Test>::get(pool_id), None);
+        assert_eq!(PoolEnd::<Test>::get(pool_id), None);
+        assert!(Targets::<Test>::get(pool_id).is_empty());
+        assert!(PoolOwned::<Test>::get(account.clone()).is_empty());
+        assert_eq!(Balances::free_balance(&account), account_balance);
+
+        // the players are removed in batches
+        assert_eq!(Members::<Test>::iter_prefix(pool_id).count(), 1);
+        assert!(ClosedPools::<Test>::contains_key(pool_id));
+        run_to_block(6);
+        assert_eq!(Members::<Test>::iter_prefix(pool_id).count(), 0);
+        assert!(!ClosedPools::<Test>::contains_key(pool_id));
+    })
+}
+
+#[test]
+fn withdraw_pool_with_end_block_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        assert_ok!(Sponsored::create_pool(
+            Origin::signed(account.clone()),
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            Permill::from_percent(10),
+            10,
+            Some(10),
+            None
+        ));
+        let pool_id: ID = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
+        assert_eq!(Expiring::<Test>::get(10).to_vec(), vec![pool_id]);
+
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account.clone()), pool_id));
+        assert!(Expiring::<Test>::get(10).is_empty());
+        assert_eq!(PoolEnd::<Test>::get(pool_id), None);
+        assert_eq!(Balances::free_balance(&account), account_balance);
+    })
+}
+
+#[test]
+fn deposit_works() {
     ExtBuilder::default().build_and_execute(|| {
         run_to_block(1);
         let account_balance = 1_000_000 * unit(GAKI);
@@ -175,29 +392,46 @@ fn get_service_works() {
             account_balance,
             vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
             pool_value,
-            100,
-            Permill::from_percent(10),
+            10,
+            Permill::from_percent(70),
         );
 
-        let service = Sponsored::get_service(pool_id).unwrap();
-        assert_eq!(service.sponsor, account);
-        assert_eq!(service.service.discount, Permill::from_percent(10));
-        assert_eq!(service.service.tx_limit, 100);
+        let amount = 500 * unit(GAKI);
+        assert_ok!(Sponsored::deposit(
+            Origin::signed(account.clone()),
+            pool_id,
+            amount
+        ));
         assert_eq!(
-            service.targets,
-            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()]
+            Balances::free_balance(&account),
+            account_balance - pool_value - amount
+        );
+        assert_eq!(
+            Balances::free_balance(AccountId32::from(pool_id)),
+            pool_value + amount
+        );
+        assert_eq!(Pools::<Test>::get(pool_id).unwrap().value, pool_value + amount);
+
+        let account_1 = new_account([1_u8; 32], account_balance);
+        assert_noop!(
+            Sponsored::deposit(Origin::signed(account_1), pool_id, amount),
+            Error::<Test>::NotTheOwner
+        );
+        assert_noop!(
+            Sponsored::deposit(Origin::signed(account), [1_u8; 32], amount),
+            Error::<Test>::PoolNotExist
         );
     })
 }
 
 #[test]
-fn new_targets_works() {
+fn withdraw_works() {
     ExtBuilder::default().build_and_execute(|| {
         run_to_block(1);
         let account_balance = 1_000_000 * unit(GAKI);
         let account = new_account([0_u8; 32], account_balance);
-        let pool_value = 1000 * unit(GAKI);
-        create_pool(
+        let pool_value = 1500 * unit(GAKI);
+        let pool_id = create_pool(
             account.clone(),
             account_balance,
             vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
@@ -206,18 +440,1015 @@ fn new_targets_works() {
             Permill::from_percent(70),
         );
 
-        let pool_id: ID = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
-        let new_targets: Vec<H160> =
-            vec![H160::from_str("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap()];
+        let amount = 500 * unit(GAKI);
+        assert_ok!(Sponsored::withdraw(
+            Origin::signed(account.clone()),
+            pool_id,
+            amount
+        ));
+        assert_eq!(
+            Balances::free_balance(&account),
+            account_balance - pool_value + amount
+        );
+        assert_eq!(
+            Balances::free_balance(AccountId32::from(pool_id)),
+            pool_value - amount
+        );
+        assert_eq!(Pools::<Test>::get(pool_id).unwrap().value, pool_value - amount);
+    })
+}
 
-        assert_ok!(Sponsored::new_targets(
+#[test]
+fn withdraw_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1500 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            10,
+            Permill::from_percent(70),
+        );
+
+        // not the owner
+        {
+            let account_1 = new_account([1_u8; 32], account_balance);
+            assert_noop!(
+                Sponsored::withdraw(Origin::signed(account_1), pool_id, unit(GAKI)),
+                Error::<Test>::NotTheOwner
+            );
+        }
+
+        // remain less than MinPoolBalance
+        {
+            assert_noop!(
+                Sponsored::withdraw(Origin::signed(account.clone()), pool_id, 501 * unit(GAKI)),
+                Error::<Test>::NotReachMinPoolBalance
+            );
+            assert_noop!(
+                Sponsored::withdraw(Origin::signed(account.clone()), pool_id, 2000 * unit(GAKI)),
+                Error::<Test>::NotReachMinPoolBalance
+            );
+        }
+    })
+}
+
+#[test]
+fn update_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            100,
+            Permill::from_percent(10),
+        );
+
+        assert_ok!(Sponsored::update_pool(
             Origin::signed(account.clone()),
             pool_id,
-            new_targets.clone()
+            Permill::from_percent(50),
+            20
         ));
-        let targets = Targets::<Test>::get(pool_id);
+        let update = Sponsored::pending_update(pool_id).unwrap();
+        assert_eq!(update.discount, Permill::from_percent(50));
+        assert_eq!(update.tx_limit, 20);
 
-        assert_eq!(targets, new_targets);
+        // the current period keeps the old service
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert_eq!(service.service.discount, Permill::from_percent(10));
+        assert_eq!(service.service.tx_limit, 100);
+
+        // the tickets are renewed
+        MarkTime::set(TIME_SERVICE);
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert_eq!(service.service.discount, Permill::from_percent(50));
+        assert_eq!(service.service.tx_limit, 20);
+        let info = Sponsored::pool_info(pool_id).unwrap();
+        assert_eq!(info.service.discount, Permill::from_percent(50));
+        assert_eq!(info.service.tx_limit, 20);
+
+        // the update in effect is folded into the pool on the first read
+        assert_eq!(Sponsored::pending_update(pool_id), None);
+        let pool = Pools::<Test>::get(pool_id).unwrap();
+        assert_eq!(pool.discount, Permill::from_percent(50));
+        assert_eq!(pool.tx_limit, 20);
+
+        // the next update keeps the applied one until the next renewal
+        assert_ok!(Sponsored::update_pool(
+            Origin::signed(account.clone()),
+            pool_id,
+            Permill::from_percent(70),
+            50
+        ));
+        let pool = Pools::<Test>::get(pool_id).unwrap();
+        assert_eq!(pool.discount, Permill::from_percent(50));
+        assert_eq!(pool.tx_limit, 20);
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert_eq!(service.service.discount, Permill::from_percent(50));
+        assert_eq!(service.service.tx_limit, 20);
+
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account), pool_id));
+        assert_eq!(Sponsored::pending_update(pool_id), None);
+    })
+}
+
+#[test]
+fn update_pool_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            100,
+            Permill::from_percent(10),
+        );
+
+        let account_1 = new_account([1_u8; 32], account_balance);
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account_1),
+                pool_id,
+                Permill::from_percent(50),
+                20
+            ),
+            Error::<Test>::NotTheOwner
+        );
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account.clone()),
+                [1_u8; 32],
+                Permill::from_percent(50),
+                20
+            ),
+            Error::<Test>::PoolNotExist
+        );
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account.clone()),
+                pool_id,
+                Permill::from_percent(50),
+                MinTxLimit::get() - 1
+            ),
+            Error::<Test>::LessThanMinTxLimit
+        );
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account.clone()),
+                pool_id,
+                Permill::from_percent(50),
+                MaxTxLimit::get() + 1
+            ),
+            Error::<Test>::GreaterThanMaxTxLimit
+        );
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account.clone()),
+                pool_id,
+                Permill::from_percent(5),
+                20
+            ),
+            Error::<Test>::LessThanMinDiscountPercent
+        );
+        assert_noop!(
+            Sponsored::update_pool(
+                Origin::signed(account.clone()),
+                pool_id,
+                Permill::from_percent(80),
+                20
+            ),
+            Error::<Test>::GreaterThanMinDiscountPercent
+        );
+    })
+}
+
+#[test]
+fn set_target_selectors_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let contract = H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap();
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![contract],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let move_selector = [0x0d, 0xc1, 0x3e, 0x87];
+        let withdraw_selector = [0x2e, 0x1a, 0x7d, 0x4d];
+
+        assert_ok!(Sponsored::set_target_selectors(
+            Origin::signed(account.clone()),
+            pool_id,
+            contract,
+            vec![move_selector]
+        ));
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert_eq!(service.selectors, vec![(contract, vec![move_selector])]);
+        assert!(service.is_sponsored_function(&contract, Some(move_selector)));
+        assert!(!service.is_sponsored_function(&contract, Some(withdraw_selector)));
+        assert!(!service.is_sponsored_function(&contract, None));
+
+        // empty selectors sponsor any call again
+        assert_ok!(Sponsored::set_target_selectors(
+            Origin::signed(account.clone()),
+            pool_id,
+            contract,
+            vec![]
+        ));
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert!(service.selectors.is_empty());
+        assert!(service.is_sponsored_function(&contract, Some(withdraw_selector)));
+        assert!(service.is_sponsored_function(&contract, None));
+
+        // the selectors of the replaced targets are removed
+        assert_ok!(Sponsored::set_target_selectors(
+            Origin::signed(account.clone()),
+            pool_id,
+            contract,
+            vec![move_selector]
+        ));
+        assert_ok!(Sponsored::new_targets(
+            Origin::signed(account.clone()),
+            pool_id,
+            vec![H160::from_str("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap()]
+        ));
+        assert!(TargetSelectors::<Test>::get(pool_id).is_empty());
+    })
+}
+
+#[test]
+fn set_target_selectors_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let contract = H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap();
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![contract],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+
+        let account_1 = new_account([1_u8; 32], account_balance);
+        assert_noop!(
+            Sponsored::set_target_selectors(Origin::signed(account_1), pool_id, contract, vec![]),
+            Error::<Test>::NotTheOwner
+        );
+        assert_noop!(
+            Sponsored::set_target_selectors(
+                Origin::signed(account.clone()),
+                pool_id,
+                H160::from_str("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(),
+                vec![]
+            ),
+            Error::<Test>::TargetNotExist
+        );
+        assert_noop!(
+            Sponsored::set_target_selectors(
+                Origin::signed(account),
+                pool_id,
+                contract,
+                vec![[0_u8; 4]; 11]
+            ),
+            Error::<Test>::ExceedMaxTargetSelector
+        );
+    })
+}
+
+#[test]
+fn set_budget_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+
+        let account_1 = new_account([1_u8; 32], account_balance);
+        assert_noop!(
+            Sponsored::set_budget(Origin::signed(account_1), pool_id, None, None, Some(1)),
+            Error::<Test>::NotTheOwner
+        );
+
+        assert_ok!(Sponsored::set_budget(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(10),
+            Some(15),
+            Some(5)
+        ));
+        assert_eq!(
+            Budgets::<Test>::get(pool_id),
+            Some(PoolBudget {
+                player_cap: Some(10),
+                pool_cap: Some(15),
+                tx_cap: Some(5),
+            })
+        );
+
+        assert_ok!(Sponsored::set_budget(
+            Origin::signed(account.clone()),
+            pool_id,
+            None,
+            None,
+            None
+        ));
+        assert_eq!(Budgets::<Test>::get(pool_id), None);
+    })
+}
+
+#[test]
+fn check_budget_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let player_1 = AccountId32::from([1_u8; 32]);
+        let player_2 = AccountId32::from([2_u8; 32]);
+
+        // no caps
+        assert!(Sponsored::check_budget(pool_id, &player_1, u128::MAX));
+
+        assert_ok!(Sponsored::set_budget(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(10),
+            Some(15),
+            Some(5)
+        ));
+        assert!(!Sponsored::check_budget(pool_id, &player_1, 6));
+        System::assert_last_event(Event::Sponsored(crate::Event::CapReached {
+            id: pool_id,
+            player: player_1.clone(),
+            cap: BudgetCap::Transaction,
+        }));
+
+        assert!(Sponsored::check_budget(pool_id, &player_1, 5));
+        Sponsored::spend(pool_id, &player_1, 5);
+        assert!(Sponsored::check_budget(pool_id, &player_1, 5));
+        Sponsored::spend(pool_id, &player_1, 5);
+        assert!(!Sponsored::check_budget(pool_id, &player_1, 1));
+        System::assert_last_event(Event::Sponsored(crate::Event::CapReached {
+            id: pool_id,
+            player: player_1.clone(),
+            cap: BudgetCap::Player,
+        }));
+
+        assert!(Sponsored::check_budget(pool_id, &player_2, 5));
+        Sponsored::spend(pool_id, &player_2, 5);
+        assert!(!Sponsored::check_budget(pool_id, &player_2, 1));
+        System::assert_last_event(Event::Sponsored(crate::Event::CapReached {
+            id: pool_id,
+            player: player_2,
+            cap: BudgetCap::Pool,
+        }));
+    })
+}
+
+#[test]
+fn low_balance_alert_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+
+        assert_noop!(
+            Sponsored::set_low_balance_threshold(
+                Origin::signed(AccountId32::from([1_u8; 32])),
+                pool_id,
+                Some(2000 * unit(GAKI))
+            ),
+            Error::<Test>::NotTheOwner
+        );
+
+        assert_ok!(Sponsored::set_low_balance_threshold(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(2000 * unit(GAKI))
+        ));
+        assert_eq!(
+            Sponsored::low_balance_threshold(pool_id),
+            Some(2000 * unit(GAKI))
+        );
+        System::assert_last_event(Event::Sponsored(crate::Event::LowBalance {
+            id: pool_id,
+            balance: 1000 * unit(GAKI) - EXISTENTIAL_DEPOSIT,
+        }));
+
+        // alert only once
+        let player = AccountId32::from([1_u8; 32]);
+        Sponsored::spend(pool_id, &player, 5);
+        assert!(LowBalanceAlerted::<Test>::contains_key(pool_id));
+        System::reset_events();
+        Sponsored::spend(pool_id, &player, 5);
+        assert_eq!(System::events().len(), 0);
+
+        // topped up above the threshold
+        assert_ok!(Sponsored::deposit(
+            Origin::signed(account.clone()),
+            pool_id,
+            1500 * unit(GAKI)
+        ));
+        assert!(!LowBalanceAlerted::<Test>::contains_key(pool_id));
+
+        // alert again
+        assert_ok!(Sponsored::withdraw(
+            Origin::signed(account.clone()),
+            pool_id,
+            1000 * unit(GAKI)
+        ));
+        System::assert_last_event(Event::Sponsored(crate::Event::LowBalance {
+            id: pool_id,
+            balance: 1500 * unit(GAKI) - EXISTENTIAL_DEPOSIT,
+        }));
+
+        assert_ok!(Sponsored::set_low_balance_threshold(
+            Origin::signed(account.clone()),
+            pool_id,
+            None
+        ));
+        assert_eq!(Sponsored::low_balance_threshold(pool_id), None);
+        assert!(!LowBalanceAlerted::<Test>::contains_key(pool_id));
+    })
+}
+
+#[test]
+fn pause_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let player = AccountId32::from([1_u8; 32]);
+
+        // the pool could not fund the fee
+        Sponsored::insufficient_balance(pool_id, 2000 * unit(GAKI));
+        System::assert_last_event(Event::Sponsored(crate::Event::PoolPaused { id: pool_id }));
+        assert!(Sponsored::is_paused(pool_id));
+        assert!(Sponsored::pool_info(pool_id).unwrap().paused);
+        assert!(!Sponsored::check_budget(pool_id, &player, 5));
+
+        // not enough to resume
+        assert_ok!(Sponsored::deposit(
+            Origin::signed(account.clone()),
+            pool_id,
+            500 * unit(GAKI)
+        ));
+        assert!(Sponsored::is_paused(pool_id));
+
+        assert_ok!(Sponsored::deposit(
+            Origin::signed(account.clone()),
+            pool_id,
+            1000 * unit(GAKI)
+        ));
+        System::assert_last_event(Event::Sponsored(crate::Event::PoolResumed { id: pool_id }));
+        assert!(!Sponsored::is_paused(pool_id));
+        assert!(!Sponsored::pool_info(pool_id).unwrap().paused);
+        assert!(Sponsored::check_budget(pool_id, &player, 5));
+
+        // the pool can not fund another transaction like the last one
+        Sponsored::spend(pool_id, &player, 3000 * unit(GAKI));
+        assert!(Sponsored::is_paused(pool_id));
+
+        // topped up by a transfer to the pool account, sponsoring before it is resumed
+        make_deposit(&AccountId32::from(pool_id), 5000 * unit(GAKI));
+        assert!(!Sponsored::is_paused(pool_id));
+        assert!(!Sponsored::pool_info(pool_id).unwrap().paused);
+        assert!(Sponsored::check_budget(pool_id, &player, 5));
+        assert_eq!(Sponsored::paused(pool_id), None);
+
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account), pool_id));
+        assert_eq!(Sponsored::paused(pool_id), None);
+    })
+}
+
+const ASSET_ID: AssetId = 1;
+
+fn new_asset(owner: &AccountId32, balance: u128) {
+    assert_ok!(<Assets as fungibles::Create<AccountId32>>::create(
+        ASSET_ID,
+        owner.clone(),
+        false,
+        1
+    ));
+    assert_ok!(<Assets as fungibles::Mutate<AccountId32>>::mint_into(
+        ASSET_ID, owner, balance
+    ));
+}
+
+#[test]
+fn create_asset_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        new_asset(&account, account_balance);
+        let targets = vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()];
+        let pool_value = 2000 * unit(GAKI);
+        let player = AccountId32::from([1_u8; 32]);
+
+        // the pool must hold MinPoolBalance converted at the rate
+        assert_noop!(
+            Sponsored::create_asset_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                ASSET_ID,
+                1000 * unit(GAKI),
+                Permill::from_percent(70),
+                10,
+                None,
+                None
+            ),
+            Error::<Test>::NotReachMinPoolBalance
+        );
+
+        AssetRate::set(0);
+        assert_noop!(
+            Sponsored::create_asset_pool(
+                Origin::signed(account.clone()),
+                targets.clone(),
+                ASSET_ID,
+                pool_value,
+                Permill::from_percent(70),
+                10,
+                None,
+                None
+            ),
+            Error::<Test>::AssetRateNotSet
+        );
+
+        AssetRate::set(2);
+        assert_ok!(Sponsored::create_asset_pool(
+            Origin::signed(account.clone()),
+            targets,
+            ASSET_ID,
+            pool_value,
+            Permill::from_percent(70),
+            10,
+            None,
+            None
+        ));
+        let pool_id = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
+        let pool_account = AccountId32::from(pool_id);
+        assert_eq!(PoolAsset::<Test>::get(pool_id), Some(ASSET_ID));
+        assert_eq!(Assets::balance(ASSET_ID, &pool_account), pool_value);
+        assert_eq!(Assets::balance(ASSET_ID, &account), account_balance - pool_value);
+        assert_eq!(Balances::free_balance(&pool_account), EXISTENTIAL_DEPOSIT);
+
+        let info = Sponsored::pool_info(pool_id).unwrap();
+        assert_eq!(info.asset, Some(ASSET_ID));
+        assert_eq!(info.balance, pool_value);
+
+        assert_ok!(Sponsored::deposit(
+            Origin::signed(account.clone()),
+            pool_id,
+            1000 * unit(GAKI)
+        ));
+        assert_eq!(Assets::balance(ASSET_ID, &pool_account), 3000 * unit(GAKI));
+        assert_noop!(
+            Sponsored::withdraw(Origin::signed(account.clone()), pool_id, 2000 * unit(GAKI)),
+            Error::<Test>::NotReachMinPoolBalance
+        );
+        assert_ok!(Sponsored::withdraw(
+            Origin::signed(account.clone()),
+            pool_id,
+            1000 * unit(GAKI)
+        ));
+        assert_eq!(Assets::balance(ASSET_ID, &pool_account), pool_value);
+
+        // the fee is converted at the rate before it is compared to the pool
+        Sponsored::spend(pool_id, &player, 500 * unit(GAKI));
+        assert!(!Sponsored::is_paused(pool_id));
+        Sponsored::spend(pool_id, &player, 1500 * unit(GAKI));
+        assert!(Sponsored::is_paused(pool_id));
+
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account.clone()), pool_id));
+        assert_eq!(Assets::balance(ASSET_ID, &pool_account), 0);
+        assert_eq!(Assets::balance(ASSET_ID, &account), account_balance);
+        assert_eq!(Balances::free_balance(&account), account_balance);
+        assert_eq!(PoolAsset::<Test>::get(pool_id), None);
+    })
+}
+
+#[test]
+fn check_budget_asset_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        new_asset(&account, account_balance);
+        let player = AccountId32::from([1_u8; 32]);
+
+        AssetRate::set(2);
+        assert_ok!(Sponsored::create_asset_pool(
+            Origin::signed(account.clone()),
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            ASSET_ID,
+            2000 * unit(GAKI),
+            Permill::from_percent(70),
+            10,
+            None,
+            None
+        ));
+        let pool_id = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
+
+        // the caps are in the asset, the native fee is converted at the rate
+        assert_ok!(Sponsored::set_budget(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(20),
+            None,
+            Some(10)
+        ));
+        assert!(Sponsored::check_budget(pool_id, &player, 5));
+        assert!(!Sponsored::check_budget(pool_id, &player, 6));
+        System::assert_last_event(Event::Sponsored(crate::Event::CapReached {
+            id: pool_id,
+            player: player.clone(),
+            cap: BudgetCap::Transaction,
+        }));
+
+        Sponsored::spend(pool_id, &player, 5);
+        Sponsored::spend(pool_id, &player, 5);
+        assert!(!Sponsored::check_budget(pool_id, &player, 1));
+        System::assert_last_event(Event::Sponsored(crate::Event::CapReached {
+            id: pool_id,
+            player,
+            cap: BudgetCap::Player,
+        }));
+    })
+}
+
+#[test]
+fn allowlist_join_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let player_1 = AccountId32::from([1_u8; 32]);
+        let player_2 = AccountId32::from([2_u8; 32]);
+
+        assert_noop!(
+            Sponsored::set_allowlist(Origin::signed(player_1.clone()), pool_id, vec![]),
+            Error::<Test>::NotTheOwner
+        );
+        assert_noop!(
+            Sponsored::set_allowlist(
+                Origin::signed(account.clone()),
+                pool_id,
+                vec![player_1.clone(); 6]
+            ),
+            Error::<Test>::ExceedMaxAllowlist
+        );
+
+        assert_ok!(Sponsored::set_allowlist(
+            Origin::signed(account.clone()),
+            pool_id,
+            vec![player_1.clone()]
+        ));
+        assert_ok!(Sponsored::join(player_1.clone(), pool_id));
+        assert_noop!(
+            Sponsored::join(player_2.clone(), pool_id),
+            Error::<Test>::NotInAllowlist
+        );
+
+        // empty allowlist make the pool public again
+        assert_ok!(Sponsored::set_allowlist(
+            Origin::signed(account.clone()),
+            pool_id,
+            vec![]
+        ));
+        assert_ok!(Sponsored::join(player_2, pool_id));
+    })
+}
+
+fn leaf_of(account: &AccountId32) -> H256 {
+    H256::from(account.using_encoded(blake2_256))
+}
+
+fn node_of(left: H256, right: H256) -> H256 {
+    let pair = if left <= right { (left, right) } else { (right, left) };
+    H256::from(pair.using_encoded(blake2_256))
+}
+
+#[test]
+fn allowlist_root_join_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let player_1 = AccountId32::from([1_u8; 32]);
+        let player_2 = AccountId32::from([2_u8; 32]);
+        let player_3 = AccountId32::from([3_u8; 32]);
+        let player_4 = AccountId32::from([4_u8; 32]);
+
+        assert_noop!(
+            Sponsored::verify_allowlist(Origin::signed(player_1.clone()), pool_id, vec![]),
+            Error::<Test>::AllowlistRootNotSet
+        );
+
+        let node_12 = node_of(leaf_of(&player_1), leaf_of(&player_2));
+        let node_34 = node_of(leaf_of(&player_3), leaf_of(&player_4));
+        let root = node_of(node_12, node_34);
+        assert_ok!(Sponsored::set_allowlist_root(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(root)
+        ));
+        assert_noop!(
+            Sponsored::join(player_1.clone(), pool_id),
+            Error::<Test>::NotInAllowlist
+        );
+
+        assert_ok!(Sponsored::verify_allowlist(
+            Origin::signed(player_1.clone()),
+            pool_id,
+            vec![leaf_of(&player_2), node_34]
+        ));
+        assert_ok!(Sponsored::join(player_1.clone(), pool_id));
+
+        let outsider = AccountId32::from([5_u8; 32]);
+        assert_noop!(
+            Sponsored::verify_allowlist(
+                Origin::signed(outsider.clone()),
+                pool_id,
+                vec![leaf_of(&player_2), node_34]
+            ),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(Sponsored::join(outsider, pool_id), Error::<Test>::NotInAllowlist);
+
+        // publishing a new root drops the old verifications
+        assert_ok!(Sponsored::set_allowlist_root(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(node_34)
+        ));
+        assert_noop!(
+            Sponsored::join(player_1.clone(), pool_id),
+            Error::<Test>::NotInAllowlist
+        );
+
+        assert_ok!(Sponsored::set_allowlist_root(
+            Origin::signed(account),
+            pool_id,
+            None
+        ));
+        assert_ok!(Sponsored::join(player_1, pool_id));
+    })
+}
+
+#[test]
+fn withdraw_pool_clears_verified_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            1000 * unit(GAKI),
+            10,
+            Permill::from_percent(70),
+        );
+        let player_1 = AccountId32::from([1_u8; 32]);
+        let player_2 = AccountId32::from([2_u8; 32]);
+        let player_3 = AccountId32::from([3_u8; 32]);
+        let player_4 = AccountId32::from([4_u8; 32]);
+        let node_12 = node_of(leaf_of(&player_1), leaf_of(&player_2));
+        let node_34 = node_of(leaf_of(&player_3), leaf_of(&player_4));
+        assert_ok!(Sponsored::set_allowlist_root(
+            Origin::signed(account.clone()),
+            pool_id,
+            Some(node_of(node_12, node_34))
+        ));
+        assert_ok!(Sponsored::verify_allowlist(
+            Origin::signed(player_1.clone()),
+            pool_id,
+            vec![leaf_of(&player_2), node_34]
+        ));
+        assert_ok!(Sponsored::verify_allowlist(
+            Origin::signed(player_3),
+            pool_id,
+            vec![leaf_of(&player_4), node_12]
+        ));
+        assert_ok!(Sponsored::join(player_1, pool_id));
+
+        assert_ok!(Sponsored::withdraw_pool(Origin::signed(account), pool_id));
+        assert_eq!(Verified::<Test>::iter_prefix(pool_id).count(), 2);
+
+        // the member and the verified accounts are removed in batches
+        run_to_block(2);
+        assert_eq!(Members::<Test>::iter_prefix(pool_id).count(), 0);
+        assert_eq!(Verified::<Test>::iter_prefix(pool_id).count(), 1);
+        assert!(ClosedPools::<Test>::contains_key(pool_id));
+        run_to_block(3);
+        assert_eq!(Verified::<Test>::iter_prefix(pool_id).count(), 0);
+        assert!(!ClosedPools::<Test>::contains_key(pool_id));
+    })
+}
+
+#[test]
+fn get_service_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            100,
+            Permill::from_percent(10),
+        );
+
+        let service = Sponsored::get_service(pool_id).unwrap();
+        assert_eq!(service.sponsor, account);
+        assert_eq!(service.service.discount, Permill::from_percent(10));
+        assert_eq!(service.service.tx_limit, 100);
+        assert_eq!(
+            service.targets,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()]
+        );
+    })
+}
+
+#[test]
+fn pool_info_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            100,
+            Permill::from_percent(10),
+        );
+
+        let info = Sponsored::pool_info(pool_id).unwrap();
+        assert_eq!(info.id, pool_id);
+        assert_eq!(info.owner, account);
+        assert_eq!(info.service.tx_limit, 100);
+        assert_eq!(info.service.discount, Permill::from_percent(10));
+        assert_eq!(
+            info.targets,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()]
+        );
+        assert_eq!(info.balance, pool_value);
+
+        assert_eq!(Sponsored::pool_info([1_u8; 32]), None);
+    })
+}
+
+#[test]
+fn new_targets_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            10,
+            Permill::from_percent(70),
+        );
+
+        let pool_id: ID = *PoolOwned::<Test>::get(account.clone()).last().unwrap();
+        let new_targets: Vec<H160> =
+            vec![H160::from_str("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap()];
+
+        assert_ok!(Sponsored::new_targets(
+            Origin::signed(account.clone()),
+            pool_id,
+            new_targets.clone()
+        ));
+        let targets = Targets::<Test>::get(pool_id);
+
+        assert_eq!(targets, new_targets);
+    })
+}
+
+#[test]
+fn new_call_targets_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let account_1 = new_account([1_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let pool_id = create_pool(
+            account.clone(),
+            account_balance,
+            vec![H160::from_str("b28049C6EE4F90AE804C70F860e55459E837E84b").unwrap()],
+            pool_value,
+            10,
+            Permill::from_percent(70),
+        );
+
+        let call_targets = vec![(10_u8, 0_u8), (10_u8, 1_u8)];
+        assert_noop!(
+            Sponsored::new_call_targets(
+                Origin::signed(account_1.clone()),
+                pool_id,
+                call_targets.clone()
+            ),
+            Error::<Test>::NotTheOwner
+        );
+        assert_noop!(
+            Sponsored::new_call_targets(
+                Origin::signed(account.clone()),
+                pool_id,
+                vec![(10_u8, 0_u8); 11]
+            ),
+            Error::<Test>::ExceedPoolTarget
+        );
+
+        assert_ok!(Sponsored::new_call_targets(
+            Origin::signed(account.clone()),
+            pool_id,
+            call_targets.clone()
+        ));
+        assert_eq!(CallTargets::<Test>::get(pool_id), call_targets);
+        assert_eq!(
+            Sponsored::get_service(pool_id).unwrap().call_targets,
+            call_targets
+        );
     })
 }
 
@@ -231,7 +1462,7 @@ fn new_targets_fail() {
         let pool_id;
         // pool_id not exist
         {
-            let new_pool = Sponsored::new_pool();
+            let new_pool = Sponsored::new_pool(&account, None);
             assert_noop!(
                 Sponsored::withdraw_pool(Origin::signed(account.clone()), new_pool.unwrap().id),
                 Error::<Test>::PoolNotExist
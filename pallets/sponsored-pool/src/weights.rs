@@ -35,11 +35,24 @@ use frame_support::weights::constants::RocksDbWeight;
 
 pub trait WeightInfo {
 	fn create_pool(s: u32, ) -> Weight;
+	fn create_asset_pool(s: u32, ) -> Weight;
 	fn withdraw_pool(s: u32, ) -> Weight;
+	fn deposit(s: u32, ) -> Weight;
+	fn withdraw(s: u32, ) -> Weight;
+	fn update_pool(s: u32, ) -> Weight;
 	fn new_targets(s: u32, ) -> Weight;
+	fn new_call_targets(s: u32, ) -> Weight;
+	fn set_target_selectors(s: u32, ) -> Weight;
+	fn set_budget(s: u32, ) -> Weight;
+	fn set_low_balance_threshold(s: u32, ) -> Weight;
+	fn set_allowlist(s: u32, ) -> Weight;
+	fn set_allowlist_root(s: u32, ) -> Weight;
+	fn verify_allowlist(s: u32, ) -> Weight;
 	fn set_pool_name(s: u32, ) -> Weight;
 	fn clear_pool_name(_s: u32, ) -> Weight;
 	fn kill_pool_name(_s: u32, ) -> Weight;
+	fn expire_pools(s: u32, ) -> Weight;
+	fn clean_closed_pools(s: u32, ) -> Weight;
 }
 
 
@@ -49,8 +62,9 @@ pub struct SponsoredWeight<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for SponsoredWeight<T> {
 	// Storage: System Number (r:1 w:0)
-	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: SponsoredPool PoolNonce (r:1 w:1)
 	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: SponsoredPool ClosedPools (r:1 w:0)
 	// Storage: System Account (r:2 w:2)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
@@ -59,8 +73,27 @@ impl<T: frame_system::Config> WeightInfo for SponsoredWeight<T> {
 	// Storage: SponsoredPool Targets (r:1 w:1)
 	fn create_pool(s: u32, ) -> Weight {
 		(31_348_000 as Weight).saturating_mul(s as Weight)
-			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: SponsoredPool PoolNonce (r:1 w:1)
+	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: SponsoredPool ClosedPools (r:1 w:0)
+	// Storage: TxHandler AssetRates (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: SponsoredPool PoolOwned (r:1 w:1)
+	// Storage: SponsoredPool Targets (r:1 w:1)
+	// Storage: SponsoredPool PoolAsset (r:0 w:1)
+	fn create_asset_pool(s: u32, ) -> Weight {
+		(47_903_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
 	}
 	// Storage: SponsoredPool Pools (r:1 w:1)
 	// Storage: System Account (r:2 w:2)
@@ -75,6 +108,40 @@ impl<T: frame_system::Config> WeightInfo for SponsoredWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn deposit(s: u32, ) -> Weight {
+		(24_760_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn withdraw(s: u32, ) -> Weight {
+		(25_410_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: SponsoredPool PendingUpdates (r:1 w:1)
+	// Storage: Pool MarkTime (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn update_pool(s: u32, ) -> Weight {
+		(6_870_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 	// Storage: SponsoredPool Pools (r:1 w:0)
 	// Storage: SponsoredPool Targets (r:0 w:1)
 	fn new_targets(s: u32, ) -> Weight {
@@ -83,6 +150,87 @@ impl<T: frame_system::Config> WeightInfo for SponsoredWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool CallTargets (r:0 w:1)
+	fn new_call_targets(s: u32, ) -> Weight {
+		(5_000_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool Targets (r:1 w:0)
+	// Storage: SponsoredPool TargetSelectors (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_target_selectors(s: u32, ) -> Weight {
+		(7_420_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool Budgets (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_budget(s: u32, ) -> Weight {
+		(5_630_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool LowBalanceThreshold (r:1 w:1)
+	// Storage: SponsoredPool LowBalanceAlerted (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_low_balance_threshold(s: u32, ) -> Weight {
+		(8_140_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool Allowlist (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_allowlist(s: u32, ) -> Weight {
+		(12_410_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((1_120_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool AllowlistRoot (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn set_allowlist_root(_s: u32, ) -> Weight {
+		(11_870_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
+	// Storage: SponsoredPool AllowlistRoot (r:1 w:0)
+	// Storage: SponsoredPool Verified (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn verify_allowlist(s: u32, ) -> Weight {
+		(14_230_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_540_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: SponsoredPool Pools (r:1 w:0)
 	// Storage: PoolName NameOf (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: System Number (r:1 w:0)
@@ -121,13 +269,55 @@ impl<T: frame_system::Config> WeightInfo for SponsoredWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	// Storage: SponsoredPool Expiring (r:1 w:1)
+	// Storage: SponsoredPool Pools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: SponsoredPool PoolOwned (r:1 w:1)
+	// Storage: SponsoredPool PoolEnd (r:1 w:1)
+	// Storage: SponsoredPool ClosedPools (r:0 w:1)
+	// Storage: SponsoredPool Targets (r:0 w:1)
+	// Storage: SponsoredPool CallTargets (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn expire_pools(s: u32, ) -> Weight {
+		(3_120_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((41_870_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((14 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: SponsoredPool ClosedPools (r:1 w:1)
+	// Storage: SponsoredPool Members (r:1 w:1)
+	// Storage: Pool Tickets (r:0 w:1)
+	// Storage: Pool PreferredPool (r:1 w:1)
+	// Storage: SponsoredPool PlayerSpent (r:1 w:1)
+	// Storage: SponsoredPool Verified (r:1 w:1)
+	fn clean_closed_pools(s: u32, ) -> Weight {
+		(3_410_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((8_260_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(s as Weight)))
+	}
 }
 
 impl WeightInfo for () {
 	fn create_pool(s: u32, ) -> Weight {
 		(31_348_000 as Weight).saturating_mul(s as Weight)
-			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+
+	fn create_asset_pool(s: u32, ) -> Weight {
+		(47_903_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
 	}
 
 	fn withdraw_pool(s: u32, ) -> Weight {
@@ -136,12 +326,76 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
 	}
 
+	fn deposit(s: u32, ) -> Weight {
+		(24_760_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+
+	fn withdraw(s: u32, ) -> Weight {
+		(25_410_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+
+	fn update_pool(s: u32, ) -> Weight {
+		(6_870_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+
 	fn new_targets(s: u32, ) -> Weight {
 		(5_000_000 as Weight).saturating_mul(s as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 
+	fn new_call_targets(s: u32, ) -> Weight {
+		(5_000_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+
+	fn set_target_selectors(s: u32, ) -> Weight {
+		(7_420_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn set_budget(s: u32, ) -> Weight {
+		(5_630_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn set_low_balance_threshold(s: u32, ) -> Weight {
+		(8_140_000 as Weight).saturating_mul(s as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+
+	fn set_allowlist(s: u32, ) -> Weight {
+		(12_410_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((1_120_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn set_allowlist_root(_s: u32, ) -> Weight {
+		(11_870_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
+	fn verify_allowlist(s: u32, ) -> Weight {
+		(14_230_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_540_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+
 	fn set_pool_name(s: u32, ) -> Weight {
 		(14_932_000 as Weight)
 			// Standard Error: 13_000
@@ -161,4 +415,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
+
+	fn expire_pools(s: u32, ) -> Weight {
+		(3_120_000 as Weight)
+			// Standard Error: 21_000
+			.saturating_add((41_870_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((14 as Weight).saturating_mul(s as Weight)))
+	}
+
+	fn clean_closed_pools(s: u32, ) -> Weight {
+		(3_410_000 as Weight)
+			// Standard Error: 4_000
+			.saturating_add((8_260_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(s as Weight)))
+	}
 }
@@ -4,14 +4,87 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Pool;
 use crate::{Call, Config};
-use frame_benchmarking::{benchmarks};
+use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
+use sp_runtime::Permill;
+use sp_std::vec::Vec;
+
+const UNIT: u128 = 1_000_000_000_000_000_000u128;
+
+fn new_player<T: Config>(index: u32) -> T::AccountId {
+	let player: T::AccountId = account("player", index, 0);
+	let balance = u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(1_000_000 * UNIT).unwrap();
+	T::Currency::make_free_balance_be(&player, balance);
+	player
+}
+
+/// Stake with the player and send the rewards to the rewards pot
+fn fund_rewards<T: Config>(player: T::AccountId) {
+	let pool_id = Convertor::into_id(SystemTicket::Staking(TicketLevel::Basic));
+	let _ = Pallet::<T>::join(player, pool_id);
+	let pot = Pallet::<T>::rewards_account().unwrap();
+	let balance = u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(1_000 * UNIT).unwrap();
+	T::Currency::make_free_balance_be(&pot, balance + T::Currency::minimum_balance());
+}
 
 benchmarks! {
 	set_max_player {
-		let s in 0 .. 2;
-	}: _(RawOrigin::Root, s)
+		let s in 0 .. T::MaxWaitlist::get();
+		let pool_id = Convertor::into_id(SystemTicket::Staking(TicketLevel::Basic));
+		MaxPlayer::<T>::insert(pool_id, 0);
+		for i in 0 .. s {
+			let _ = Pallet::<T>::join(new_player::<T>(i), pool_id);
+		}
+	}: _(RawOrigin::Root, TicketLevel::Basic, s)
+	verify {
+		assert_eq!(Pallet::<T>::waitlist(pool_id).len(), 0);
+	}
+
+	add_service {
+		let service = SystemService::new(TicketLevel::Custom(0), 10_u32, Permill::from_percent(90), UNIT);
+	}: _(RawOrigin::Root, service)
+
+	update_service {
+		let service = SystemService::new(TicketLevel::Basic, 10_u32, Permill::from_percent(90), UNIT);
+	}: _(RawOrigin::Root, service)
+
+	retire_service {
+	}: _(RawOrigin::Root, TicketLevel::Basic)
+
+	set_fee_share {
+	}: _(RawOrigin::Root, Permill::from_percent(30))
+
+	claim_rewards {
+		let caller = new_player::<T>(0);
+		fund_rewards::<T>(caller.clone());
+		Pool::<T>::distribute_rewards();
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pool::<T>::pending_rewards(&caller), 0);
+	}
+
+	distribute_rewards {
+		fund_rewards::<T>(new_player::<T>(0));
+	}: {
+		Pool::<T>::distribute_rewards();
+	}
+	verify {
+		assert_eq!(Pool::<T>::unclaimed_rewards(), 1_000 * UNIT);
+	}
 
+	withdraw_unbonded {
+		let s in 1 .. T::MaxUnlockingChunks::get();
+		let caller = new_player::<T>(0);
+		let balance = u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(s as u128 * UNIT).unwrap();
+		T::Currency::reserve_named(&STAKING_POOL_RESERVE_ID, &caller, balance)?;
+		let chunks: Vec<UnlockChunk<T::BlockNumber>> = (0 .. s)
+			.map(|_| UnlockChunk { amount: UNIT, unlock_at: T::BlockNumber::default() })
+			.collect();
+		Unlocking::<T>::insert(&caller, BoundedVec::try_from(chunks).unwrap());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Pool::<T>::unlocking(&caller).len(), 0);
+	}
 
 	impl_benchmark_test_suite!(Pool, crate::mock::_new_test_ext(), crate::mock::Test);
 }
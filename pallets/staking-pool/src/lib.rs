@@ -19,18 +19,48 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ReservableCurrency},
+	traits::{
+		Currency, ExistenceRequirement, Imbalance, NamedReservableCurrency, OnUnbalanced,
+		StorageVersion,
+	},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
 use gafi_primitives::{
-	constant::ID,
-	system_services::{Convertor, SystemDefaultServices, SystemPool, SystemService},
+	constant::{ReserveIdentifier, ID, STAKING_POOL_RESERVE_ID},
+	pool::MasterPool,
+	system_services::{
+		Convertor, SponsoredJoin, SystemDefaultServices, SystemPool, SystemService,
+	},
 	ticket::{SystemTicket, Ticket, TicketLevel, TicketType},
 };
-use gu_convertor::u128_try_to_balance;
+use gu_convertor::{balance_try_to_u128, into_account, u128_try_to_balance};
 pub use pallet::*;
 use pallet_timestamp::{self as timestamp};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::Saturating, FixedPointNumber, FixedU128, Permill};
+
+/// The seed of the account holding the staking rewards
+const REWARDS_POT_SEED: &[u8] = b"gafi/staking/rewards";
+
+/// The stake left by the player, released after the unbonding period
+#[derive(Eq, PartialEq, Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct UnlockChunk<BlockNumber> {
+	pub amount: u128,
+	/// The block number the amount can be withdrawn
+	pub unlock_at: BlockNumber,
+}
+
+/// The rewards of a staker, accrued lazily from RewardPerStake
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct StakerReward {
+	/// The RewardPerStake when the rewards of the staker were last accrued
+	pub reward_per_stake: FixedU128,
+	/// The rewards accrued but not claimed yet
+	pub pending: u128,
+}
 
 #[cfg(test)]
 mod mock;
@@ -41,6 +71,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
@@ -49,33 +81,108 @@ pub mod pallet {
 	use super::*;
 	use frame_support::dispatch::DispatchResult;
 
+	/// The current storage version
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
 	/// Configure the pallet by specifying the parameters and types it depends on.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_timestamp::Config {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency mechanism, the staking amount is reserved with STAKING_POOL_RESERVE_ID
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
 		type StakingServices: SystemDefaultServices;
+
+		/// The origin which may add, update and retire the services
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The billing period of the pools, the staking rewards are distributed each period
+		type MasterPool: MasterPool<Self::AccountId>;
+
+		/// Number of blocks the stake stays reserved after leaving the pool
+		#[pallet::constant]
+		type UnbondingPeriod: Get<Self::BlockNumber>;
+
+		/// Max number of unlocking chunks a player can have at the same time
+		#[pallet::constant]
+		type MaxUnlockingChunks: Get<u32>;
+
+		/// Max number of players waiting for each level
+		#[pallet::constant]
+		type MaxWaitlist: Get<u32>;
+
+		/// Max number of beneficiaries a sponsor can fund
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+	}
+
+	/// on_initialize applies the service changes and distributes the staking rewards
+	/// collected in the rewards pot once the MasterPool starts a new period
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
+			let mut services = 0_u64;
+			let marktime = T::MasterPool::get_marktime();
+			if marktime > Self::rewards_marktime() {
+				// NextServices, Services and LegacyValues of each service changed
+				services = 3_u64.saturating_mul(Self::apply_next_services().into());
+				Self::distribute_rewards();
+				RewardsMarkTime::<T>::put(marktime);
+			}
+			<T as pallet::Config>::WeightInfo::distribute_rewards()
+				.saturating_add(T::DbWeight::get().reads_writes(services, services))
+		}
 	}
 
 	//** Storage **//
 
-	/// Holding the number of maximum player can join the staking pool
+	#[pallet::type_value]
+	pub fn DefaultMaxPlayer() -> u32 {
+		u32::MAX
+	}
+
+	/// Holding the number of maximum player can stake in each level, the levels never set
+	/// are not limited
+	#[pallet::storage]
+	#[pallet::getter(fn max_player)]
+	pub type MaxPlayer<T: Config> =
+		StorageMap<_, Twox64Concat, ID, u32, ValueQuery, DefaultMaxPlayer>;
+
+	/// Number of players staking in each level
+	#[pallet::storage]
+	#[pallet::getter(fn level_player_count)]
+	pub type LevelPlayerCount<T: Config> = StorageMap<_, Twox64Concat, ID, u32, ValueQuery>;
+
+	/// Holding the players waiting for each full level, first in first promoted
 	#[pallet::storage]
-	pub type MaxPlayer<T: Config> = StorageValue<_, u32, ValueQuery>;
+	#[pallet::getter(fn waitlist)]
+	pub type Waitlist<T: Config> =
+		StorageMap<_, Twox64Concat, ID, BoundedVec<T::AccountId, T::MaxWaitlist>, ValueQuery>;
+
+	/// Holding the level the player is waiting for
+	#[pallet::storage]
+	#[pallet::getter(fn waitlisted)]
+	pub type Waitlisted<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ID>;
 
 	/// Holding the tickets detail
 	#[pallet::storage]
@@ -91,13 +198,93 @@ pub mod pallet {
 	#[pallet::getter(fn services)]
 	pub type Services<T: Config> = StorageMap<_, Twox64Concat, ID, SystemService>;
 
+	/// Holding the service changes, take effect at the next period
+	#[pallet::storage]
+	#[pallet::getter(fn next_services)]
+	pub type NextServices<T: Config> = StorageMap<_, Twox64Concat, ID, SystemService>;
+
+	/// Holding the retired services, players can not join them anymore
+	#[pallet::storage]
+	#[pallet::getter(fn retired_services)]
+	pub type RetiredServices<T: Config> = StorageMap<_, Twox64Concat, ID, ()>;
+
+	/// Holding the amount staked by the player
+	#[pallet::storage]
+	pub(super) type Reserved<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u128>;
+
+	/// Holding the service value before it first changed, which is staked by the players
+	/// joined before Reserved was stored
+	#[pallet::storage]
+	pub(super) type LegacyValues<T: Config> = StorageMap<_, Twox64Concat, ID, u128>;
+
+	/// Holding the stake left by the players, waiting for the unbonding period
+	#[pallet::storage]
+	#[pallet::getter(fn unlocking)]
+	pub type Unlocking<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<UnlockChunk<T::BlockNumber>, T::MaxUnlockingChunks>,
+		ValueQuery,
+	>;
+
+	/// Holding the sponsor funding the stake of the beneficiary
+	#[pallet::storage]
+	#[pallet::getter(fn sponsors)]
+	pub type Sponsors<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// Holding the beneficiaries funded by the sponsor
+	#[pallet::storage]
+	#[pallet::getter(fn beneficiaries)]
+	pub type Beneficiaries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxBeneficiaries>,
+		ValueQuery,
+	>;
+
+	/// The share of the transaction fees sent to the staking rewards
+	#[pallet::storage]
+	#[pallet::getter(fn fee_share)]
+	pub type FeeShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The total amount staked by all the players
+	#[pallet::storage]
+	#[pallet::getter(fn total_staked)]
+	pub type TotalStaked<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// The rewards distributed for each unit staked since the pallet started
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_stake)]
+	pub type RewardPerStake<T: Config> = StorageValue<_, FixedU128, ValueQuery>;
+
+	/// The rewards distributed to the stakers but not claimed yet, kept in the rewards pot
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_rewards)]
+	pub type UnclaimedRewards<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// The marktime of the MasterPool when the rewards were last distributed
+	#[pallet::storage]
+	#[pallet::getter(fn rewards_marktime)]
+	pub(super) type RewardsMarkTime<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// Holding the rewards of the stakers
+	#[pallet::storage]
+	#[pallet::getter(fn staker_rewards)]
+	pub type StakerRewards<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, StakerReward>;
+
 	#[pallet::genesis_config]
-	pub struct GenesisConfig {}
+	pub struct GenesisConfig {
+		pub max_player: u32,
+	}
 
 	#[cfg(feature = "std")]
 	impl Default for GenesisConfig {
 		fn default() -> Self {
-			Self {}
+			Self {
+				max_player: u32::MAX,
+			}
 		}
 	}
 
@@ -105,6 +292,7 @@ pub mod pallet {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
 			for service in <T as Config>::StakingServices::get_default_services() {
+				MaxPlayer::<T>::insert(service.0, self.max_player);
 				Services::<T>::insert(service.0, service.1);
 			}
 		}
@@ -113,7 +301,20 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		StakingNewMaxPlayer { new_max_player: u32 },
+		StakingNewMaxPlayer { pool_id: ID, new_max_player: u32 },
+		ServiceAdded { pool_id: ID, service: SystemService },
+		ServiceUpdated { pool_id: ID, service: SystemService },
+		ServiceChanged { pool_id: ID, service: SystemService },
+		ServiceRetired { pool_id: ID },
+		FeeShareSet { fee_share: Permill },
+		RewardsDistributed { amount: u128, total_staked: u128 },
+		RewardsClaimed { player: T::AccountId, amount: u128 },
+		Unbonding { player: T::AccountId, amount: u128, unlock_at: T::BlockNumber },
+		Withdrawn { player: T::AccountId, amount: u128 },
+		JoinedWaitlist { player: T::AccountId, pool_id: ID, position: u32 },
+		LeftWaitlist { player: T::AccountId, pool_id: ID },
+		Promoted { player: T::AccountId, pool_id: ID },
+		JoinedFor { sponsor: T::AccountId, beneficiary: T::AccountId, pool_id: ID },
 	}
 
 	#[pallet::error]
@@ -123,11 +324,28 @@ pub mod pallet {
 		IntoBalanceFail,
 		LevelNotFound,
 		PoolNotFound,
+		SameLevel,
+		ServiceAlreadyExists,
+		ServiceRetired,
+		NoRewards,
+		RewardsPotNotFound,
+		TooManyUnlockingChunks,
+		NothingToWithdraw,
+		WaitlistFull,
+		LevelFull,
+		PlayerWaitlisted,
+		InvalidBeneficiary,
+		TooManyBeneficiaries,
+		SponsoredStake,
+		LevelNotEmpty,
 	}
 
 	impl<T: Config> SystemPool<T::AccountId> for Pallet<T> {
 		/// Join Staking Pool
 		///
+		/// The player joins the waitlist of the level when it is full, the staking amount is
+		/// reserved at once and the ticket is suspended until the player is promoted
+		///
 		/// The origin must be Signed
 		///
 		/// Parameters:
@@ -136,28 +354,25 @@ pub mod pallet {
 		/// Weight: `O(1)`
 		#[transactional]
 		fn join(sender: T::AccountId, pool_id: ID) -> DispatchResult {
-			let service = Self::get_pool_by_id(pool_id)?;
-			let staking_amount = u128_try_to_balance::<
-				<T as pallet::Config>::Currency,
-				T::AccountId,
-			>(service.value)?;
-			<T as pallet::Config>::Currency::reserve(&sender, staking_amount)?;
-
-			let new_player_count = Self::player_count()
-				.checked_add(1)
-				.ok_or(<Error<T>>::StakeCountOverflow)?;
-
-			Self::stake_pool(sender, pool_id, new_player_count);
-			Ok(())
+			Self::do_join(&sender, sender.clone(), pool_id)
 		}
 
-		/// Leave Upfront Pool
+		/// Leave Staking Pool
+		///
+		/// The ticket is removed at once, the staking amount is released after
+		/// UnbondingPeriod with `withdraw_unbonded`
 		///
 		/// The origin must be Signed
 		///
 		/// Weight: `O(1)`
 		#[transactional]
 		fn leave(sender: T::AccountId) -> DispatchResult {
+			if let Some(pool_id) = Waitlisted::<T>::get(&sender) {
+				Self::remove_from_waitlist(sender.clone(), pool_id)?;
+				Self::remove_sponsor(&sender);
+				return Ok(());
+			}
+
 			if let Some(ticket) = Tickets::<T>::get(&sender) {
 				let new_player_count = Self::player_count()
 					.checked_sub(1)
@@ -165,43 +380,338 @@ pub mod pallet {
 
 				if let TicketType::System(system_ticket) = ticket.ticket_type {
 					let pool_id = Convertor::into_id(system_ticket);
-					let service = Self::get_pool_by_id(pool_id)?;
-					let staking_amount = u128_try_to_balance::<
-						<T as pallet::Config>::Currency,
-						T::AccountId,
-					>(service.value)?;
-					<T as pallet::Config>::Currency::unreserve(&sender, staking_amount);
-					Self::unstake_pool(sender, new_player_count);
-					return Ok(());
+					let reserved_amount = Self::reserved_amount(&sender, pool_id);
+					Self::unbond(&Self::payer(&sender), reserved_amount)?;
+					Self::remove_sponsor(&sender);
+					let reward = Self::current_reward(&sender);
+					TotalStaked::<T>::mutate(|total| *total = total.saturating_sub(reserved_amount));
+					Self::unstake_pool(sender.clone(), new_player_count);
+					Self::set_staker_reward(&sender, reward);
+					LevelPlayerCount::<T>::mutate(pool_id, |count| *count = count.saturating_sub(1));
+					return Self::promote(pool_id);
 				}
 			}
 			return Err(Error::<T>::PlayerNotStake.into());
 		}
 
+		/// Change the ticket level of Staking Pool
+		///
+		/// The staking amount is reserved to match the new level, the amount over the new level
+		/// is unbonded like leaving the pool
+		///
+		/// Parameters:
+		/// - `new_pool_id`: The pool id of the new level
+		///
+		/// Weight: `O(1)`
+		#[transactional]
+		fn change_level(sender: T::AccountId, new_pool_id: ID) -> DispatchResult {
+			ensure!(
+				!Waitlisted::<T>::contains_key(&sender),
+				<Error<T>>::PlayerWaitlisted
+			);
+			ensure!(!Sponsors::<T>::contains_key(&sender), <Error<T>>::SponsoredStake);
+			let mut ticket = Tickets::<T>::get(&sender).ok_or(<Error<T>>::PlayerNotStake)?;
+			let level = Self::get_player_level(sender.clone()).ok_or(<Error<T>>::PlayerNotStake)?;
+			let old_pool_id = Convertor::into_id(SystemTicket::Staking(level));
+			let old_amount = Self::reserved_amount(&sender, old_pool_id);
+			let new_service = Self::get_pool_by_id(new_pool_id)?;
+			ensure!(level != new_service.ticket_level, <Error<T>>::SameLevel);
+			ensure!(
+				!RetiredServices::<T>::contains_key(new_pool_id),
+				<Error<T>>::ServiceRetired
+			);
+			ensure!(Self::has_capacity(new_pool_id), <Error<T>>::LevelFull);
+
+			if new_service.value >= old_amount {
+				let staking_amount = u128_try_to_balance::<
+					<T as pallet::Config>::Currency,
+					T::AccountId,
+				>(new_service.value - old_amount)?;
+				<T as pallet::Config>::Currency::reserve_named(
+					&STAKING_POOL_RESERVE_ID,
+					&sender,
+					staking_amount,
+				)?;
+			} else {
+				Self::unbond(&sender, old_amount - new_service.value)?;
+			}
+			Self::accrue_rewards(&sender);
+			Reserved::<T>::insert(&sender, new_service.value);
+			TotalStaked::<T>::mutate(|total| {
+				*total = total.saturating_sub(old_amount).saturating_add(new_service.value)
+			});
+
+			ticket.ticket_type = TicketType::System(SystemTicket::Staking(new_service.ticket_level));
+			Tickets::<T>::insert(sender, ticket);
+			LevelPlayerCount::<T>::mutate(old_pool_id, |count| *count = count.saturating_sub(1));
+			LevelPlayerCount::<T>::mutate(new_pool_id, |count| *count = count.saturating_add(1));
+			Self::promote(old_pool_id)
+		}
+
 		fn get_service(pool_id: ID) -> Option<SystemService> {
 			Services::<T>::get(pool_id)
 		}
+
+		/// The ticket of the player in the waitlist can't be used until promoted
+		fn is_suspended(player: &T::AccountId) -> bool {
+			Waitlisted::<T>::contains_key(player)
+		}
+	}
+
+	impl<T: Config> SponsoredJoin<T::AccountId> for Pallet<T> {
+		/// Join Staking Pool for the beneficiary
+		///
+		/// The staking amount is reserved from the sponsor and returned to the sponsor when
+		/// leaving, the beneficiary gets the ticket and the staking rewards but can't leave
+		/// or change level
+		///
+		/// Parameters:
+		/// - `sponsor`: the account funding the stake
+		/// - `beneficiary`: the account getting the ticket
+		///
+		/// Weight: `O(1)`
+		#[transactional]
+		fn join_for(sponsor: T::AccountId, beneficiary: T::AccountId, pool_id: ID) -> DispatchResult {
+			ensure!(sponsor != beneficiary, <Error<T>>::InvalidBeneficiary);
+			Beneficiaries::<T>::try_mutate(&sponsor, |beneficiaries| {
+				beneficiaries
+					.try_push(beneficiary.clone())
+					.map_err(|_| <Error<T>>::TooManyBeneficiaries)
+			})?;
+			Sponsors::<T>::insert(&beneficiary, &sponsor);
+			Self::do_join(&sponsor, beneficiary.clone(), pool_id)?;
+
+			Self::deposit_event(Event::<T>::JoinedFor {
+				sponsor,
+				beneficiary,
+				pool_id,
+			});
+			Ok(())
+		}
+
+		fn sponsor_of(player: &T::AccountId) -> Option<T::AccountId> {
+			Sponsors::<T>::get(player)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set MaxPlayer
 		///
+		/// Set the capacity of a level, the players waiting for it are promoted to the free slots
+		///
 		/// The root must be Signed
 		///
 		/// Parameters:
-		/// - `max_player`: new value of MaxPlayer
+		/// - `level`: the level to set the capacity
+		/// - `new_max_player`: new value of MaxPlayer
+		///
+		/// Weight: `O(MaxWaitlist)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_max_player(T::MaxWaitlist::get()))]
+		#[transactional]
+		pub fn set_max_player(
+			origin: OriginFor<T>,
+			level: TicketLevel,
+			new_max_player: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Staking(level));
+			Self::get_pool_by_id(pool_id)?;
+
+			MaxPlayer::<T>::insert(pool_id, new_max_player);
+			Self::promote(pool_id)?;
+			Self::deposit_event(Event::<T>::StakingNewMaxPlayer {
+				pool_id,
+				new_max_player,
+			});
+			Ok(())
+		}
+
+		/// Add Service
+		///
+		/// Add a new level of ticket, players can join it immediately
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `service`: the service of the new level
 		///
 		/// Weight: `O(1)`
-		#[pallet::weight((
-			0,
-			DispatchClass::Normal,
-			Pays::No
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_service())]
+		pub fn add_service(origin: OriginFor<T>, service: SystemService) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Staking(service.ticket_level));
+			ensure!(
+				!Services::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceAlreadyExists
+			);
+
+			Services::<T>::insert(pool_id, service);
+			Self::deposit_event(Event::<T>::ServiceAdded { pool_id, service });
+			Ok(())
+		}
+
+		/// Update Service
+		///
+		/// Change the staking amount, tx limit or discount of a level, the change takes effect
+		/// at the next period. The players already staked keep their staking amount
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `service`: the new service of the level
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::update_service())]
+		pub fn update_service(origin: OriginFor<T>, service: SystemService) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Staking(service.ticket_level));
+			Self::get_pool_by_id(pool_id)?;
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceRetired
+			);
+
+			NextServices::<T>::insert(pool_id, service);
+			Self::deposit_event(Event::<T>::ServiceUpdated { pool_id, service });
+			Ok(())
+		}
+
+		/// Retire Service
+		///
+		/// Stop players joining a level, the staking players are never moved to another level so
+		/// the level can only be retired once no player stakes or waits in it
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `level`: the level to retire
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::retire_service())]
+		pub fn retire_service(origin: OriginFor<T>, level: TicketLevel) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Staking(level));
+			Self::get_pool_by_id(pool_id)?;
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceRetired
+			);
+			ensure!(
+				Self::level_player_count(pool_id) == 0 &&
+					Waitlist::<T>::decode_len(pool_id).unwrap_or_default() == 0,
+				<Error<T>>::LevelNotEmpty
+			);
+
+			RetiredServices::<T>::insert(pool_id, ());
+			NextServices::<T>::remove(pool_id);
+			Self::deposit_event(Event::<T>::ServiceRetired { pool_id });
+			Ok(())
+		}
+
+		/// Set Fee Share
+		///
+		/// Set the share of the transaction fees sent to the staking rewards
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `fee_share`: the share of the fees
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_fee_share())]
+		pub fn set_fee_share(origin: OriginFor<T>, fee_share: Permill) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			FeeShare::<T>::put(fee_share);
+			Self::deposit_event(Event::<T>::FeeShareSet { fee_share });
+			Ok(())
+		}
+
+		/// Withdraw Unbonded
+		///
+		/// Release the stake left by the player when the unbonding period is over
+		///
+		/// The origin must be Signed
+		///
+		/// Weight: `O(MaxUnlockingChunks)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw_unbonded(
+			T::MaxUnlockingChunks::get()
 		))]
-		pub fn set_max_player(origin: OriginFor<T>, new_max_player: u32) -> DispatchResult {
-			ensure_root(origin)?;
-			MaxPlayer::<T>::put(new_max_player);
-			Self::deposit_event(Event::<T>::StakingNewMaxPlayer { new_max_player });
+		#[transactional]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let mut chunks = Unlocking::<T>::get(&sender);
+			let mut amount = 0_u128;
+			chunks.retain(|chunk| {
+				if chunk.unlock_at <= now {
+					amount = amount.saturating_add(chunk.amount);
+					false
+				} else {
+					true
+				}
+			});
+			ensure!(amount > 0, <Error<T>>::NothingToWithdraw);
+
+			let staking_amount =
+				u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(amount)?;
+			<T as pallet::Config>::Currency::unreserve_named(
+				&STAKING_POOL_RESERVE_ID,
+				&sender,
+				staking_amount,
+			);
+			if chunks.is_empty() {
+				Unlocking::<T>::remove(&sender);
+			} else {
+				Unlocking::<T>::insert(&sender, chunks);
+			}
+
+			Self::deposit_event(Event::<T>::Withdrawn {
+				player: sender,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Claim Rewards
+		///
+		/// Transfer the staking rewards accrued by the player from the rewards pot
+		///
+		/// The origin must be Signed
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let reward = Self::accrue_rewards(&sender);
+			ensure!(reward.pending > 0, <Error<T>>::NoRewards);
+			let pot = Self::rewards_account().ok_or(<Error<T>>::RewardsPotNotFound)?;
+
+			let amount = u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(
+				reward.pending,
+			)?;
+			<T as pallet::Config>::Currency::transfer(
+				&pot,
+				&sender,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			UnclaimedRewards::<T>::mutate(|unclaimed| {
+				*unclaimed = unclaimed.saturating_sub(reward.pending)
+			});
+			Self::set_staker_reward(
+				&sender,
+				StakerReward {
+					pending: 0,
+					..reward
+				},
+			);
+
+			Self::deposit_event(Event::<T>::RewardsClaimed {
+				player: sender,
+				amount: reward.pending,
+			});
 			Ok(())
 		}
 	}
@@ -224,16 +734,287 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Reserve the staking amount from the payer and stake for the player, or put the player
+		/// in the waitlist when the level is full
+		fn do_join(payer: &T::AccountId, player: T::AccountId, pool_id: ID) -> DispatchResult {
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceRetired
+			);
+			let service = Self::get_pool_by_id(pool_id)?;
+			let staking_amount = u128_try_to_balance::<
+				<T as pallet::Config>::Currency,
+				T::AccountId,
+			>(service.value)?;
+			<T as pallet::Config>::Currency::reserve_named(
+				&STAKING_POOL_RESERVE_ID,
+				payer,
+				staking_amount,
+			)?;
+
+			if Self::has_capacity(pool_id) {
+				Self::activate(player, pool_id, service.value)
+			} else {
+				Self::add_to_waitlist(player, pool_id, service.value)
+			}
+		}
+
+		/// The account the stake of the player is reserved from
+		fn payer(player: &T::AccountId) -> T::AccountId {
+			Sponsors::<T>::get(player).unwrap_or_else(|| player.clone())
+		}
+
+		fn remove_sponsor(beneficiary: &T::AccountId) {
+			if let Some(sponsor) = Sponsors::<T>::take(beneficiary) {
+				Beneficiaries::<T>::mutate_exists(&sponsor, |maybe_beneficiaries| {
+					if let Some(beneficiaries) = maybe_beneficiaries {
+						beneficiaries.retain(|funded| funded != beneficiary);
+						if beneficiaries.is_empty() {
+							*maybe_beneficiaries = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// The level has a free slot and no one is waiting for it
+		fn has_capacity(pool_id: ID) -> bool {
+			Self::level_player_count(pool_id) < Self::max_player(pool_id) &&
+				Waitlist::<T>::get(pool_id).is_empty()
+		}
+
+		/// Stake the amount reserved by the player and give the ticket of the level
+		fn activate(player: T::AccountId, pool_id: ID, amount: u128) -> DispatchResult {
+			let new_player_count = Self::player_count()
+				.checked_add(1)
+				.ok_or(<Error<T>>::StakeCountOverflow)?;
+
+			StakerRewards::<T>::insert(&player, Self::current_reward(&player));
+			Reserved::<T>::insert(&player, amount);
+			TotalStaked::<T>::mutate(|total| *total = total.saturating_add(amount));
+			LevelPlayerCount::<T>::mutate(pool_id, |count| *count = count.saturating_add(1));
+			Self::stake_pool(player, pool_id, new_player_count)?;
+			Ok(())
+		}
+
+		fn add_to_waitlist(player: T::AccountId, pool_id: ID, amount: u128) -> DispatchResult {
+			let position = Waitlist::<T>::try_mutate(pool_id, |waitlist| {
+				waitlist
+					.try_push(player.clone())
+					.map(|_| waitlist.len() as u32)
+					.map_err(|_| <Error<T>>::WaitlistFull)
+			})?;
+			Reserved::<T>::insert(&player, amount);
+			Waitlisted::<T>::insert(&player, pool_id);
+
+			Self::deposit_event(Event::<T>::JoinedWaitlist {
+				player,
+				pool_id,
+				position,
+			});
+			Ok(())
+		}
+
+		/// Leave the waitlist, the staking amount is released at once since it never staked
+		fn remove_from_waitlist(player: T::AccountId, pool_id: ID) -> DispatchResult {
+			Waitlist::<T>::mutate_exists(pool_id, |maybe_waitlist| {
+				if let Some(waitlist) = maybe_waitlist {
+					waitlist.retain(|waiting| waiting != &player);
+					if waitlist.is_empty() {
+						*maybe_waitlist = None;
+					}
+				}
+			});
+			Waitlisted::<T>::remove(&player);
+			let staking_amount = u128_try_to_balance::<
+				<T as pallet::Config>::Currency,
+				T::AccountId,
+			>(Reserved::<T>::take(&player).unwrap_or_default())?;
+			<T as pallet::Config>::Currency::unreserve_named(
+				&STAKING_POOL_RESERVE_ID,
+				&Self::payer(&player),
+				staking_amount,
+			);
+
+			Self::deposit_event(Event::<T>::LeftWaitlist { player, pool_id });
+			Ok(())
+		}
+
+		/// Promote the players waiting for the level while it has a free slot
+		fn promote(pool_id: ID) -> DispatchResult {
+			while Self::level_player_count(pool_id) < Self::max_player(pool_id) {
+				let next = Waitlist::<T>::mutate_exists(pool_id, |maybe_waitlist| {
+					let waitlist = maybe_waitlist.as_mut().filter(|waitlist| !waitlist.is_empty())?;
+					let player = waitlist.remove(0);
+					if waitlist.is_empty() {
+						*maybe_waitlist = None;
+					}
+					Some(player)
+				});
+				let player = match next {
+					Some(player) => player,
+					None => break,
+				};
+
+				Waitlisted::<T>::remove(&player);
+				let amount = Reserved::<T>::get(&player).unwrap_or_default();
+				Self::activate(player.clone(), pool_id, amount)?;
+				Self::deposit_event(Event::<T>::Promoted { player, pool_id });
+			}
+			Ok(())
+		}
+
+		/// The position of the player in the waitlist, starting from 1
+		pub fn waitlist_position(player: &T::AccountId) -> Option<u32> {
+			let pool_id = Waitlisted::<T>::get(player)?;
+			Waitlist::<T>::get(pool_id)
+				.iter()
+				.position(|waiting| waiting == player)
+				.map(|index| index as u32 + 1)
+		}
+
 		fn unstake_pool(sender: T::AccountId, new_player_count: u32) {
 			<PlayerCount<T>>::put(new_player_count);
+			Reserved::<T>::remove(&sender);
 			Tickets::<T>::remove(sender);
 		}
 
+		/// The amount staked by the player in the pool
+		pub(crate) fn reserved_amount(player: &T::AccountId, pool_id: ID) -> u128 {
+			match Reserved::<T>::get(player) {
+				Some(amount) => amount,
+				None => LegacyValues::<T>::get(pool_id)
+					.or_else(|| Services::<T>::get(pool_id).map(|service| service.value))
+					.unwrap_or_default(),
+			}
+		}
+
+		/// Queue the amount to be released after UnbondingPeriod, it stays reserved until the
+		/// player withdraws it
+		fn unbond(player: &T::AccountId, amount: u128) -> DispatchResult {
+			if amount == 0 {
+				return Ok(());
+			}
+			let unlock_at =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::UnbondingPeriod::get());
+			Unlocking::<T>::try_mutate(player, |chunks| {
+				chunks
+					.try_push(UnlockChunk { amount, unlock_at })
+					.map_err(|_| <Error<T>>::TooManyUnlockingChunks)
+			})?;
+
+			Self::deposit_event(Event::<T>::Unbonding {
+				player: player.clone(),
+				amount,
+				unlock_at,
+			});
+			Ok(())
+		}
+
+		/// The account holding the staking rewards, the treasury allocations are sent here
+		pub fn rewards_account() -> Option<T::AccountId> {
+			into_account::<T::AccountId>(REWARDS_POT_SEED.using_encoded(blake2_256))
+		}
+
+		/// The amount staked by the player, zero when the player is not staking
+		fn staked_amount(player: &T::AccountId) -> u128 {
+			match Tickets::<T>::get(player).map(|ticket| ticket.ticket_type) {
+				Some(TicketType::System(system_ticket)) => {
+					Self::reserved_amount(player, Convertor::into_id(system_ticket))
+				}
+				_ => 0,
+			}
+		}
+
+		/// The rewards of the player accrued up to the current RewardPerStake
+		fn current_reward(player: &T::AccountId) -> StakerReward {
+			let reward_per_stake = Self::reward_per_stake();
+			let reward = StakerRewards::<T>::get(player).unwrap_or_default();
+			let accrued = reward_per_stake
+				.saturating_sub(reward.reward_per_stake)
+				.saturating_mul_int(Self::staked_amount(player));
+			StakerReward {
+				reward_per_stake,
+				pending: reward.pending.saturating_add(accrued),
+			}
+		}
+
+		fn set_staker_reward(player: &T::AccountId, reward: StakerReward) {
+			if reward.pending == 0 && !Tickets::<T>::contains_key(player) {
+				StakerRewards::<T>::remove(player);
+			} else {
+				StakerRewards::<T>::insert(player, reward);
+			}
+		}
+
+		/// Accrue the rewards of the player, must be called before the staked amount changes
+		fn accrue_rewards(player: &T::AccountId) -> StakerReward {
+			let reward = Self::current_reward(player);
+			Self::set_staker_reward(player, reward);
+			reward
+		}
+
+		/// The rewards the player can claim
+		pub fn pending_rewards(player: &T::AccountId) -> u128 {
+			Self::current_reward(player).pending
+		}
+
+		/// Apply the service changes, return the number of the services changed
+		pub(crate) fn apply_next_services() -> u32 {
+			let mut count = 0_u32;
+			for (pool_id, service) in NextServices::<T>::drain() {
+				count = count.saturating_add(1);
+				if let Some(old_service) = Services::<T>::get(pool_id) {
+					if old_service.value != service.value &&
+						!LegacyValues::<T>::contains_key(pool_id)
+					{
+						LegacyValues::<T>::insert(pool_id, old_service.value);
+					}
+				}
+				Services::<T>::insert(pool_id, service);
+				Self::deposit_event(Event::<T>::ServiceChanged { pool_id, service });
+			}
+			count
+		}
+
+		/// Distribute the new balance of the rewards pot to the stakers pro rata by the
+		/// amount they staked
+		pub(crate) fn distribute_rewards() {
+			let total_staked = Self::total_staked();
+			if total_staked == 0 {
+				return;
+			}
+			let pot = match Self::rewards_account() {
+				Some(pot) => pot,
+				None => return,
+			};
+			let available = <T as pallet::Config>::Currency::free_balance(&pot)
+				.saturating_sub(<T as pallet::Config>::Currency::minimum_balance());
+			let amount = balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(
+				available,
+			)
+			.unwrap_or_default()
+			.saturating_sub(Self::unclaimed_rewards());
+			if amount == 0 {
+				return;
+			}
+
+			RewardPerStake::<T>::mutate(|reward_per_stake| {
+				*reward_per_stake = reward_per_stake
+					.saturating_add(FixedU128::saturating_from_rational(amount, total_staked))
+			});
+			UnclaimedRewards::<T>::mutate(|unclaimed| *unclaimed = unclaimed.saturating_add(amount));
+			Self::deposit_event(Event::<T>::RewardsDistributed {
+				amount,
+				total_staked,
+			});
+		}
+
 		pub fn moment_to_u128(input: T::Moment) -> u128 {
 			sp_runtime::SaturatedConversion::saturated_into(input)
 		}
 
-		fn get_player_level(player: T::AccountId) -> Option<TicketLevel> {
+		pub(crate) fn get_player_level(player: T::AccountId) -> Option<TicketLevel> {
 			match Tickets::<T>::get(player) {
 				Some(ticket) => {
 					if let TicketType::System(SystemTicket::Staking(level)) = ticket.ticket_type {
@@ -254,3 +1035,40 @@ pub mod pallet {
 		}
 	}
 }
+
+/// The transaction fees sent to the rewards pot, distributed to the stakers in the next period
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		if let Some(pot) = Pallet::<T>::rewards_account() {
+			<T as pallet::Config>::Currency::resolve_creating(&pot, amount);
+		}
+	}
+}
+
+/// Send FeeShare of the transaction fees to the staking rewards and the rest to `Rest`
+pub struct ShareFees<T, Rest>(PhantomData<(T, Rest)>);
+
+impl<T, Rest> OnUnbalanced<NegativeImbalanceOf<T>> for ShareFees<T, Rest>
+where
+	T: Config,
+	Rest: OnUnbalanced<NegativeImbalanceOf<T>>,
+{
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>)
+	where
+		NegativeImbalanceOf<T>: Imbalance<B>,
+	{
+		if let Some(fees) = fees_then_tips.next() {
+			let share = FeeShare::<T>::get() * Imbalance::<BalanceOf<T>>::peek(&fees);
+			let (rewards, rest) = Imbalance::<BalanceOf<T>>::split(fees, share);
+			Pallet::<T>::on_unbalanced(rewards);
+			Rest::on_unbalanceds(Some(rest).into_iter().chain(fees_then_tips));
+		}
+	}
+
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let share = FeeShare::<T>::get() * amount.peek();
+		let (rewards, rest) = amount.split(share);
+		Pallet::<T>::on_unbalanced(rewards);
+		Rest::on_unbalanced(rest);
+	}
+}
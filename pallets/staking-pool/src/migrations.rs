@@ -0,0 +1,168 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use super::*;
+use frame_support::{
+	storage::{storage_prefix, unhashed},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess},
+};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Move the staking amount reserved by the players to STAKING_POOL_RESERVE_ID
+	pub struct MigrateToNamedReserve<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToNamedReserve<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut players = 0_u64;
+			for (player, ticket) in Tickets::<T>::iter() {
+				if let TicketType::System(system_ticket) = ticket.ticket_type {
+					let staking_amount = Pallet::<T>::reserved_amount(
+						&player,
+						Convertor::into_id(system_ticket),
+					);
+					if let Ok(staking_amount) = u128_try_to_balance::<
+						<T as pallet::Config>::Currency,
+						T::AccountId,
+					>(staking_amount)
+					{
+						gu_currency::move_to_named_reserve::<
+							T::AccountId,
+							<T as pallet::Config>::Currency,
+						>(&STAKING_POOL_RESERVE_ID, &player, staking_amount);
+					}
+				}
+				players = players.saturating_add(1);
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				players.saturating_mul(4).saturating_add(1),
+				players.saturating_add(1),
+			)
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// Initialize TotalStaked with the amount staked by the players joined before the
+	/// staking rewards
+	pub struct InitializeTotalStaked<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for InitializeTotalStaked<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut players = 0_u64;
+			let mut total_staked = 0_u128;
+			for (player, ticket) in Tickets::<T>::iter() {
+				if let TicketType::System(system_ticket) = ticket.ticket_type {
+					total_staked = total_staked.saturating_add(Pallet::<T>::reserved_amount(
+						&player,
+						Convertor::into_id(system_ticket),
+					));
+				}
+				players = players.saturating_add(1);
+			}
+			TotalStaked::<T>::put(total_staked);
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				players.saturating_mul(3).saturating_add(1),
+				2,
+			)
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Count the players staking in each level, the capacity of the levels is not limited
+	/// when MaxPlayer was never set
+	pub struct InitializeLevelPlayerCount<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for InitializeLevelPlayerCount<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut players = 0_u64;
+			for (_, ticket) in Tickets::<T>::iter() {
+				if let TicketType::System(system_ticket) = ticket.ticket_type {
+					LevelPlayerCount::<T>::mutate(Convertor::into_id(system_ticket), |count| {
+						*count = count.saturating_add(1)
+					});
+				}
+				players = players.saturating_add(1);
+			}
+			let max_player_key = v4::old_max_player_key::<T>();
+			if unhashed::get::<u32>(&max_player_key).unwrap_or_default() == 0 {
+				unhashed::put(&max_player_key, &u32::MAX);
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				players.saturating_mul(2).saturating_add(2),
+				players.saturating_add(2),
+			)
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// The storage key of MaxPlayer when it was a single value for all the levels
+	pub(crate) fn old_max_player_key<T: Config>() -> [u8; 32] {
+		storage_prefix(<Pallet<T> as PalletInfoAccess>::name().as_bytes(), b"MaxPlayer")
+	}
+
+	/// Move the capacity shared by all the levels to the capacity of each level
+	pub struct MigrateToLevelMaxPlayer<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToLevelMaxPlayer<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut levels = 0_u64;
+			if let Some(max_player) = unhashed::take::<u32>(&old_max_player_key::<T>()) {
+				for pool_id in Services::<T>::iter_keys() {
+					MaxPlayer::<T>::insert(pool_id, max_player);
+					levels = levels.saturating_add(1);
+				}
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(levels.saturating_add(2), levels.saturating_add(2))
+		}
+	}
+}
@@ -56,7 +56,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -135,11 +135,24 @@ impl SystemDefaultServices for StakingPoolDefaultServices {
 	}
 }
 
+parameter_types! {
+	pub UnbondingPeriod: u64 = 10;
+	pub MaxUnlockingChunks: u32 = 5;
+	pub MaxWaitlist: u32 = 5;
+	pub MaxBeneficiaries: u32 = 2;
+}
+
 impl staking_pool::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = ();
 	type StakingServices = StakingPoolDefaultServices;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
+	type MasterPool = ();
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxWaitlist = MaxWaitlist;
+	type MaxBeneficiaries = MaxBeneficiaries;
 }
 
 // Build genesis storage according to the mock runtime.
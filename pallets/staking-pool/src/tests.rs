@@ -3,11 +3,29 @@
 * and not related with Currency e.g. Balances, Transaction Payment
 */
 use crate::{mock::*};
-use crate::{PlayerCount, Tickets};
-use frame_support::{assert_ok, traits::Currency};
+use crate::{Error, LevelPlayerCount, MaxPlayer, NextServices, PlayerCount, ShareFees, Tickets};
+use crate::{TotalStaked, UnlockChunk};
+use crate::migrations::{
+	v1::MigrateToNamedReserve,
+	v2::InitializeTotalStaked,
+	v3::InitializeLevelPlayerCount,
+	v4::{old_max_player_key, MigrateToLevelMaxPlayer},
+};
+use frame_support::{
+	assert_err, assert_ok,
+	storage::unhashed,
+	traits::{
+		Currency, GetStorageVersion, NamedReservableCurrency, OnRuntimeUpgrade, OnUnbalanced,
+		ReservableCurrency, StorageVersion,
+	},
+};
 use gafi_primitives::currency::{unit, NativeToken::GAKI};
-use gafi_primitives::{ticket::TicketLevel, system_services::SystemPool, constant::ID};
-use sp_runtime::AccountId32;
+use gafi_primitives::{
+	ticket::{SystemTicket, TicketLevel},
+	system_services::{Convertor, SponsoredJoin, SystemPool, SystemService},
+	constant::{ID, STAKING_POOL_RESERVE_ID},
+};
+use sp_runtime::{traits::BadOrigin, AccountId32, Permill};
 use sp_std::str::FromStr;
 
 const STAKING_BASIC_ID: ID = [223, 236, 215, 227, 124, 27, 202, 81, 144, 36, 86, 22, 116, 218, 112, 227, 22, 53, 161, 192, 104, 124, 153, 71, 95, 117, 111, 122, 147, 230, 110, 79];
@@ -73,3 +91,511 @@ fn leave_pool_should_work() {
 		assert_eq!(Tickets::<Test>::get(alice.clone()), None);
 	})
 }
+
+#[test]
+fn leave_unbonds_stake_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		run_to_block(2);
+		assert_ok!(StakingPool::leave(alice.clone()));
+
+		// the ticket is removed at once but the stake stays reserved
+		assert_eq!(Tickets::<Test>::get(alice.clone()), None);
+		assert_eq!(Balances::reserved_balance(&alice), 1000 * unit(GAKI));
+		let unlock_at = 2 + UnbondingPeriod::get();
+		System::assert_last_event(Event::StakingPool(crate::Event::Unbonding {
+			player: alice.clone(),
+			amount: 1000 * unit(GAKI),
+			unlock_at,
+		}));
+		assert_err!(
+			StakingPool::withdraw_unbonded(Origin::signed(alice.clone())),
+			<Error<Test>>::NothingToWithdraw
+		);
+
+		run_to_block(unlock_at);
+		assert_ok!(StakingPool::withdraw_unbonded(Origin::signed(alice.clone())));
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(Balances::free_balance(&alice), 1_000_000 * unit(GAKI));
+		assert_eq!(StakingPool::unlocking(alice.clone()).len(), 0);
+		System::assert_last_event(Event::StakingPool(crate::Event::Withdrawn {
+			player: alice,
+			amount: 1000 * unit(GAKI),
+		}));
+	})
+}
+
+#[test]
+fn leave_exceed_max_unlocking_chunks_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		for _ in 0..MaxUnlockingChunks::get() {
+			assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+			assert_ok!(StakingPool::leave(alice.clone()));
+		}
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		assert_err!(
+			StakingPool::leave(alice.clone()),
+			<Error<Test>>::TooManyUnlockingChunks
+		);
+	})
+}
+
+#[test]
+fn change_level_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		let join_time = Tickets::<Test>::get(alice.clone()).unwrap().join_time;
+
+		run_to_block(2);
+		assert_ok!(StakingPool::change_level(alice.clone(), STAKING_ADVANCE_ID));
+		assert_eq!(StakingPool::get_player_level(alice.clone()), Some(TicketLevel::Advance));
+		assert_eq!(Tickets::<Test>::get(alice.clone()).unwrap().join_time, join_time);
+		assert_eq!(PlayerCount::<Test>::get(), 1);
+
+		assert_ok!(StakingPool::change_level(alice.clone(), STAKING_MEDIUM_ID));
+		assert_eq!(StakingPool::get_player_level(alice.clone()), Some(TicketLevel::Medium));
+
+		// the amount over the new level is unbonded
+		assert_eq!(
+			StakingPool::unlocking(alice.clone()).to_vec(),
+			vec![UnlockChunk { amount: 500 * unit(GAKI), unlock_at: 2 + UnbondingPeriod::get() }]
+		);
+		assert_eq!(Balances::reserved_balance(&alice), 2000 * unit(GAKI));
+	})
+}
+
+#[test]
+fn add_service_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let level = TicketLevel::Custom(1);
+		let pool_id = Convertor::into_id(SystemTicket::Staking(level));
+		let service = SystemService::new(level, 20_u32, Permill::from_percent(90), 5000 * unit(GAKI));
+		let alice = new_account(1_000_000 * unit(GAKI));
+
+		assert_err!(StakingPool::add_service(Origin::signed(alice.clone()), service), BadOrigin);
+		assert_ok!(StakingPool::add_service(Origin::root(), service));
+		assert_eq!(StakingPool::get_service(pool_id), Some(service));
+		assert_err!(
+			StakingPool::add_service(Origin::root(), service),
+			<Error<Test>>::ServiceAlreadyExists
+		);
+
+		assert_ok!(StakingPool::join(alice.clone(), pool_id));
+		assert_eq!(StakingPool::get_player_level(alice), Some(level));
+	})
+}
+
+#[test]
+fn update_service_keeps_staked_amount_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		let staked = Balances::reserved_balance(&alice);
+
+		let service = SystemService::new(TicketLevel::Basic, 20_u32, Permill::from_percent(40), staked * 2);
+		let old_service = StakingPool::get_service(STAKING_BASIC_ID).unwrap();
+		assert_ok!(StakingPool::update_service(Origin::root(), service));
+		assert_eq!(StakingPool::get_service(STAKING_BASIC_ID), Some(old_service));
+		assert_eq!(NextServices::<Test>::get(STAKING_BASIC_ID), Some(service));
+
+		// the change takes effect at the next period
+		StakingPool::apply_next_services();
+		assert_eq!(StakingPool::get_service(STAKING_BASIC_ID), Some(service));
+		assert_eq!(NextServices::<Test>::get(STAKING_BASIC_ID), None);
+
+		assert_ok!(StakingPool::leave(alice.clone()));
+		run_to_block(1 + UnbondingPeriod::get());
+		assert_ok!(StakingPool::withdraw_unbonded(Origin::signed(alice.clone())));
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+	})
+}
+
+#[test]
+fn retire_service_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+
+		assert_ok!(StakingPool::retire_service(Origin::root(), TicketLevel::Medium));
+		assert_err!(
+			StakingPool::retire_service(Origin::root(), TicketLevel::Medium),
+			<Error<Test>>::ServiceRetired
+		);
+		assert_err!(
+			StakingPool::change_level(alice.clone(), STAKING_MEDIUM_ID),
+			<Error<Test>>::ServiceRetired
+		);
+
+		// the level is retired once the players staked left
+		assert_err!(
+			StakingPool::retire_service(Origin::root(), TicketLevel::Basic),
+			<Error<Test>>::LevelNotEmpty
+		);
+		assert_ok!(StakingPool::leave(alice.clone()));
+		assert_ok!(StakingPool::retire_service(Origin::root(), TicketLevel::Basic));
+		assert_err!(StakingPool::join(alice, STAKING_BASIC_ID), <Error<Test>>::ServiceRetired);
+	})
+}
+
+#[test]
+fn retire_service_with_waitlist_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 0));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		assert_eq!(StakingPool::level_player_count(STAKING_BASIC_ID), 0);
+
+		assert_err!(
+			StakingPool::retire_service(Origin::root(), TicketLevel::Basic),
+			<Error<Test>>::LevelNotEmpty
+		);
+		assert_ok!(StakingPool::leave(alice));
+		assert_ok!(StakingPool::retire_service(Origin::root(), TicketLevel::Basic));
+	})
+}
+
+#[test]
+fn migrate_to_named_reserve_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		let staked = Balances::reserved_balance_named(&STAKING_POOL_RESERVE_ID, &alice);
+		assert_eq!(Balances::reserved_balance(&alice), staked);
+
+		// the staking amount reserved before the named reserves
+		Balances::unreserve_named(&STAKING_POOL_RESERVE_ID, &alice, staked);
+		assert_ok!(Balances::reserve(&alice, staked));
+		StorageVersion::new(0).put::<StakingPool>();
+
+		MigrateToNamedReserve::<Test>::on_runtime_upgrade();
+		assert_eq!(Balances::reserved_balance_named(&STAKING_POOL_RESERVE_ID, &alice), staked);
+		assert_eq!(StakingPool::on_chain_storage_version(), 1);
+
+		assert_ok!(StakingPool::leave(alice.clone()));
+		run_to_block(1 + UnbondingPeriod::get());
+		assert_ok!(StakingPool::withdraw_unbonded(Origin::signed(alice.clone())));
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+	})
+}
+
+#[test]
+fn distribute_and_claim_rewards_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(3, 1_000_000 * unit(GAKI));
+		let pot = StakingPool::rewards_account().unwrap();
+		make_deposit(&pot, EXISTENTIAL_DEPOSIT);
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_ADVANCE_ID));
+		assert_eq!(StakingPool::total_staked(), 3000 * unit(GAKI));
+
+		// rewards are shared pro rata by the amount staked
+		make_deposit(&pot, 30 * unit(GAKI));
+		StakingPool::distribute_rewards();
+		assert_eq!(StakingPool::pending_rewards(&accounts[0]), 10 * unit(GAKI));
+		assert_eq!(StakingPool::pending_rewards(&accounts[1]), 20 * unit(GAKI));
+		assert_eq!(StakingPool::unclaimed_rewards(), 30 * unit(GAKI));
+
+		// the unclaimed rewards are not distributed again
+		StakingPool::distribute_rewards();
+		assert_eq!(StakingPool::pending_rewards(&accounts[0]), 10 * unit(GAKI));
+
+		// the player joined after the distribution gets nothing from it
+		assert_ok!(StakingPool::join(accounts[2].clone(), STAKING_BASIC_ID));
+		assert_eq!(StakingPool::pending_rewards(&accounts[2]), 0);
+		assert_err!(
+			StakingPool::claim_rewards(Origin::signed(accounts[2].clone())),
+			<Error<Test>>::NoRewards
+		);
+
+		let before_balance = Balances::free_balance(&accounts[0]);
+		assert_ok!(StakingPool::claim_rewards(Origin::signed(accounts[0].clone())));
+		assert_eq!(Balances::free_balance(&accounts[0]), before_balance + 10 * unit(GAKI));
+		assert_eq!(StakingPool::pending_rewards(&accounts[0]), 0);
+		assert_eq!(StakingPool::unclaimed_rewards(), 20 * unit(GAKI));
+		assert_eq!(Balances::free_balance(&pot), EXISTENTIAL_DEPOSIT + 20 * unit(GAKI));
+	})
+}
+
+#[test]
+fn rewards_accrue_lazily_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		let pot = StakingPool::rewards_account().unwrap();
+		make_deposit(&pot, EXISTENTIAL_DEPOSIT + 10 * unit(GAKI));
+		assert_ok!(StakingPool::join(alice.clone(), STAKING_BASIC_ID));
+		StakingPool::distribute_rewards();
+
+		// the rewards accrued with the old level are kept after changing level
+		assert_ok!(StakingPool::change_level(alice.clone(), STAKING_ADVANCE_ID));
+		assert_eq!(StakingPool::total_staked(), 2000 * unit(GAKI));
+		make_deposit(&pot, 10 * unit(GAKI));
+		StakingPool::distribute_rewards();
+		assert_eq!(StakingPool::pending_rewards(&alice), 20 * unit(GAKI));
+
+		// the rewards are still claimable after leaving
+		assert_ok!(StakingPool::leave(alice.clone()));
+		assert_eq!(StakingPool::total_staked(), 0);
+		assert_eq!(StakingPool::pending_rewards(&alice), 20 * unit(GAKI));
+		assert_ok!(StakingPool::claim_rewards(Origin::signed(alice.clone())));
+		assert_eq!(StakingPool::staker_rewards(&alice), None);
+	})
+}
+
+#[test]
+fn share_fees_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let pot = StakingPool::rewards_account().unwrap();
+		assert_err!(
+			StakingPool::set_fee_share(Origin::signed(new_account(0)), Permill::from_percent(30)),
+			BadOrigin
+		);
+		assert_ok!(StakingPool::set_fee_share(Origin::root(), Permill::from_percent(30)));
+		assert_eq!(StakingPool::fee_share(), Permill::from_percent(30));
+
+		let issuance = Balances::total_issuance();
+		ShareFees::<Test, ()>::on_unbalanced(Balances::issue(100 * unit(GAKI)));
+		assert_eq!(Balances::free_balance(&pot), 30 * unit(GAKI));
+		// the rest is handled by the inner OnUnbalanced, burned here
+		assert_eq!(Balances::total_issuance(), issuance + 30 * unit(GAKI));
+	})
+}
+
+#[test]
+fn initialize_total_staked_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(2, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_MEDIUM_ID));
+
+		TotalStaked::<Test>::kill();
+		StorageVersion::new(1).put::<StakingPool>();
+		InitializeTotalStaked::<Test>::on_runtime_upgrade();
+		assert_eq!(StakingPool::total_staked(), 2500 * unit(GAKI));
+		assert_eq!(StakingPool::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+fn join_full_level_waitlist_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(3, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 1));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+
+		// the players over the capacity wait with the staking amount reserved
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[2].clone(), STAKING_BASIC_ID));
+		System::assert_last_event(Event::StakingPool(crate::Event::JoinedWaitlist {
+			player: accounts[2].clone(),
+			pool_id: STAKING_BASIC_ID,
+			position: 2,
+		}));
+		assert_eq!(Tickets::<Test>::get(accounts[1].clone()), None);
+		assert_eq!(StakingPool::is_suspended(&accounts[1]), true);
+		assert_eq!(Balances::reserved_balance(&accounts[1]), 1000 * unit(GAKI));
+		assert_eq!(StakingPool::player_count(), 1);
+		assert_eq!(StakingPool::waitlist_position(&accounts[2]), Some(2));
+
+		// the first player waiting is promoted when someone leaves
+		assert_ok!(StakingPool::leave(accounts[0].clone()));
+		System::assert_last_event(Event::StakingPool(crate::Event::Promoted {
+			player: accounts[1].clone(),
+			pool_id: STAKING_BASIC_ID,
+		}));
+		assert_eq!(StakingPool::is_suspended(&accounts[1]), false);
+		assert_eq!(StakingPool::get_player_level(accounts[1].clone()), Some(TicketLevel::Basic));
+		assert_eq!(StakingPool::total_staked(), 1000 * unit(GAKI));
+		assert_eq!(StakingPool::waitlist_position(&accounts[2]), Some(1));
+
+		// leaving the waitlist releases the staking amount at once
+		assert_ok!(StakingPool::leave(accounts[2].clone()));
+		assert_eq!(Balances::reserved_balance(&accounts[2]), 0);
+		assert_eq!(StakingPool::waitlist_position(&accounts[2]), None);
+		assert_eq!(StakingPool::waitlist(STAKING_BASIC_ID).len(), 0);
+	})
+}
+
+#[test]
+fn set_max_player_promotes_waitlist_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(2, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 1));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_BASIC_ID));
+		assert_err!(
+			StakingPool::change_level(accounts[1].clone(), STAKING_MEDIUM_ID),
+			<Error<Test>>::PlayerWaitlisted
+		);
+
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 2));
+		assert_eq!(StakingPool::is_suspended(&accounts[1]), false);
+		assert_eq!(StakingPool::level_player_count(STAKING_BASIC_ID), 2);
+		assert_eq!(StakingPool::player_count(), 2);
+	})
+}
+
+#[test]
+fn set_max_player_each_level_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(3, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 1));
+		System::assert_last_event(Event::StakingPool(crate::Event::StakingNewMaxPlayer {
+			pool_id: STAKING_BASIC_ID,
+			new_max_player: 1,
+		}));
+		assert_eq!(StakingPool::max_player(STAKING_BASIC_ID), 1);
+		assert_eq!(StakingPool::max_player(STAKING_MEDIUM_ID), u32::MAX);
+
+		// the other levels keep their capacity
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_MEDIUM_ID));
+		assert_ok!(StakingPool::join(accounts[2].clone(), STAKING_MEDIUM_ID));
+		assert_eq!(StakingPool::level_player_count(STAKING_MEDIUM_ID), 2);
+		assert_eq!(StakingPool::waitlist(STAKING_MEDIUM_ID).len(), 0);
+	})
+}
+
+#[test]
+fn set_max_player_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let account = new_account(1_000_000 * unit(GAKI));
+		assert_err!(
+			StakingPool::set_max_player(Origin::signed(account), TicketLevel::Basic, 1),
+			BadOrigin
+		);
+		assert_err!(
+			StakingPool::set_max_player(Origin::root(), TicketLevel::Custom(9), 1),
+			<Error<Test>>::PoolNotFound
+		);
+	})
+}
+
+#[test]
+fn change_level_to_full_level_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(2, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Medium, 1));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_MEDIUM_ID));
+
+		assert_err!(
+			StakingPool::change_level(accounts[0].clone(), STAKING_MEDIUM_ID),
+			<Error<Test>>::LevelFull
+		);
+		assert_ok!(StakingPool::change_level(accounts[1].clone(), STAKING_ADVANCE_ID));
+		assert_eq!(StakingPool::level_player_count(STAKING_MEDIUM_ID), 0);
+		assert_eq!(StakingPool::level_player_count(STAKING_ADVANCE_ID), 1);
+	})
+}
+
+#[test]
+fn initialize_level_player_count_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(3, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[1].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join(accounts[2].clone(), STAKING_ADVANCE_ID));
+
+		LevelPlayerCount::<Test>::remove_all(None);
+		StorageVersion::new(2).put::<StakingPool>();
+		InitializeLevelPlayerCount::<Test>::on_runtime_upgrade();
+		assert_eq!(StakingPool::level_player_count(STAKING_BASIC_ID), 2);
+		assert_eq!(StakingPool::level_player_count(STAKING_ADVANCE_ID), 1);
+		assert_eq!(unhashed::get::<u32>(&old_max_player_key::<Test>()), Some(u32::MAX));
+		assert_eq!(StakingPool::on_chain_storage_version(), 3);
+	})
+}
+
+#[test]
+fn migrate_to_level_max_player_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		MaxPlayer::<Test>::remove_all(None);
+		unhashed::put(&old_max_player_key::<Test>(), &10_u32);
+		StorageVersion::new(3).put::<StakingPool>();
+		MigrateToLevelMaxPlayer::<Test>::on_runtime_upgrade();
+		assert_eq!(StakingPool::max_player(STAKING_BASIC_ID), 10);
+		assert_eq!(StakingPool::max_player(STAKING_MEDIUM_ID), 10);
+		assert_eq!(StakingPool::max_player(STAKING_ADVANCE_ID), 10);
+		assert_eq!(unhashed::get::<u32>(&old_max_player_key::<Test>()), None);
+		assert_eq!(StakingPool::on_chain_storage_version(), 4);
+	})
+}
+
+#[test]
+fn join_for_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(4, 1_000_000 * unit(GAKI));
+		let sponsor = accounts[0].clone();
+		assert_err!(
+			StakingPool::join_for(sponsor.clone(), sponsor.clone(), STAKING_BASIC_ID),
+			<Error<Test>>::InvalidBeneficiary
+		);
+		assert_ok!(StakingPool::join_for(sponsor.clone(), accounts[1].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join_for(sponsor.clone(), accounts[2].clone(), STAKING_MEDIUM_ID));
+		assert_err!(
+			StakingPool::join_for(sponsor.clone(), accounts[3].clone(), STAKING_BASIC_ID),
+			<Error<Test>>::TooManyBeneficiaries
+		);
+
+		assert_eq!(Balances::reserved_balance(&sponsor), 2500 * unit(GAKI));
+		assert_eq!(Balances::reserved_balance(&accounts[1]), 0);
+		assert_eq!(StakingPool::sponsor_of(&accounts[1]), Some(sponsor.clone()));
+		assert_eq!(
+			StakingPool::beneficiaries(&sponsor).to_vec(),
+			vec![accounts[1].clone(), accounts[2].clone()]
+		);
+		assert_eq!(StakingPool::get_player_level(accounts[1].clone()), Some(TicketLevel::Basic));
+		assert_err!(
+			StakingPool::change_level(accounts[1].clone(), STAKING_ADVANCE_ID),
+			<Error<Test>>::SponsoredStake
+		);
+
+		// the stake is unbonded to the sponsor
+		assert_ok!(StakingPool::leave(accounts[1].clone()));
+		assert_eq!(StakingPool::sponsor_of(&accounts[1]), None);
+		assert_eq!(StakingPool::beneficiaries(&sponsor).to_vec(), vec![accounts[2].clone()]);
+		run_to_block(1 + UnbondingPeriod::get());
+		assert_ok!(StakingPool::withdraw_unbonded(Origin::signed(sponsor.clone())));
+		assert_eq!(Balances::reserved_balance(&sponsor), 1500 * unit(GAKI));
+	})
+}
+
+#[test]
+fn join_for_waitlist_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = _new_accounts(3, 1_000_000 * unit(GAKI));
+		assert_ok!(StakingPool::set_max_player(Origin::root(), TicketLevel::Basic, 1));
+		assert_ok!(StakingPool::join(accounts[0].clone(), STAKING_BASIC_ID));
+		assert_ok!(StakingPool::join_for(accounts[1].clone(), accounts[2].clone(), STAKING_BASIC_ID));
+		assert_eq!(StakingPool::waitlist_position(&accounts[2]), Some(1));
+
+		// leaving the waitlist returns the stake to the sponsor at once
+		assert_ok!(StakingPool::leave(accounts[2].clone()));
+		assert_eq!(Balances::reserved_balance(&accounts[1]), 0);
+		assert_eq!(StakingPool::beneficiaries(&accounts[1]).len(), 0);
+	})
+}
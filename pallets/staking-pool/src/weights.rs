@@ -35,28 +35,147 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
 	fn set_max_player(s: u32, ) -> Weight;
+	fn add_service() -> Weight;
+	fn update_service() -> Weight;
+	fn retire_service() -> Weight;
+	fn set_fee_share() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn distribute_rewards() -> Weight;
+	fn withdraw_unbonded(s: u32, ) -> Weight;
 }
 
 /// Weight functions for `staking_pool`.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-		// Storage: System Number (r:1 w:0)
+	// Storage: StakingPool Services (r:1 w:0)
+	// Storage: StakingPool MaxPlayer (r:0 w:1)
+	// Storage: StakingPool LevelPlayerCount (r:1 w:1)
+	// Storage: StakingPool Waitlist (r:1 w:1)
+	// Storage: StakingPool Waitlisted (r:0 w:1)
+	// Storage: StakingPool Reserved (r:1 w:1)
+	// Storage: StakingPool PlayerCount (r:1 w:1)
+	// Storage: StakingPool RewardPerStake (r:1 w:0)
+	// Storage: StakingPool StakerRewards (r:1 w:1)
+	// Storage: StakingPool Tickets (r:1 w:1)
+	// Storage: StakingPool TotalStaked (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: System Number (r:1 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
-	// Storage: StakingPool MaxPlayer (r:0 w:1)
-	fn set_max_player(_s: u32, ) -> Weight {
-		(10_000_000_u64)
-			.saturating_add(T::DbWeight::get().reads(4_u64 ))
-			.saturating_add(T::DbWeight::get().writes(3_u64 ))
+	fn set_max_player(s: u32, ) -> Weight {
+		(12_000_000_u64)
+			.saturating_add((30_000_000_u64).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((11 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: StakingPool Services (r:1 w:1)
+	fn add_service() -> Weight {
+		(16_108_000_u64)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: StakingPool Services (r:1 w:0)
+	// Storage: StakingPool RetiredServices (r:1 w:0)
+	// Storage: StakingPool NextServices (r:0 w:1)
+	fn update_service() -> Weight {
+		(19_023_000_u64)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: StakingPool Services (r:1 w:0)
+	// Storage: StakingPool RetiredServices (r:1 w:1)
+	// Storage: StakingPool LevelPlayerCount (r:1 w:0)
+	// Storage: StakingPool Waitlist (r:1 w:0)
+	// Storage: StakingPool NextServices (r:0 w:1)
+	fn retire_service() -> Weight {
+		(15_871_000_u64)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: StakingPool FeeShare (r:0 w:1)
+	fn set_fee_share() -> Weight {
+		(12_352_000_u64)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: StakingPool RewardPerStake (r:1 w:0)
+	// Storage: StakingPool StakerRewards (r:1 w:1)
+	// Storage: StakingPool Tickets (r:1 w:0)
+	// Storage: StakingPool Reserved (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: StakingPool UnclaimedRewards (r:1 w:1)
+	fn claim_rewards() -> Weight {
+		(48_716_000_u64)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: StakingPool RewardsMarkTime (r:1 w:1)
+	// Storage: StakingPool TotalStaked (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	// Storage: StakingPool UnclaimedRewards (r:1 w:1)
+	// Storage: StakingPool RewardPerStake (r:1 w:1)
+	fn distribute_rewards() -> Weight {
+		(21_937_000_u64)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: StakingPool Unlocking (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded(s: u32, ) -> Weight {
+		(27_406_000_u64)
+			// Standard Error: 2_000
+			.saturating_add((143_000_u64).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 }
 
 
 impl WeightInfo for () {
-	fn set_max_player(_s: u32, ) -> Weight {
-		(10_000_000_u64)
-			.saturating_add(RocksDbWeight::get().reads(4_u64 ))
-			.saturating_add(RocksDbWeight::get().writes(3_u64 ))
+	fn set_max_player(s: u32, ) -> Weight {
+		(12_000_000_u64)
+			.saturating_add((30_000_000_u64).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((11 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(s as Weight)))
+	}
+	fn add_service() -> Weight {
+		(16_108_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn update_service() -> Weight {
+		(19_023_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn retire_service() -> Weight {
+		(15_871_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_fee_share() -> Weight {
+		(12_352_000_u64)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn claim_rewards() -> Weight {
+		(48_716_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn distribute_rewards() -> Weight {
+		(21_937_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn withdraw_unbonded(s: u32, ) -> Weight {
+		(27_406_000_u64)
+			.saturating_add((143_000_u64).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 }
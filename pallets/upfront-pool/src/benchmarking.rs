@@ -4,12 +4,108 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Pool;
 use crate::{Call, Config};
-use frame_benchmarking::{benchmarks};
+use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
+use sp_runtime::Permill;
+
+const UNIT: u128 = 1_000_000_000_000_000_000u128;
+
+fn new_player<T: Config>(index: u32) -> T::AccountId {
+	let player: T::AccountId = account("player", index, 0);
+	let balance = u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(1_000_000 * UNIT);
+	T::Currency::make_free_balance_be(&player, balance);
+	player
+}
 
 benchmarks! {
 	set_max_player {
 		let s in 0 .. 2;
 	}: _(RawOrigin::Root, s)
-}
 
+	start_charge {
+		let s in 0 .. T::MaxPlayerStorage::get();
+		// half of the players are moved from the NewPlayers
+		let ingame_players: Vec<T::AccountId> =
+			(0 .. s / 2).map(|i| account("player", i, 0)).collect();
+		let new_players: Vec<T::AccountId> =
+			(s / 2 .. s).map(|i| account("player", i, 0)).collect();
+		IngamePlayers::<T>::put(BoundedVec::try_from(ingame_players).unwrap());
+		NewPlayers::<T>::put(BoundedVec::try_from(new_players).unwrap());
+	}: {
+		Pallet::<T>::start_period(Pallet::<T>::get_timestamp());
+	}
+	verify {
+		assert_eq!(Pallet::<T>::charge_count(), s / 2);
+		assert_eq!(IngamePlayers::<T>::get().len(), s as usize);
+	}
+
+	add_service {
+		let service = SystemService::new(TicketLevel::Custom(0), 10_u32, Permill::from_percent(90), UNIT);
+	}: _(RawOrigin::Root, service)
+
+	update_service {
+		let service = SystemService::new(TicketLevel::Basic, 10_u32, Permill::from_percent(90), UNIT);
+	}: _(RawOrigin::Root, service)
+
+	retire_service {
+	}: _(RawOrigin::Root, TicketLevel::Basic, TicketLevel::Medium)
+
+	pay_arrears {
+		let caller = new_player::<T>(0);
+		Delinquents::<T>::insert(&caller, Delinquency { unpaid_periods: 1, arrears: UNIT });
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Delinquents::<T>::get(&caller).is_none());
+	}
+
+	prepay {
+		let caller = new_player::<T>(0);
+		let pool_id = Convertor::into_id(SystemTicket::Upfront(TicketLevel::Basic));
+		MaxPlayer::<T>::put(T::MaxPlayerStorage::get());
+		let _ = Pallet::<T>::join(caller.clone(), pool_id);
+		let discounts: Vec<(u32, Permill)> = (0 .. T::MaxPrepaidTiers::get())
+			.map(|i| (i + 1, Permill::from_percent(i)))
+			.collect();
+		PrepaidDiscounts::<T>::put(BoundedVec::try_from(discounts).unwrap());
+	}: _(RawOrigin::Signed(caller.clone()), T::MaxPrepaidPeriods::get())
+	verify {
+		assert!(Prepayments::<T>::get(&caller).is_some());
+	}
+
+	set_prepaid_discounts {
+		let discounts: Vec<(u32, Permill)> = (0 .. T::MaxPrepaidTiers::get())
+			.map(|i| (i + 1, Permill::from_percent(i)))
+			.collect();
+	}: _(RawOrigin::Root, discounts)
+
+	// charge_ingame is bounded by MaxChargePerBlock in a block, the players charged can't pay
+	// and are kicked while the period has MaxPlayerStorage players to charge
+	charge_ingame {
+		let s in 0 .. T::MaxChargePerBlock::get();
+		let pool_id = Convertor::into_id(SystemTicket::Upfront(TicketLevel::Basic));
+		MaxPlayer::<T>::put(T::MaxPlayerStorage::get());
+		for i in 0 .. s {
+			let player = new_player::<T>(i);
+			let _ = Pallet::<T>::join(player.clone(), pool_id);
+			T::Currency::make_free_balance_be(&player, T::Currency::minimum_balance());
+			Delinquents::<T>::insert(
+				&player,
+				Delinquency { unpaid_periods: T::GracePeriods::get(), arrears: UNIT },
+			);
+		}
+		let _ = Pallet::<T>::move_newplayer_to_ingame();
+		let _ = IngamePlayers::<T>::try_mutate(|players| {
+			for i in s .. T::MaxPlayerStorage::get() {
+				players.try_push(account("player", i, 0))?;
+			}
+			Ok::<(), T::AccountId>(())
+		});
+		Pallet::<T>::start_charge(Pallet::<T>::get_timestamp().saturating_add(1));
+	}: {
+		Pallet::<T>::charge_ingame(s);
+	}
+	verify {
+		assert_eq!(Pallet::<T>::charge_cursor(), s);
+		assert_eq!(Pallet::<T>::player_count(), 0);
+	}
+}
@@ -23,21 +23,42 @@ use frame_support::{
 	pallet_prelude::*,
 	traits::{
 		tokens::{ExistenceRequirement, WithdrawReasons},
-		Currency, ReservableCurrency,
+		Currency, NamedReservableCurrency, StorageVersion,
 	},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
 use gafi_primitives::pool::MasterPool;
 use gafi_primitives::{
-	constant::ID,
+	constant::{ReserveIdentifier, ID, UPFRONT_POOL_RESERVE_ID},
 	system_services::{SystemDefaultServices, SystemPool, SystemService, Convertor},
 	ticket::{SystemTicket, Ticket, TicketLevel, TicketType},
 };
-use gu_convertor::{u128_to_balance, u128_try_to_balance};
+use gu_convertor::{balance_try_to_u128, u128_to_balance, u128_try_to_balance};
 pub use pallet::*;
 use pallet_timestamp::{self as timestamp};
 use sp_io::hashing::blake2_256;
+use sp_runtime::Permill;
+
+/// The player who can't pay the service fee, keeps the ticket without the discount until
+/// the arrears are paid or GracePeriods is over
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct Delinquency {
+	pub unpaid_periods: u32,
+	pub arrears: u128,
+}
+
+/// The periods paid in advance by the player, charged instead of the service fee
+#[derive(
+	Eq, PartialEq, Clone, Copy, Encode, Decode, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct Prepayment {
+	pub periods: u32,
+	/// The fee paid for each period after the discount, refunded for the unused periods
+	pub period_fee: u128,
+}
 
 #[cfg(test)]
 mod mock;
@@ -48,6 +69,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub mod weights;
 pub use weights::*;
 
@@ -57,8 +80,12 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// The current storage version
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types it depends on.
@@ -67,8 +94,11 @@ pub mod pallet {
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency mechanism, the service fee is reserved with UPFRONT_POOL_RESERVE_ID
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
@@ -81,21 +111,69 @@ pub mod pallet {
 		type MaxPlayerStorage: Get<u32>;
 
 		type UpfrontServices: SystemDefaultServices;
+
+		/// Max number of player charged in a block, should be large enough to charge
+		/// MaxPlayerStorage players within a TimeService, otherwise the next billing period
+		/// starts late
+		#[pallet::constant]
+		type MaxChargePerBlock: Get<u32>;
+
+		/// The origin which may add, update and retire the services
+		type AdminOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Number of periods the player can leave unpaid before being kicked
+		#[pallet::constant]
+		type GracePeriods: Get<u32>;
+
+		/// Max number of periods the player can pay in advance
+		#[pallet::constant]
+		type MaxPrepaidPeriods: Get<u32>;
+
+		/// Max number of tiers of the prepaid discounts
+		#[pallet::constant]
+		type MaxPrepaidTiers: Get<u32>;
 	}
 
-	/// on_finalize following by steps:
-	/// 1. Check if current timestamp is the correct time to charge service fee
-	///	2. Charge player in the IngamePlayers - Kick player when they can't pay
+	/// on_initialize following by steps:
+	///	1. Start the new billing period if the boundary was reached and all players of the
+	///	previous period are charged
+	///	2. Charge at most MaxChargePerBlock players of the ChargingPlayers
+	///	- Mark player delinquent when they can't pay
+	///	- Kick player when they can't pay more than GracePeriods
+	///
+	/// The new billing period following by steps:
+	///	1. Apply the service changes of the new billing period
+	///	2. Move all players in the IngamePlayers to ChargingPlayers
 	///	3. Move all players from NewPlayer to IngamePlayers
 	///
+	/// on_finalize checks if current timestamp is the correct time to charge service fee
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
+			// ChargeCount and PendingCharge
+			let mut weight = T::DbWeight::get().reads(2);
+			if Self::charge_count() == 0 {
+				if let Some(charge_start) = PendingCharge::<T>::take() {
+					let (players, services) = Self::start_period(charge_start);
+					// NextServices, Services and LegacyValues of each service changed
+					let services = 3_u64.saturating_mul(services.into());
+					weight = weight
+						.saturating_add(<T as pallet::Config>::WeightInfo::start_charge(players))
+						.saturating_add(T::DbWeight::get().reads_writes(services, services));
+				}
+			}
+
+			let charged = Self::charge_ingame(T::MaxChargePerBlock::get());
+			// reserve the weight of on_finalize
+			weight
+				.saturating_add(<T as pallet::Config>::WeightInfo::charge_ingame(charged))
+				.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+		}
+
 		fn on_finalize(_block_number: BlockNumberFor<T>) {
 			let _now: u128 = Self::get_timestamp();
 			if _now - T::MasterPool::get_marktime() >= T::MasterPool::get_timeservice() {
-				let _ = Self::charge_ingame();
-				let _ = Self::move_newplayer_to_ingame();
-				Self::deposit_event(<Event<T>>::ChargePoolService);
+				PendingCharge::<T>::put(_now);
 			}
 		}
 	}
@@ -121,6 +199,25 @@ pub mod pallet {
 	#[pallet::getter(fn services)]
 	pub type Services<T: Config> = StorageMap<_, Twox64Concat, ID, SystemService>;
 
+	/// Holding the service changes, take effect at the next billing period
+	#[pallet::storage]
+	#[pallet::getter(fn next_services)]
+	pub type NextServices<T: Config> = StorageMap<_, Twox64Concat, ID, SystemService>;
+
+	/// Holding the retired services with the service that their players are moved to
+	#[pallet::storage]
+	#[pallet::getter(fn retired_services)]
+	pub type RetiredServices<T: Config> = StorageMap<_, Twox64Concat, ID, ID>;
+
+	/// Holding the amount reserved by the player when joining the pool
+	#[pallet::storage]
+	pub(super) type Reserved<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u128>;
+
+	/// Holding the service value before it first changed, which is reserved by the players
+	/// joined before Reserved was stored
+	#[pallet::storage]
+	pub(super) type LegacyValues<T: Config> = StorageMap<_, Twox64Concat, ID, u128>;
+
 	/// The new players join the pool before the TimeService, whose are without charge
 	#[pallet::storage]
 	pub(super) type NewPlayers<T: Config> =
@@ -131,6 +228,46 @@ pub mod pallet {
 	pub type IngamePlayers<T: Config> =
 		StorageValue<_, BoundedVec<T::AccountId, T::MaxPlayerStorage>, ValueQuery>;
 
+	/// Holding the players to charge in the current period by their index, charged by chunks
+	#[pallet::storage]
+	pub(super) type ChargingPlayers<T: Config> = StorageMap<_, Twox64Concat, u32, T::AccountId>;
+
+	/// Number of the players to charge in the current period
+	#[pallet::storage]
+	#[pallet::getter(fn charge_count)]
+	pub(super) type ChargeCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The index of the next player in the ChargingPlayers to charge
+	#[pallet::storage]
+	#[pallet::getter(fn charge_cursor)]
+	pub(super) type ChargeCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The time the current period started to charge, players joined after are not charged
+	#[pallet::storage]
+	pub(super) type ChargeStart<T: Config> = StorageValue<_, u128, ValueQuery>;
+
+	/// The time the billing boundary was reached, the new period starts once the players
+	/// of the previous period are all charged so the ChargingPlayers are never overwritten
+	#[pallet::storage]
+	pub(super) type PendingCharge<T: Config> = StorageValue<_, u128>;
+
+	/// Holding the players who can't pay the service fee with their arrears
+	#[pallet::storage]
+	#[pallet::getter(fn delinquents)]
+	pub type Delinquents<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Delinquency>;
+
+	/// Holding the periods paid in advance by the players
+	#[pallet::storage]
+	#[pallet::getter(fn prepayments)]
+	pub type Prepayments<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Prepayment>;
+
+	/// The discount of the prepaid periods, each tier is the min number of periods with the
+	/// discount applied
+	#[pallet::storage]
+	#[pallet::getter(fn prepaid_discounts)]
+	pub type PrepaidDiscounts<T: Config> =
+		StorageValue<_, BoundedVec<(u32, Permill), T::MaxPrepaidTiers>, ValueQuery>;
+
 	//** Genesis Conguration **//
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {}
@@ -160,6 +297,16 @@ pub mod pallet {
 		CanNotClearNewPlayers,
 		IntoBalanceFail,
 		LevelNotFound,
+		SameLevel,
+		ServiceAlreadyExists,
+		ServiceRetired,
+		NotDelinquent,
+		PlayerDelinquent,
+		InvalidPeriods,
+		ExceedMaxPrepaidPeriods,
+		ExceedMaxPrepaidTiers,
+		InvalidPrepaidDiscounts,
+		PlayerPrepaid,
 	}
 
 	#[pallet::event]
@@ -167,6 +314,18 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		ChargePoolService,
 		UpfrontSetMaxPlayer { new_max_player: u32 },
+		Charged { player: T::AccountId, fee: u128 },
+		Kicked { player: T::AccountId },
+		ServiceAdded { pool_id: ID, service: SystemService },
+		ServiceUpdated { pool_id: ID, service: SystemService },
+		ServiceChanged { pool_id: ID, service: SystemService },
+		ServiceRetired { pool_id: ID, fallback_pool_id: ID },
+		PlayerMigrated { player: T::AccountId, pool_id: ID },
+		PaymentFailed { player: T::AccountId, fee: u128, unpaid_periods: u32 },
+		ArrearsPaid { player: T::AccountId, arrears: u128 },
+		Prepaid { player: T::AccountId, periods: u32, fee: u128 },
+		PrepaidPeriodUsed { player: T::AccountId, remaining_periods: u32 },
+		PrepaidDiscountsSet { discounts: Vec<(u32, Permill)> },
 	}
 
 	impl<T: Config> SystemPool<T::AccountId> for Pallet<T> {
@@ -188,6 +347,10 @@ pub mod pallet {
 				new_player_count <= Self::max_player(),
 				<Error<T>>::ExceedMaxPlayer
 			);
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceRetired
+			);
 			{
 				let service = Self::get_pool_by_id(pool_id)?;
 				let service_fee = u128_try_to_balance::<
@@ -201,13 +364,14 @@ pub mod pallet {
 				);
 				<NewPlayers<T>>::try_mutate(|newplayers| newplayers.try_push(sender.clone()))
 					.map_err(|_| <Error<T>>::ExceedMaxPlayer)?;
-				T::Currency::reserve(&sender, service_fee)?;
+				T::Currency::reserve_named(&UPFRONT_POOL_RESERVE_ID, &sender, service_fee)?;
 				T::Currency::withdraw(
 					&sender,
 					service_fee,
 					WithdrawReasons::FEE,
 					ExistenceRequirement::KeepAlive,
 				)?;
+				Reserved::<T>::insert(&sender, service.value);
 			}
 			Self::join_pool(sender, pool_id, new_player_count);
 			Ok(())
@@ -215,6 +379,9 @@ pub mod pallet {
 
 		/// Leave Upfront Pool
 		///
+		/// The used service fee and the arrears are settled from the reserve first, the rest is
+		/// charged as far as the balance allows so a delinquent player can always leave
+		///
 		/// The origin must be Signed
 		///
 		/// Weight: `O(1)`
@@ -227,29 +394,28 @@ pub mod pallet {
 					let join_time = ticket.join_time;
 					let _now = Self::moment_to_u128(<timestamp::Pallet<T>>::get());
 
-					let service_fee;
 					let charge_fee;
 					{
 						let service = Self::get_pool_by_id(pool_id)?;
 						let refund_fee = Self::get_refund_balance(_now, join_time, service.value);
-						charge_fee = u128_try_to_balance::<
-							<T as pallet::Config>::Currency,
-							T::AccountId,
-						>(service.value - refund_fee)?;
-						service_fee = u128_try_to_balance::<
+						let arrears = Self::delinquents(&sender)
+							.map(|delinquency| delinquency.arrears)
+							.unwrap_or_default();
+						charge_fee = (service.value - refund_fee).saturating_add(arrears);
+					}
+
+					let rest = Self::settle_reserve(&sender, pool_id, charge_fee);
+					Self::charge_available(&sender, rest);
+
+					// refund the unused prepaid periods
+					if let Some(prepayment) = Self::prepayments(&sender) {
+						let refund_fee = u128_try_to_balance::<
 							<T as pallet::Config>::Currency,
 							T::AccountId,
-						>(service.value)?;
+						>(prepayment.period_fee.saturating_mul(prepayment.periods.into()))?;
+						T::Currency::deposit_into_existing(&sender, refund_fee)?;
 					}
 
-					T::Currency::unreserve(&sender, service_fee);
-					T::Currency::withdraw(
-						&sender,
-						charge_fee,
-						WithdrawReasons::FEE,
-						ExistenceRequirement::KeepAlive,
-					)?;
-
 					let new_player_count = Self::player_count()
 						.checked_sub(1)
 						.ok_or(<Error<T>>::PlayerCountOverflow)?;
@@ -260,9 +426,73 @@ pub mod pallet {
 			return Err(Error::<T>::PlayerNotFound.into());
 		}
 
+		/// Change the ticket level of Upfront Pool
+		///
+		/// The price difference is settled pro rata for the rest of the TimeService period
+		/// and the reserve is moved to the service fee of the new level
+		///
+		/// Parameters:
+		/// - `new_pool_id`: The pool id of the new level
+		///
+		/// Weight: `O(1)`
+		#[transactional]
+		fn change_level(sender: T::AccountId, new_pool_id: ID) -> DispatchResult {
+			let mut ticket = Tickets::<T>::get(sender.clone()).ok_or(<Error<T>>::PlayerNotFound)?;
+			let level = Self::get_player_level(sender.clone()).ok_or(<Error<T>>::PlayerNotFound)?;
+			let old_pool_id = Convertor::into_id(SystemTicket::Upfront(level));
+			let old_service = Self::get_pool_by_id(old_pool_id)?;
+			let new_service = Self::get_pool_by_id(new_pool_id)?;
+			ensure!(level != new_service.ticket_level, <Error<T>>::SameLevel);
+			ensure!(
+				!Prepayments::<T>::contains_key(&sender),
+				<Error<T>>::PlayerPrepaid
+			);
+			ensure!(
+				!RetiredServices::<T>::contains_key(new_pool_id),
+				<Error<T>>::ServiceRetired
+			);
+
+			Self::move_reserve(
+				&sender,
+				Self::reserved_amount(&sender, old_pool_id),
+				new_service.value,
+			)?;
+
+			let _now = Self::get_timestamp();
+			let old_remaining = Self::get_remaining_balance(_now, old_service.value);
+			let new_remaining = Self::get_remaining_balance(_now, new_service.value);
+
+			if new_remaining >= old_remaining {
+				let charge_fee = u128_try_to_balance::<
+					<T as pallet::Config>::Currency,
+					T::AccountId,
+				>(new_remaining - old_remaining)?;
+				T::Currency::withdraw(
+					&sender,
+					charge_fee,
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			} else {
+				let refund_fee = u128_try_to_balance::<
+					<T as pallet::Config>::Currency,
+					T::AccountId,
+				>(old_remaining - new_remaining)?;
+				T::Currency::deposit_into_existing(&sender, refund_fee)?;
+			}
+
+			ticket.ticket_type = TicketType::System(SystemTicket::Upfront(new_service.ticket_level));
+			Tickets::<T>::insert(sender, ticket);
+			Ok(())
+		}
+
 		fn get_service(pool_id: ID) -> Option<SystemService> {
 			Services::<T>::get(pool_id)
 		}
+
+		fn is_suspended(player: &T::AccountId) -> bool {
+			Delinquents::<T>::contains_key(player)
+		}
 	}
 
 	#[pallet::call]
@@ -284,6 +514,211 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Add Service
+		///
+		/// Add a new level of ticket, players can join it immediately
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `service`: the service of the new level
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_service())]
+		pub fn add_service(origin: OriginFor<T>, service: SystemService) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Upfront(service.ticket_level));
+			ensure!(
+				!Services::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceAlreadyExists
+			);
+
+			Services::<T>::insert(pool_id, service);
+			Self::deposit_event(Event::<T>::ServiceAdded { pool_id, service });
+			Ok(())
+		}
+
+		/// Update Service
+		///
+		/// Change the price, tx limit or discount of a level, the change takes effect at the
+		/// next billing period
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `service`: the new service of the level
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::update_service())]
+		pub fn update_service(origin: OriginFor<T>, service: SystemService) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Upfront(service.ticket_level));
+			Self::get_pool_by_id(pool_id)?;
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id),
+				<Error<T>>::ServiceRetired
+			);
+
+			NextServices::<T>::insert(pool_id, service);
+			Self::deposit_event(Event::<T>::ServiceUpdated { pool_id, service });
+			Ok(())
+		}
+
+		/// Retire Service
+		///
+		/// Stop players joining a level, the players of the level are moved to the fallback
+		/// level when they are charged in the next billing period
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `level`: the level to retire
+		/// - `fallback_level`: the level that the players are moved to
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::retire_service())]
+		pub fn retire_service(
+			origin: OriginFor<T>,
+			level: TicketLevel,
+			fallback_level: TicketLevel,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let pool_id = Convertor::into_id(SystemTicket::Upfront(level));
+			let fallback_pool_id = Convertor::into_id(SystemTicket::Upfront(fallback_level));
+			ensure!(level != fallback_level, <Error<T>>::SameLevel);
+			Self::get_pool_by_id(pool_id)?;
+			Self::get_pool_by_id(fallback_pool_id)?;
+			ensure!(
+				!RetiredServices::<T>::contains_key(pool_id) &&
+					!RetiredServices::<T>::contains_key(fallback_pool_id),
+				<Error<T>>::ServiceRetired
+			);
+
+			RetiredServices::<T>::insert(pool_id, fallback_pool_id);
+			NextServices::<T>::remove(pool_id);
+			Self::deposit_event(Event::<T>::ServiceRetired { pool_id, fallback_pool_id });
+			Ok(())
+		}
+
+		/// Pay Arrears
+		///
+		/// Pay the service fee of the unpaid periods, the discount of the ticket is applied
+		/// again
+		///
+		/// The origin must be Signed
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::pay_arrears())]
+		#[transactional]
+		pub fn pay_arrears(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let delinquency = Self::delinquents(&sender).ok_or(<Error<T>>::NotDelinquent)?;
+			let arrears = u128_try_to_balance::<
+				<T as pallet::Config>::Currency,
+				T::AccountId,
+			>(delinquency.arrears)?;
+
+			T::Currency::withdraw(
+				&sender,
+				arrears,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Delinquents::<T>::remove(&sender);
+			Self::deposit_event(Event::<T>::ArrearsPaid {
+				player: sender,
+				arrears: delinquency.arrears,
+			});
+			Ok(())
+		}
+
+		/// Prepay
+		///
+		/// Pay the service fee of the next periods in advance with the prepaid discount, the
+		/// player is not charged while the prepaid periods remain
+		///
+		/// The origin must be Signed
+		///
+		/// Parameters:
+		/// - `periods`: the number of periods to pay
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::prepay())]
+		#[transactional]
+		pub fn prepay(origin: OriginFor<T>, periods: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(periods > 0, <Error<T>>::InvalidPeriods);
+			let level = Self::get_player_level(sender.clone()).ok_or(<Error<T>>::PlayerNotFound)?;
+			ensure!(
+				!Delinquents::<T>::contains_key(&sender),
+				<Error<T>>::PlayerDelinquent
+			);
+			let service = Self::get_pool_by_id(Convertor::into_id(SystemTicket::Upfront(level)))?;
+
+			let prepayment = Self::prepayments(&sender).unwrap_or_default();
+			let total_periods = prepayment
+				.periods
+				.checked_add(periods)
+				.filter(|total_periods| *total_periods <= T::MaxPrepaidPeriods::get())
+				.ok_or(<Error<T>>::ExceedMaxPrepaidPeriods)?;
+
+			let full_fee = service.value.saturating_mul(periods.into());
+			let fee = full_fee.saturating_sub(Self::prepaid_discount(periods) * full_fee);
+			T::Currency::withdraw(
+				&sender,
+				u128_try_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(fee)?,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let total_fee = prepayment
+				.period_fee
+				.saturating_mul(prepayment.periods.into())
+				.saturating_add(fee);
+			Prepayments::<T>::insert(
+				&sender,
+				Prepayment {
+					periods: total_periods,
+					period_fee: total_fee / u128::from(total_periods),
+				},
+			);
+			Self::deposit_event(Event::<T>::Prepaid { player: sender, periods, fee });
+			Ok(())
+		}
+
+		/// Set Prepaid Discounts
+		///
+		/// Set the discount curve of the prepaid periods, the min number of periods of the tiers
+		/// must be nonzero and strictly increasing, the discounts must not exceed 100%
+		///
+		/// The origin must be AdminOrigin
+		///
+		/// Parameters:
+		/// - `discounts`: the tiers of (min number of periods, discount)
+		///
+		/// Weight: `O(1)`
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_prepaid_discounts())]
+		pub fn set_prepaid_discounts(
+			origin: OriginFor<T>,
+			discounts: Vec<(u32, Permill)>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let bounded_discounts: BoundedVec<(u32, Permill), T::MaxPrepaidTiers> =
+				discounts.clone().try_into().map_err(|_| <Error<T>>::ExceedMaxPrepaidTiers)?;
+			let mut last_periods = 0_u32;
+			for (periods, discount) in bounded_discounts.iter() {
+				ensure!(
+					*periods > last_periods && *discount <= Permill::one(),
+					<Error<T>>::InvalidPrepaidDiscounts
+				);
+				last_periods = *periods;
+			}
+
+			PrepaidDiscounts::<T>::put(bounded_discounts);
+			Self::deposit_event(Event::<T>::PrepaidDiscountsSet { discounts });
+			Ok(())
+		}
 	}
 }
 
@@ -312,20 +747,47 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn get_refund_balance(leave_time: u128, join_time: u128, service_fee: u128) -> u128 {
+		let time_service = T::MasterPool::get_timeservice();
+		let time_service_start = T::MasterPool::get_timeservice_start();
+
+		// the player has been charged with the old TimeService, count from the switchover
+		if join_time < time_service_start {
+			let extra = leave_time.saturating_sub(time_service_start) % time_service;
+			return service_fee
+				.saturating_mul(time_service.saturating_sub(extra))
+				.saturating_div(time_service);
+		}
+
 		let period_time = leave_time.saturating_sub(join_time);
-		if period_time < T::MasterPool::get_timeservice() {
+		if period_time < time_service {
 			service_fee
 		} else {
-			let extra = period_time % T::MasterPool::get_timeservice();
+			let extra = period_time % time_service;
 			service_fee
-				.saturating_mul(T::MasterPool::get_timeservice().saturating_sub(extra))
-				.saturating_div(T::MasterPool::get_timeservice())
+				.saturating_mul(time_service.saturating_sub(extra))
+				.saturating_div(time_service)
+		}
+	}
+
+	/// The part of the service fee for the rest of the current TimeService period
+	fn get_remaining_balance(now: u128, service_fee: u128) -> u128 {
+		let time_service = T::MasterPool::get_timeservice();
+		if time_service == 0 {
+			return 0;
 		}
+		let remaining_time = T::MasterPool::get_marktime()
+			.saturating_add(time_service)
+			.saturating_sub(now)
+			.min(time_service);
+		service_fee.saturating_mul(remaining_time).saturating_div(time_service)
 	}
 
 	fn remove_player(player: &T::AccountId, pool_id: ID, new_player_count: u32) {
 		T::MasterPool::remove_player(player, pool_id);
 		Tickets::<T>::remove(player);
+		Reserved::<T>::remove(player);
+		Delinquents::<T>::remove(player);
+		Prepayments::<T>::remove(player);
 
 		<IngamePlayers<T>>::mutate(|players| {
 			if let Some(ind) = players.iter().position(|id| id == player) {
@@ -342,33 +804,264 @@ impl<T: Config> Pallet<T> {
 		<PlayerCount<T>>::put(new_player_count);
 	}
 
-	fn charge_ingame() -> Result<(), Error<T>> {
-		let ingame_players: Vec<T::AccountId> = IngamePlayers::<T>::get().into_inner();
-		for player in ingame_players {
-			if let Some(service) = Self::get_player_service(player.clone()) {
-				let fee_value =
-					u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(service.value);
+	/// Start the new billing period, return the number of the players to charge with the new
+	/// players and the number of the services changed
+	fn start_period(charge_start: u128) -> (u32, u32) {
+		let services = Self::apply_next_services();
+		Self::start_charge(charge_start);
+		let _ = Self::move_newplayer_to_ingame();
+		Self::deposit_event(<Event<T>>::ChargePoolService);
+		(IngamePlayers::<T>::decode_len().unwrap_or_default() as u32, services)
+	}
 
-				match T::Currency::withdraw(
-					&player,
-					fee_value,
-					WithdrawReasons::FEE,
-					ExistenceRequirement::KeepAlive,
-				) {
-					Ok(_) => {}
-					Err(_) => {
-						let new_player_count = Self::player_count()
-							.checked_sub(1)
-							.ok_or(<Error<T>>::PlayerCountOverflow)?;
-						let _ = Self::remove_player(
-							&player,
-							(SystemTicket::Upfront(service.ticket_level)).using_encoded(blake2_256),
-							new_player_count,
+	/// Start to charge the players in the IngamePlayers, players are charged by chunks in
+	/// the next blocks
+	fn start_charge(now: u128) {
+		let ingame_players = IngamePlayers::<T>::get();
+		ChargeCount::<T>::put(ingame_players.len() as u32);
+		for (index, player) in ingame_players.into_iter().enumerate() {
+			ChargingPlayers::<T>::insert(index as u32, player);
+		}
+		ChargeCursor::<T>::put(0);
+		ChargeStart::<T>::put(now);
+	}
+
+	/// Charge at most `limit` players from the cursor, return the number of the players charged
+	fn charge_ingame(limit: u32) -> u32 {
+		let cursor = Self::charge_cursor();
+		let count = Self::charge_count();
+		let end = count.min(cursor.saturating_add(limit));
+		if cursor >= end {
+			return 0;
+		}
+
+		let charge_start = ChargeStart::<T>::get();
+		for index in cursor..end {
+			if let Some(player) = ChargingPlayers::<T>::take(index) {
+				Self::charge_player(&player, charge_start);
+			}
+		}
+
+		if end == count {
+			ChargeCount::<T>::kill();
+			ChargeCursor::<T>::kill();
+		} else {
+			ChargeCursor::<T>::put(end);
+		}
+		end - cursor
+	}
+
+	fn charge_player(player: &T::AccountId, charge_start: u128) {
+		// skip the players left or rejoined after the period started
+		let level = match Tickets::<T>::get(player) {
+			Some(Ticket {
+				join_time,
+				ticket_type: TicketType::System(SystemTicket::Upfront(level)),
+				..
+			}) if join_time < charge_start => level,
+			_ => return,
+		};
+
+		let mut pool_id = Convertor::into_id(SystemTicket::Upfront(level));
+		if let Some(fallback_pool_id) = RetiredServices::<T>::get(pool_id) {
+			// keep the retired level until the player can afford the fallback level
+			if Self::migrate_player(player, pool_id, fallback_pool_id).is_ok() {
+				pool_id = fallback_pool_id;
+			}
+		}
+
+		if let Some(prepayment) = Self::prepayments(player) {
+			let remaining_periods = prepayment.periods.saturating_sub(1);
+			if remaining_periods == 0 {
+				Prepayments::<T>::remove(player);
+			} else {
+				Prepayments::<T>::insert(
+					player,
+					Prepayment { periods: remaining_periods, ..prepayment },
+				);
+			}
+			Self::deposit_event(Event::<T>::PrepaidPeriodUsed {
+				player: player.clone(),
+				remaining_periods,
+			});
+			return;
+		}
+
+		if let Some(service) = Self::get_service(pool_id) {
+			let delinquency = Self::delinquents(player).unwrap_or_default();
+			let fee_value =
+				u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(service.value);
+
+			match T::Currency::withdraw(
+				player,
+				fee_value,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			) {
+				Ok(_) => {
+					Self::deposit_event(Event::<T>::Charged {
+						player: player.clone(),
+						fee: service.value,
+					});
+					// the arrears are settled after the fee of the period as far as the
+					// balance allows
+					if delinquency.arrears > 0 {
+						let paid = Self::charge_available(player, delinquency.arrears);
+						if paid == delinquency.arrears {
+							Delinquents::<T>::remove(player);
+						} else {
+							Delinquents::<T>::insert(
+								player,
+								Delinquency { arrears: delinquency.arrears - paid, ..delinquency },
+							);
+						}
+						if paid > 0 {
+							Self::deposit_event(Event::<T>::ArrearsPaid {
+								player: player.clone(),
+								arrears: paid,
+							});
+						}
+					}
+				}
+				Err(_) => {
+					let unpaid_periods = delinquency.unpaid_periods.saturating_add(1);
+					if unpaid_periods > T::GracePeriods::get() {
+						Self::settle_reserve(player, pool_id, delinquency.arrears);
+						let new_player_count = Self::player_count().saturating_sub(1);
+						Self::remove_player(player, pool_id, new_player_count);
+						Self::deposit_event(Event::<T>::Kicked { player: player.clone() });
+					} else {
+						Delinquents::<T>::insert(
+							player,
+							Delinquency {
+								unpaid_periods,
+								arrears: delinquency.arrears.saturating_add(service.value),
+							},
 						);
+						Self::deposit_event(Event::<T>::PaymentFailed {
+							player: player.clone(),
+							fee: service.value,
+							unpaid_periods,
+						});
 					}
-				};
+				}
+			};
+		}
+	}
+
+	/// Settle the arrears of the leaving or kicked player from the reserved service fee, the rest
+	/// of the reserve is unreserved, return the arrears the reserve can not cover
+	fn settle_reserve(player: &T::AccountId, pool_id: ID, arrears: u128) -> u128 {
+		let reserved = Self::reserved_amount(player, pool_id);
+		let settled = arrears.min(reserved);
+		let _ = T::Currency::slash_reserved_named(
+			&UPFRONT_POOL_RESERVE_ID,
+			player,
+			u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(settled),
+		);
+		T::Currency::unreserve_named(
+			&UPFRONT_POOL_RESERVE_ID,
+			player,
+			u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(reserved - settled),
+		);
+		Reserved::<T>::remove(player);
+		arrears - settled
+	}
+
+	/// Charge the `amount` as far as the free balance of the player allows, the player is kept
+	/// alive, return the charged amount
+	fn charge_available(player: &T::AccountId, amount: u128) -> u128 {
+		let available = balance_try_to_u128::<<T as pallet::Config>::Currency, T::AccountId>(
+			T::Currency::free_balance(player).saturating_sub(T::Currency::minimum_balance()),
+		)
+		.unwrap_or_default();
+		let charged = amount.min(available);
+		if charged == 0 {
+			return 0;
+		}
+		match T::Currency::withdraw(
+			player,
+			u128_to_balance::<<T as pallet::Config>::Currency, T::AccountId>(charged),
+			WithdrawReasons::FEE,
+			ExistenceRequirement::KeepAlive,
+		) {
+			Ok(_) => charged,
+			Err(_) => 0,
+		}
+	}
+
+	#[transactional]
+	fn migrate_player(player: &T::AccountId, pool_id: ID, new_pool_id: ID) -> DispatchResult {
+		let new_service = Self::get_pool_by_id(new_pool_id)?;
+		Self::move_reserve(player, Self::reserved_amount(player, pool_id), new_service.value)?;
+
+		let new_ticket = TicketType::System(SystemTicket::Upfront(new_service.ticket_level));
+		Tickets::<T>::mutate(player, |ticket| {
+			if let Some(ticket) = ticket {
+				ticket.ticket_type = new_ticket;
 			}
+		});
+		T::MasterPool::change_pool(player, pool_id, new_ticket);
+		Self::deposit_event(Event::<T>::PlayerMigrated {
+			player: player.clone(),
+			pool_id: new_pool_id,
+		});
+		Ok(())
+	}
+
+	/// Apply the service changes scheduled by update_service
+	/// Apply the service changes, return the number of the services changed
+	fn apply_next_services() -> u32 {
+		let mut count = 0_u32;
+		for (pool_id, service) in NextServices::<T>::drain() {
+			count = count.saturating_add(1);
+			if let Some(old_service) = Services::<T>::get(pool_id) {
+				if old_service.value != service.value && !LegacyValues::<T>::contains_key(pool_id) {
+					LegacyValues::<T>::insert(pool_id, old_service.value);
+				}
+			}
+			Services::<T>::insert(pool_id, service);
+			Self::deposit_event(Event::<T>::ServiceChanged { pool_id, service });
+		}
+		count
+	}
+
+	/// The highest discount of the tiers reached by the number of periods
+	fn prepaid_discount(periods: u32) -> Permill {
+		Self::prepaid_discounts()
+			.iter()
+			.filter(|(min_periods, _)| *min_periods <= periods)
+			.map(|(_, discount)| *discount)
+			.max()
+			.unwrap_or(Permill::from_percent(0))
+	}
+
+	/// The amount reserved by the player in the pool
+	fn reserved_amount(player: &T::AccountId, pool_id: ID) -> u128 {
+		match Reserved::<T>::get(player) {
+			Some(amount) => amount,
+			None => LegacyValues::<T>::get(pool_id)
+				.or_else(|| Services::<T>::get(pool_id).map(|service| service.value))
+				.unwrap_or_default(),
+		}
+	}
+
+	/// Move the reserve of the player from the old amount to the new amount
+	fn move_reserve(player: &T::AccountId, old_amount: u128, new_amount: u128) -> DispatchResult {
+		if new_amount >= old_amount {
+			let reserve_fee = u128_try_to_balance::<
+				<T as pallet::Config>::Currency,
+				T::AccountId,
+			>(new_amount - old_amount)?;
+			T::Currency::reserve_named(&UPFRONT_POOL_RESERVE_ID, player, reserve_fee)?;
+		} else {
+			let unreserve_fee = u128_try_to_balance::<
+				<T as pallet::Config>::Currency,
+				T::AccountId,
+			>(old_amount - new_amount)?;
+			T::Currency::unreserve_named(&UPFRONT_POOL_RESERVE_ID, player, unreserve_fee);
 		}
+		Reserved::<T>::insert(player, new_amount);
 		Ok(())
 	}
 
@@ -0,0 +1,62 @@
+// This file is part of Gafi Network.
+
+// Copyright (C) 2021-2022 CryptoViet.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Move the service fee reserved by the players to UPFRONT_POOL_RESERVE_ID
+	pub struct MigrateToNamedReserve<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToNamedReserve<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut players = 0_u64;
+			for (player, ticket) in Tickets::<T>::iter() {
+				if let TicketType::System(system_ticket) = ticket.ticket_type {
+					let service_fee = Pallet::<T>::reserved_amount(
+						&player,
+						Convertor::into_id(system_ticket),
+					);
+					if let Ok(service_fee) = u128_try_to_balance::<
+						<T as pallet::Config>::Currency,
+						T::AccountId,
+					>(service_fee)
+					{
+						gu_currency::move_to_named_reserve::<
+							T::AccountId,
+							<T as pallet::Config>::Currency,
+						>(&UPFRONT_POOL_RESERVE_ID, &player, service_fee);
+					}
+				}
+				players = players.saturating_add(1);
+			}
+
+			STORAGE_VERSION.put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(
+				players.saturating_mul(4).saturating_add(1),
+				players.saturating_add(1),
+			)
+		}
+	}
+}
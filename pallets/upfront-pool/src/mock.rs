@@ -59,7 +59,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -139,6 +139,10 @@ impl SystemDefaultServices for UpfrontPoolDefaultServices {
 
 parameter_types! {
 	pub MaxPlayerStorage: u32 = 1000;
+	pub MaxChargePerBlock: u32 = 10;
+	pub GracePeriods: u32 = 1;
+	pub MaxPrepaidPeriods: u32 = 10;
+	pub MaxPrepaidTiers: u32 = 5;
 }
 
 impl upfront_pool::Config for Test {
@@ -148,6 +152,11 @@ impl upfront_pool::Config for Test {
 	type MaxPlayerStorage = MaxPlayerStorage;
 	type MasterPool = ();
 	type UpfrontServices = UpfrontPoolDefaultServices;
+	type MaxChargePerBlock = MaxChargePerBlock;
+	type GracePeriods = GracePeriods;
+	type MaxPrepaidPeriods = MaxPrepaidPeriods;
+	type MaxPrepaidTiers = MaxPrepaidTiers;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
 }
 
 
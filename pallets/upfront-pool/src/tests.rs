@@ -1,12 +1,21 @@
-use crate::{mock::*, Error, IngamePlayers, NewPlayers};
-use crate::{PlayerCount, Tickets};
-use frame_support::{assert_err, assert_ok, traits::Currency};
+use crate::{mock::*, ChargingPlayers, Error, Event as UpfrontEvent, IngamePlayers, NewPlayers};
+use crate::{Delinquency, Delinquents, PendingCharge, Prepayment, Prepayments};
+use crate::{LegacyValues, NextServices, PlayerCount, Reserved, RetiredServices, Services, Tickets};
+use crate::migrations::v1::MigrateToNamedReserve;
+use frame_support::{
+	assert_err, assert_ok,
+	traits::{
+		Currency, GetStorageVersion, NamedReservableCurrency, OnInitialize, OnRuntimeUpgrade,
+		ReservableCurrency, StorageVersion,
+	},
+};
 use gafi_primitives::currency::{unit, NativeToken::GAKI};
 use gafi_primitives::{
-	system_services::SystemPool,
-	constant::ID
+	system_services::{Convertor, SystemPool, SystemService},
+	constant::{ID, UPFRONT_POOL_RESERVE_ID},
+	ticket::{SystemTicket, TicketLevel},
 };
-use sp_runtime::AccountId32;
+use sp_runtime::{traits::BadOrigin, AccountId32, Permill};
 use sp_std::str::FromStr;
 
 const CIRCLE_BLOCK: u64 = (TIME_SERVICE as u64) / SLOT_DURATION;
@@ -194,9 +203,584 @@ fn charge_ingame_works() {
 
 		run_to_block(CIRCLE_BLOCK + 1); // move to ingame
 		let before_balance = Balances::free_balance(&alice);
-		let _ = UpfrontPool::charge_ingame();
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
 		let after_balance = Balances::free_balance(&alice);
 		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
 		assert_eq!(before_balance, after_balance + service.value);
 	})
 }
+
+#[test]
+fn change_level_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let free_balance = Balances::free_balance(&alice);
+		let reserved_balance = Balances::reserved_balance(&alice);
+
+		// 98% of the TimeService period remains at block 1
+		assert_ok!(UpfrontPool::change_level(alice.clone(), UPFRONT_ADVANCE_ID));
+		assert_eq!(Balances::reserved_balance(&alice), reserved_balance + 5 * unit(GAKI));
+		assert_eq!(
+			Balances::free_balance(&alice),
+			free_balance - 5 * unit(GAKI) - 49 * unit(GAKI) / 10
+		);
+		assert_eq!(UpfrontPool::get_player_level(alice.clone()), Some(TicketLevel::Advance));
+		assert_eq!(NewPlayers::<Test>::get().len(), 1);
+		assert_eq!(PlayerCount::<Test>::get(), 1);
+
+		assert_ok!(UpfrontPool::change_level(alice.clone(), UPFRONT_BASIC_ID));
+		assert_eq!(Balances::reserved_balance(&alice), reserved_balance);
+		assert_eq!(Balances::free_balance(&alice), free_balance);
+		assert_eq!(UpfrontPool::get_player_level(alice.clone()), Some(TicketLevel::Basic));
+	})
+}
+
+#[test]
+fn change_level_should_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_err!(
+			UpfrontPool::change_level(alice.clone(), UPFRONT_MEDIUM_ID),
+			<Error<Test>>::PlayerNotFound
+		);
+
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		assert_err!(
+			UpfrontPool::change_level(alice.clone(), UPFRONT_BASIC_ID),
+			<Error<Test>>::SameLevel
+		);
+	})
+}
+
+#[test]
+fn charge_ingame_by_chunks_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = new_accounts(25, 1_000_000 * unit(GAKI));
+		for account in accounts.iter() {
+			assert_ok!(UpfrontPool::join(account.clone(), UPFRONT_BASIC_ID));
+		}
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(10), 10);
+		assert_eq!(UpfrontPool::charge_cursor(), 10);
+		assert_eq!(UpfrontPool::charge_ingame(10), 10);
+		assert_eq!(UpfrontPool::charge_ingame(10), 5);
+		assert_eq!(UpfrontPool::charge_cursor(), 0);
+		assert_eq!(UpfrontPool::charge_count(), 0);
+		assert_eq!(ChargingPlayers::<Test>::iter().count(), 0);
+		assert_eq!(UpfrontPool::charge_ingame(10), 0);
+
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		for account in accounts {
+			System::assert_has_event(Event::UpfrontPool(UpfrontEvent::Charged {
+				player: account,
+				fee: service.value,
+			}));
+		}
+	})
+}
+
+#[test]
+fn pending_charge_waits_for_charging_players() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let accounts = new_accounts(25, 1_000_000 * unit(GAKI));
+		for account in accounts.iter() {
+			assert_ok!(UpfrontPool::join(account.clone(), UPFRONT_BASIC_ID));
+		}
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(10), 10);
+
+		// the boundary is reached before the players of the period are all charged
+		let charge_start = UpfrontPool::get_timestamp() + 1;
+		PendingCharge::<Test>::put(charge_start);
+		UpfrontPool::on_initialize(3);
+		assert_eq!(UpfrontPool::charge_cursor(), 20);
+		UpfrontPool::on_initialize(4);
+		assert_eq!(UpfrontPool::charge_count(), 0);
+		assert_eq!(PendingCharge::<Test>::get(), Some(charge_start));
+
+		UpfrontPool::on_initialize(5);
+		assert_eq!(PendingCharge::<Test>::get(), None);
+		assert_eq!(UpfrontPool::charge_count(), 25);
+		assert_eq!(ChargingPlayers::<Test>::iter().count(), 15);
+		assert_eq!(UpfrontPool::charge_cursor(), 10);
+		System::assert_has_event(Event::UpfrontPool(UpfrontEvent::ChargePoolService));
+	})
+}
+
+#[test]
+fn charge_ingame_kick_player_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(service.value * 2 + service.value / 2);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		// alice is kicked after GracePeriods unpaid periods
+		for _ in 0..GracePeriods::get() {
+			UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+			assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+			assert_ne!(Tickets::<Test>::get(alice.clone()), None);
+		}
+
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		assert_eq!(Tickets::<Test>::get(alice.clone()), None);
+		assert_eq!(Delinquents::<Test>::get(alice.clone()), None);
+		assert_eq!(IngamePlayers::<Test>::get().len(), 0);
+		assert_eq!(PlayerCount::<Test>::get(), 0);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::Kicked { player: alice }));
+	})
+}
+
+#[test]
+fn charge_ingame_kick_player_settles_reserve_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let old_service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(old_service.value * 2 + old_service.value / 2);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		// the arrears are less than the reserve
+		let service = SystemService::new(TicketLevel::Basic, 10_u32, Permill::from_percent(30), 3 * unit(GAKI));
+		assert_ok!(UpfrontPool::update_service(Origin::root(), service));
+		UpfrontPool::apply_next_services();
+
+		run_to_block(2);
+		for _ in 0..=GracePeriods::get() {
+			UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+			assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		}
+
+		// the arrears of GracePeriods periods are settled from the reserve
+		let arrears = service.value * GracePeriods::get() as u128;
+		assert_eq!(Tickets::<Test>::get(alice.clone()), None);
+		assert_eq!(Reserved::<Test>::get(alice.clone()), None);
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(
+			Balances::free_balance(&alice),
+			old_service.value / 2 + old_service.value - arrears
+		);
+	})
+}
+
+#[test]
+fn charge_ingame_payment_failed_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(service.value * 2 + service.value / 2);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		assert_eq!(
+			Delinquents::<Test>::get(alice.clone()),
+			Some(Delinquency { unpaid_periods: 1, arrears: service.value })
+		);
+		assert_eq!(UpfrontPool::is_suspended(&alice), true);
+		assert_eq!(IngamePlayers::<Test>::get().len(), 1);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::PaymentFailed {
+			player: alice.clone(),
+			fee: service.value,
+			unpaid_periods: 1,
+		}));
+
+		// the fee of the period is charged first, the arrears as far as the balance allows
+		make_deposit(&alice, service.value);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		let paid = service.value / 2 - EXISTENTIAL_DEPOSIT;
+		assert_eq!(
+			Delinquents::<Test>::get(alice.clone()),
+			Some(Delinquency { unpaid_periods: 1, arrears: service.value - paid })
+		);
+		assert_eq!(Balances::free_balance(&alice), EXISTENTIAL_DEPOSIT);
+		System::assert_has_event(Event::UpfrontPool(UpfrontEvent::Charged {
+			player: alice.clone(),
+			fee: service.value,
+		}));
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::ArrearsPaid {
+			player: alice.clone(),
+			arrears: paid,
+		}));
+
+		// the rest of the arrears are settled with the fee of the next period
+		make_deposit(&alice, service.value * 2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		assert_eq!(Delinquents::<Test>::get(alice.clone()), None);
+		assert_eq!(UpfrontPool::is_suspended(&alice), false);
+		assert_eq!(Balances::free_balance(&alice), service.value / 2);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::ArrearsPaid {
+			player: alice,
+			arrears: service.value - paid,
+		}));
+	})
+}
+
+#[test]
+fn delinquent_player_leave_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(service.value * 2 + service.value / 2);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+		assert_eq!(UpfrontPool::is_suspended(&alice), true);
+
+		// the arrears are settled from the reserve, the used fee from the free balance
+		let join_time = Tickets::<Test>::get(alice.clone()).unwrap().join_time;
+		let refund_fee =
+			UpfrontPool::get_refund_balance(UpfrontPool::get_timestamp(), join_time, service.value);
+		let used_fee = (service.value - refund_fee).min(service.value / 2 - EXISTENTIAL_DEPOSIT);
+		assert_ok!(UpfrontPool::leave(alice.clone()));
+		assert_eq!(Tickets::<Test>::get(alice.clone()), None);
+		assert_eq!(Delinquents::<Test>::get(alice.clone()), None);
+		assert_eq!(Reserved::<Test>::get(alice.clone()), None);
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(Balances::free_balance(&alice), service.value / 2 - used_fee);
+	})
+}
+
+#[test]
+fn pay_arrears_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(service.value * 2 + service.value / 2);
+		assert_err!(
+			UpfrontPool::pay_arrears(Origin::signed(alice.clone())),
+			<Error<Test>>::NotDelinquent
+		);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+		assert!(UpfrontPool::pay_arrears(Origin::signed(alice.clone())).is_err());
+
+		make_deposit(&alice, service.value);
+		assert_ok!(UpfrontPool::pay_arrears(Origin::signed(alice.clone())));
+		assert_eq!(Delinquents::<Test>::get(alice.clone()), None);
+		assert_eq!(Balances::free_balance(&alice), service.value / 2);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::ArrearsPaid {
+			player: alice,
+			arrears: service.value,
+		}));
+	})
+}
+
+#[test]
+fn charge_ingame_skip_new_players_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+
+		// alice joined in the same block the period started
+		let before_balance = Balances::free_balance(&alice);
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		assert_eq!(Balances::free_balance(&alice), before_balance);
+	})
+}
+
+#[test]
+fn add_service_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let level = TicketLevel::Custom(1);
+		let pool_id = Convertor::into_id(SystemTicket::Upfront(level));
+		let service = SystemService::new(level, 20_u32, Permill::from_percent(90), 20 * unit(GAKI));
+		let alice = new_account(1_000_000 * unit(GAKI));
+
+		assert_err!(UpfrontPool::add_service(Origin::signed(alice.clone()), service), BadOrigin);
+		assert_ok!(UpfrontPool::add_service(Origin::root(), service));
+		assert_eq!(Services::<Test>::get(pool_id), Some(service));
+		assert_err!(
+			UpfrontPool::add_service(Origin::root(), service),
+			<Error<Test>>::ServiceAlreadyExists
+		);
+
+		assert_ok!(UpfrontPool::join(alice.clone(), pool_id));
+		assert_eq!(UpfrontPool::get_player_level(alice), Some(level));
+	})
+}
+
+#[test]
+fn update_service_takes_effect_next_period_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let old_service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let service = SystemService::new(TicketLevel::Basic, 20_u32, Permill::from_percent(40), 6 * unit(GAKI));
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+
+		assert_ok!(UpfrontPool::update_service(Origin::root(), service));
+		assert_eq!(UpfrontPool::get_service(UPFRONT_BASIC_ID), Some(old_service));
+		assert_eq!(NextServices::<Test>::get(UPFRONT_BASIC_ID), Some(service));
+
+		UpfrontPool::apply_next_services();
+		assert_eq!(UpfrontPool::get_service(UPFRONT_BASIC_ID), Some(service));
+		assert_eq!(NextServices::<Test>::get(UPFRONT_BASIC_ID), None);
+		assert_eq!(LegacyValues::<Test>::get(UPFRONT_BASIC_ID), Some(old_service.value));
+
+		// the player is charged with the new price, the reserve is not touched
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+		run_to_block(2);
+		let before_balance = Balances::free_balance(&alice);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+		assert_eq!(Balances::free_balance(&alice), before_balance - service.value);
+		assert_eq!(Reserved::<Test>::get(alice.clone()), Some(old_service.value));
+
+		assert_ok!(UpfrontPool::leave(alice.clone()));
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+	})
+}
+
+#[test]
+fn retire_service_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		let bob = AccountId32::new([1_u8; 32]);
+		make_deposit(&bob, 1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+
+		assert_err!(
+			UpfrontPool::retire_service(Origin::root(), TicketLevel::Basic, TicketLevel::Basic),
+			<Error<Test>>::SameLevel
+		);
+		assert_ok!(UpfrontPool::retire_service(
+			Origin::root(),
+			TicketLevel::Basic,
+			TicketLevel::Medium
+		));
+		assert_eq!(RetiredServices::<Test>::get(UPFRONT_BASIC_ID), Some(UPFRONT_MEDIUM_ID));
+		assert_err!(UpfrontPool::join(bob, UPFRONT_BASIC_ID), <Error<Test>>::ServiceRetired);
+
+		// the player is moved to the fallback level when charged
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+		run_to_block(2);
+		let medium_service = UpfrontPool::get_service(UPFRONT_MEDIUM_ID).unwrap();
+		let before_balance = Balances::free_balance(&alice);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+
+		assert_eq!(UpfrontPool::get_player_level(alice.clone()), Some(TicketLevel::Medium));
+		assert_eq!(Balances::reserved_balance(&alice), medium_service.value);
+		assert_eq!(
+			Balances::free_balance(&alice),
+			before_balance - 2 * unit(GAKI) - medium_service.value
+		);
+		System::assert_has_event(Event::UpfrontPool(UpfrontEvent::PlayerMigrated {
+			player: alice,
+			pool_id: UPFRONT_MEDIUM_ID,
+		}));
+	})
+}
+
+#[test]
+fn set_prepaid_discounts_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		let discounts = vec![(3_u32, Permill::from_percent(10)), (6_u32, Permill::from_percent(20))];
+
+		assert_err!(
+			UpfrontPool::set_prepaid_discounts(Origin::signed(alice), discounts.clone()),
+			BadOrigin
+		);
+		assert_ok!(UpfrontPool::set_prepaid_discounts(Origin::root(), discounts.clone()));
+		assert_eq!(UpfrontPool::prepaid_discounts().into_inner(), discounts);
+		assert_eq!(UpfrontPool::prepaid_discount(2), Permill::from_percent(0));
+		assert_eq!(UpfrontPool::prepaid_discount(5), Permill::from_percent(10));
+		assert_eq!(UpfrontPool::prepaid_discount(10), Permill::from_percent(20));
+
+		let too_many_tiers = (0..MaxPrepaidTiers::get() + 1)
+			.map(|i| (i, Permill::from_percent(i)))
+			.collect::<Vec<(u32, Permill)>>();
+		assert_err!(
+			UpfrontPool::set_prepaid_discounts(Origin::root(), too_many_tiers),
+			<Error<Test>>::ExceedMaxPrepaidTiers
+		);
+	})
+}
+
+#[test]
+fn set_prepaid_discounts_should_fail() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let zero_periods = vec![(0_u32, Permill::from_percent(10))];
+		let same_periods =
+			vec![(3_u32, Permill::from_percent(10)), (3_u32, Permill::from_percent(20))];
+		let decreasing_periods =
+			vec![(6_u32, Permill::from_percent(20)), (3_u32, Permill::from_percent(10))];
+
+		for discounts in vec![zero_periods, same_periods, decreasing_periods] {
+			assert_err!(
+				UpfrontPool::set_prepaid_discounts(Origin::root(), discounts),
+				<Error<Test>>::InvalidPrepaidDiscounts
+			);
+		}
+		assert_eq!(UpfrontPool::prepaid_discounts().len(), 0);
+	})
+}
+
+#[test]
+fn prepay_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::set_prepaid_discounts(
+			Origin::root(),
+			vec![(3_u32, Permill::from_percent(10))]
+		));
+		assert_err!(
+			UpfrontPool::prepay(Origin::signed(alice.clone()), 3),
+			<Error<Test>>::PlayerNotFound
+		);
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+
+		assert_err!(
+			UpfrontPool::prepay(Origin::signed(alice.clone()), 0),
+			<Error<Test>>::InvalidPeriods
+		);
+		assert_err!(
+			UpfrontPool::prepay(Origin::signed(alice.clone()), MaxPrepaidPeriods::get() + 1),
+			<Error<Test>>::ExceedMaxPrepaidPeriods
+		);
+
+		let before_balance = Balances::free_balance(&alice);
+		let fee = service.value * 3 - service.value * 3 / 10;
+		assert_ok!(UpfrontPool::prepay(Origin::signed(alice.clone()), 3));
+		assert_eq!(Balances::free_balance(&alice), before_balance - fee);
+		assert_eq!(
+			Prepayments::<Test>::get(alice.clone()),
+			Some(Prepayment { periods: 3, period_fee: fee / 3 })
+		);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::Prepaid {
+			player: alice.clone(),
+			periods: 3,
+			fee,
+		}));
+		assert_err!(
+			UpfrontPool::change_level(alice, UPFRONT_MEDIUM_ID),
+			<Error<Test>>::PlayerPrepaid
+		);
+	})
+}
+
+#[test]
+fn charge_ingame_prepaid_player_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		assert_ok!(UpfrontPool::prepay(Origin::signed(alice.clone()), 2));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		let before_balance = Balances::free_balance(&alice);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		assert_eq!(UpfrontPool::charge_ingame(MaxChargePerBlock::get()), 1);
+		assert_eq!(Balances::free_balance(&alice), before_balance);
+		assert_eq!(Prepayments::<Test>::get(alice.clone()).unwrap().periods, 1);
+		System::assert_last_event(Event::UpfrontPool(UpfrontEvent::PrepaidPeriodUsed {
+			player: alice.clone(),
+			remaining_periods: 1,
+		}));
+
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+		assert_eq!(Prepayments::<Test>::get(alice.clone()), None);
+		assert_eq!(Balances::free_balance(&alice), before_balance);
+
+		// the player is charged again when the prepaid periods are used
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+		assert_eq!(Balances::free_balance(&alice), before_balance - service.value);
+	})
+}
+
+#[test]
+fn leave_refund_prepaid_periods_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		assert_ok!(UpfrontPool::prepay(Origin::signed(alice.clone()), 3));
+		let _ = UpfrontPool::move_newplayer_to_ingame();
+
+		run_to_block(2);
+		UpfrontPool::start_charge(UpfrontPool::get_timestamp());
+		UpfrontPool::charge_ingame(MaxChargePerBlock::get());
+
+		// the reserve and the 2 unused periods are returned
+		let before_balance = Balances::free_balance(&alice);
+		assert_ok!(UpfrontPool::leave(alice.clone()));
+		assert_eq!(
+			Balances::free_balance(&alice),
+			before_balance + service.value + service.value * 2
+		);
+		assert_eq!(Prepayments::<Test>::get(alice), None);
+	})
+}
+
+#[test]
+fn migrate_to_named_reserve_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		run_to_block(1);
+		let service = UpfrontPool::get_service(UPFRONT_BASIC_ID).unwrap();
+		let alice = new_account(1_000_000 * unit(GAKI));
+		assert_ok!(UpfrontPool::join(alice.clone(), UPFRONT_BASIC_ID));
+		assert_eq!(
+			Balances::reserved_balance_named(&UPFRONT_POOL_RESERVE_ID, &alice),
+			service.value
+		);
+
+		// the service fee reserved before the named reserves
+		Balances::unreserve_named(&UPFRONT_POOL_RESERVE_ID, &alice, service.value);
+		assert_ok!(Balances::reserve(&alice, service.value));
+		StorageVersion::new(0).put::<UpfrontPool>();
+
+		MigrateToNamedReserve::<Test>::on_runtime_upgrade();
+		assert_eq!(
+			Balances::reserved_balance_named(&UPFRONT_POOL_RESERVE_ID, &alice),
+			service.value
+		);
+		assert_eq!(Balances::reserved_balance(&alice), service.value);
+		assert_eq!(UpfrontPool::on_chain_storage_version(), 1);
+
+		// the migration runs once
+		MigrateToNamedReserve::<Test>::on_runtime_upgrade();
+		assert_eq!(Balances::reserved_balance(&alice), service.value);
+	})
+}
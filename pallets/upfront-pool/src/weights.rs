@@ -35,6 +35,14 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
 	fn set_max_player(s: u32,) -> Weight;
+	fn start_charge(s: u32, ) -> Weight;
+	fn charge_ingame(s: u32, ) -> Weight;
+	fn add_service() -> Weight;
+	fn update_service() -> Weight;
+	fn retire_service() -> Weight;
+	fn pay_arrears() -> Weight;
+	fn prepay() -> Weight;
+	fn set_prepaid_discounts() -> Weight;
 }
 
 /// Weight functions for `upfront_pool`.
@@ -50,6 +58,96 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	// Storage: UpfrontPool NextServices (r:1 w:0)
+	// Storage: UpfrontPool IngamePlayers (r:2 w:1)
+	// Storage: UpfrontPool NewPlayers (r:1 w:1)
+	// Storage: UpfrontPool ChargeCount (r:0 w:1)
+	// Storage: UpfrontPool ChargingPlayers (r:0 w:1)
+	// Storage: UpfrontPool ChargeCursor (r:0 w:1)
+	// Storage: UpfrontPool ChargeStart (r:0 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn start_charge(s: u32, ) -> Weight {
+		(10_000_000_u64)
+			.saturating_add((100_000_u64).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: UpfrontPool ChargeCursor (r:1 w:1)
+	// Storage: UpfrontPool ChargeCount (r:1 w:1)
+	// Storage: UpfrontPool ChargingPlayers (r:1 w:1)
+	// Storage: UpfrontPool ChargeStart (r:1 w:0)
+	// Storage: UpfrontPool Tickets (r:1 w:1)
+	// Storage: UpfrontPool RetiredServices (r:1 w:0)
+	// Storage: UpfrontPool Prepayments (r:1 w:1)
+	// Storage: UpfrontPool Services (r:1 w:0)
+	// Storage: UpfrontPool Delinquents (r:1 w:1)
+	// Storage: UpfrontPool Reserved (r:1 w:1)
+	// Storage: UpfrontPool IngamePlayers (r:1 w:1)
+	// Storage: UpfrontPool NewPlayers (r:1 w:1)
+	// Storage: UpfrontPool PlayerCount (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn charge_ingame(s: u32, ) -> Weight {
+		(10_000_000_u64)
+			.saturating_add((50_000_000_u64).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(s as Weight)))
+	}
+	// Storage: UpfrontPool Services (r:1 w:1)
+	fn add_service() -> Weight {
+		(16_312_000_u64)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: UpfrontPool Services (r:1 w:0)
+	// Storage: UpfrontPool RetiredServices (r:1 w:0)
+	// Storage: UpfrontPool NextServices (r:0 w:1)
+	fn update_service() -> Weight {
+		(17_650_000_u64)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: UpfrontPool Services (r:2 w:0)
+	// Storage: UpfrontPool RetiredServices (r:2 w:1)
+	// Storage: UpfrontPool NextServices (r:0 w:1)
+	fn retire_service() -> Weight {
+		(20_473_000_u64)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: UpfrontPool Delinquents (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn pay_arrears() -> Weight {
+		(30_187_000_u64)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: UpfrontPool Tickets (r:1 w:0)
+	// Storage: UpfrontPool Delinquents (r:1 w:0)
+	// Storage: UpfrontPool Services (r:1 w:0)
+	// Storage: UpfrontPool Prepayments (r:1 w:1)
+	// Storage: UpfrontPool PrepaidDiscounts (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn prepay() -> Weight {
+		(38_904_000_u64)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: UpfrontPool PrepaidDiscounts (r:0 w:1)
+	fn set_prepaid_discounts() -> Weight {
+		(12_716_000_u64)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 impl WeightInfo for () {
@@ -58,4 +156,48 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	fn start_charge(s: u32, ) -> Weight {
+		(10_000_000_u64)
+			.saturating_add((100_000_u64).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+	}
+	fn charge_ingame(s: u32, ) -> Weight {
+		(10_000_000_u64)
+			.saturating_add((50_000_000_u64).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((12 as Weight).saturating_mul(s as Weight)))
+	}
+	fn add_service() -> Weight {
+		(16_312_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn update_service() -> Weight {
+		(17_650_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn retire_service() -> Weight {
+		(20_473_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn pay_arrears() -> Weight {
+		(30_187_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn prepay() -> Weight {
+		(38_904_000_u64)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn set_prepaid_discounts() -> Weight {
+		(12_716_000_u64)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -1,2 +1,10 @@
-
 pub type ID = [u8; 32];
+
+/// The identifier of the balance reserved by a pallet
+pub type ReserveIdentifier = [u8; 8];
+
+pub const UPFRONT_POOL_RESERVE_ID: ReserveIdentifier = *b"gafi/upf";
+pub const STAKING_POOL_RESERVE_ID: ReserveIdentifier = *b"gafi/stk";
+pub const POOL_NAMES_RESERVE_ID: ReserveIdentifier = *b"gafi/pnm";
+pub const GAME_CREATOR_RESERVE_ID: ReserveIdentifier = *b"gafi/gcr";
+pub const ADDRESS_MAPPING_RESERVE_ID: ReserveIdentifier = *b"gafi/map";
@@ -28,6 +28,9 @@ pub enum NativeToken {
 
 pub type Balance = u128;
 
+/// The identifier of the assets in `pallet_assets`
+pub type AssetId = u32;
+
 pub struct GafiCurrency {}
 
 impl TokenInfo for GafiCurrency {
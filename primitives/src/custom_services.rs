@@ -1,7 +1,10 @@
-use crate::constant::ID;
+use crate::{constant::ID, currency::AssetId};
 use frame_support::pallet_prelude::*;
+#[cfg(feature = "std")]
+use frame_support::serde::{Deserialize, Serialize};
 use scale_info::TypeInfo;
-use sp_core::H160;
+use sp_core::{H160, H256};
+use sp_io::hashing::blake2_256;
 use sp_runtime::{RuntimeDebug, Permill};
 use sp_std::vec::Vec;
 use crate::pool::{Service};
@@ -11,23 +14,155 @@ pub struct CustomService<AccountId> {
 	pub service: Service,
 	pub sponsor: AccountId,
 	pub targets: Vec<H160>,
+	/// pallet index and call index of the sponsored native extrinsics
+	pub call_targets: Vec<(u8, u8)>,
+	/// the sponsored function selectors of the targets, the targets without
+	/// selectors are sponsored for any call
+	pub selectors: Vec<(H160, Vec<[u8; 4]>)>,
 }
 
 impl<AccountId> CustomService<AccountId> {
-	pub fn new(targets: Vec<H160>, tx_limit: u32, discount: Permill, sponsor: AccountId) -> Self {
+	pub fn new(
+		targets: Vec<H160>,
+		call_targets: Vec<(u8, u8)>,
+		selectors: Vec<(H160, Vec<[u8; 4]>)>,
+		tx_limit: u32,
+		discount: Permill,
+		sponsor: AccountId,
+	) -> Self {
 		CustomService {
 			targets,
+			call_targets,
+			selectors,
 			service: Service { tx_limit, discount },
 			sponsor,
 		}
 	}
+
+	/// Check if the call to the `contract` with the function `selector` is sponsored
+	pub fn is_sponsored_function(&self, contract: &H160, selector: Option<[u8; 4]>) -> bool {
+		if !self.targets.contains(contract) {
+			return false;
+		}
+		match self.selectors.iter().find(|(target, _)| target == contract) {
+			Some((_, selectors)) => selector.map_or(false, |selector| selectors.contains(&selector)),
+			None => true,
+		}
+	}
+}
+
+/// The prefix of the hash of the sponsored pool id
+pub const SPONSORED_POOL_ID_PREFIX: [u8; 8] = *b"gafi/spo";
+
+/// What the sponsored pool id is derived from besides the owner
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum PoolIdSeed {
+	/// the number of the pools the owner created before without a salt
+	Nonce(u64),
+	/// the salt chosen by the owner
+	Salt(H256),
+}
+
+/// The id of the sponsored pool created by the `owner`,
+/// so the clients know the id before the pool is created
+pub fn sponsored_pool_id<AccountId: Encode>(owner: &AccountId, seed: PoolIdSeed) -> ID {
+	(SPONSORED_POOL_ID_PREFIX, owner, seed).using_encoded(blake2_256)
+}
+
+/// The account holding the balance of the sponsored pool
+pub fn sponsored_pool_account<AccountId: Decode>(pool_id: &ID) -> Option<AccountId> {
+	AccountId::decode(&mut &pool_id[..]).ok()
+}
+
+/// Snapshot of a sponsored pool exposed to clients through the runtime API
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct SponsoredPoolInfo<AccountId, Balance> {
+	pub id: ID,
+	pub owner: AccountId,
+	pub service: Service,
+	pub targets: Vec<H160>,
+	pub call_targets: Vec<(u8, u8)>,
+	pub selectors: Vec<(H160, Vec<[u8; 4]>)>,
+	/// the asset the pool is funded with, `None` for the native currency
+	pub asset: Option<AssetId>,
+	/// the balance of the pool in the currency of the pool
+	pub balance: Balance,
+	/// the pool stops sponsoring until the balance is topped up
+	pub paused: bool,
 }
 
 pub trait CustomPool<AccountId> {
 	fn join(sender: AccountId, pool_id: ID) -> DispatchResult;
-	fn leave(sender: AccountId) -> DispatchResult;
+	fn leave(sender: AccountId, pool_id: ID) -> DispatchResult;
 	fn get_service(pool_id: ID) -> Option<CustomService<AccountId>>;
+	/// Whether the pool is paused and its balance still can't fund the fee that paused it
+	fn is_paused(pool_id: ID) -> bool;
+	/// Whether the player or the pool has spent the cap of the current period
+	fn is_capped(pool_id: ID, player: &AccountId) -> bool;
+	/// Store the player joined the pool before the members were stored, used by the storage
+	/// migration of pallet-pool, return false if the pool does not exist
+	fn backfill_member(pool_id: ID, player: &AccountId) -> bool;
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn add_default(owner: AccountId, pool_id: ID);
 }
+
+/// Budget controls of the sponsored pools
+pub trait SponsoredBudget<AccountId> {
+	/// Check the sponsored fee of the player against the pool caps,
+	/// return false when any cap is hit or the pool is paused
+	fn check_budget(pool_id: ID, player: &AccountId, fee: u128) -> bool;
+	/// Record the fee sponsored for the player
+	fn spend(pool_id: ID, player: &AccountId, fee: u128);
+	/// The pool account could not fund the sponsored fee
+	fn insufficient_balance(pool_id: ID, fee: u128);
+}
+
+impl<AccountId> SponsoredBudget<AccountId> for () {
+	fn check_budget(_pool_id: ID, _player: &AccountId, _fee: u128) -> bool {
+		true
+	}
+	fn spend(_pool_id: ID, _player: &AccountId, _fee: u128) {}
+	fn insufficient_balance(_pool_id: ID, _fee: u128) {}
+}
+
+/// The asset of the sponsored pools funded with an asset instead of the native currency
+pub trait SponsoredAsset {
+	/// The asset the pool is funded with, `None` for the native currency
+	fn pool_asset(pool_id: ID) -> Option<AssetId>;
+}
+
+impl SponsoredAsset for () {
+	fn pool_asset(_pool_id: ID) -> Option<AssetId> {
+		None
+	}
+}
+
+/// Pay the native fee with an asset at the approved rate
+pub trait AssetConversion<AccountId> {
+	/// The amount of the asset worth `fee` in the native currency,
+	/// `None` when the asset has no approved rate
+	fn to_asset(asset: AssetId, fee: u128) -> Option<u128>;
+	/// Pay `fee` in the native currency with the asset of the `payer`,
+	/// return the amount of the asset spent
+	fn pay_fee(asset: AssetId, payer: &AccountId, fee: u128) -> Option<u128>;
+
+	/// Approve the rate of the asset
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_rate(asset: AssetId, rate: sp_runtime::FixedU128);
+}
+
+impl<AccountId> AssetConversion<AccountId> for () {
+	fn to_asset(_asset: AssetId, _fee: u128) -> Option<u128> {
+		None
+	}
+	fn pay_fee(_asset: AssetId, _payer: &AccountId, _fee: u128) -> Option<u128> {
+		None
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_rate(_asset: AssetId, _rate: sp_runtime::FixedU128) {}
+}
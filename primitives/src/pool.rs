@@ -4,7 +4,7 @@ use frame_support::serde::{Deserialize, Serialize};
 use scale_info::TypeInfo;
 use sp_runtime::{RuntimeDebug, Permill};
 
-use crate::constant::ID;
+use crate::{constant::ID, ticket::TicketType};
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Copy, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -27,6 +27,8 @@ pub trait MasterPool<AccountId> {
 	fn remove_player(player: &AccountId, pool_id: ID);
 	fn get_timeservice() -> u128;
 	fn get_marktime() -> u128;
+	fn get_timeservice_start() -> u128;
+	fn change_pool(player: &AccountId, pool_id: ID, new_ticket: TicketType);
 }
 
 impl<AccountId> MasterPool<AccountId> for () {
@@ -37,4 +39,8 @@ impl<AccountId> MasterPool<AccountId> for () {
 	fn get_marktime() -> u128 {
 		u128::default()
 	}
+	fn get_timeservice_start() -> u128 {
+		u128::default()
+	}
+	fn change_pool(_player: &AccountId, _pool_id: ID, _new_ticket: TicketType) {}
 }
@@ -30,7 +30,18 @@ impl SystemService {
 pub trait SystemPool<AccountId> {
 	fn join(sender: AccountId, pool_id: ID) -> DispatchResult;
 	fn leave(sender: AccountId) -> DispatchResult;
+	fn change_level(sender: AccountId, new_pool_id: ID) -> DispatchResult;
 	fn get_service(pool_id: ID) -> Option<SystemService>;
+	/// The ticket of the player can't be used, e.g. the service fee is unpaid
+	fn is_suspended(player: &AccountId) -> bool;
+}
+
+/// The system pool that lets a sponsor fund the ticket of a beneficiary
+pub trait SponsoredJoin<AccountId> {
+	/// Reserve the stake from the sponsor and issue the ticket to the beneficiary
+	fn join_for(sponsor: AccountId, beneficiary: AccountId, pool_id: ID) -> DispatchResult;
+	/// The sponsor funding the ticket of the player, the only one who can leave it
+	fn sponsor_of(player: &AccountId) -> Option<AccountId>;
 }
 
 pub trait SystemDefaultServices {
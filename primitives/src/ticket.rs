@@ -45,6 +45,8 @@ pub enum TicketLevel {
 	Basic,
 	Medium,
 	Advance,
+	/// The level added on runtime by the admin
+	Custom(u8),
 }
 
 /// Holding the number of tickets to restrict player transaction
@@ -53,6 +55,8 @@ pub enum TicketLevel {
 pub struct TicketInfo {
     pub ticket_type: TicketType,
     pub tickets: u32,
+    /// the epoch that the tickets were renewed lastly
+    pub epoch: u32,
 }
 
 impl TicketInfo {
@@ -62,28 +66,42 @@ impl TicketInfo {
             return Some(TicketInfo {
                 tickets: new_tickets,
                 ticket_type: self.ticket_type,
+                epoch: self.epoch,
             });
         }
         None
     }
 
     /// renew ticket
-    pub fn renew_ticket(&self, new_remain: u32) -> Self {
+    pub fn renew_ticket(&self, new_remain: u32, epoch: u32) -> Self {
         TicketInfo {
             tickets: new_remain,
             ticket_type: self.ticket_type,
+            epoch,
         }
     }
 }
 
+/// The target of the transaction that the ticket is used for
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Copy, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TicketTarget {
+    /// smart-contract address of an EVM transaction
+    Contract(H160),
+    /// pallet index and call index of a native extrinsic
+    Call(u8, u8),
+    /// smart-contract address and the 4-byte function selector of an EVM transaction
+    Function(H160, [u8; 4]),
+}
+
 pub trait PlayerTicket<AccountId> {
-    fn use_ticket(player: AccountId, target: Option<H160>) -> Option<(TicketType, ID)>;
+    fn use_ticket(player: AccountId, target: Option<TicketTarget>) -> Option<(TicketType, ID)>;
     fn get_service(pool_id: ID) -> Option<Service>;
     fn get_targets(pool_id: ID) -> Vec<H160>;
 }
 
 impl<AccountId> PlayerTicket<AccountId> for () {
-    fn use_ticket(_player: AccountId, _target: Option<H160>) -> Option<(TicketType, ID)> {
+    fn use_ticket(_player: AccountId, _target: Option<TicketTarget>) -> Option<(TicketType, ID)> {
         None
     }
 
@@ -23,7 +23,7 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, DispatchInfoOf, Dispatchable,
-		IdentifyAccount, NumberFor, PostDispatchInfoOf, Verify,
+		Convert, IdentifyAccount, NumberFor, PostDispatchInfoOf, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
 	ApplyExtrinsicResult, MultiSignature,
@@ -63,7 +63,7 @@ pub use sp_runtime::{Perbill, Permill};
 
 pub use gafi_primitives::{
 	cache::Cache,
-	currency::{centi, deposit, microcent, milli, unit, NativeToken::GAKI},
+	currency::{centi, deposit, microcent, milli, unit, AssetId, NativeToken::GAKI},
 	system_services::{SystemService, SystemDefaultServices},
 	ticket::{TicketInfo, TicketType, SystemTicket, TicketLevel},
 	constant::ID
@@ -82,7 +82,7 @@ pub use staking_pool;
 pub use upfront_pool;
 
 // custom traits
-use gafi_tx::{GafiEVMCurrencyAdapter, GafiGasWeightMapping};
+use gafi_tx::{GafiCurrencyAdapter, GafiEVMCurrencyAdapter, GafiGasWeightMapping};
 
 mod precompiles;
 use precompiles::FrontierPrecompiles;
@@ -288,11 +288,12 @@ parameter_types! {
 	// For weight estimation, we assume that the most locks on an individual account will be 50.
 	// This number may need to be adjusted in the future if this assumption no longer holds true.
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -305,13 +306,41 @@ impl pallet_balances::Config for Runtime {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees<Runtime>>;
+	type OnChargeTransaction = GafiCurrencyAdapter<
+		CurrencyAdapter<Balances, staking_pool::ShareFees<Runtime, DealWithFees<Runtime>>>,
+	>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
 }
 
+parameter_types! {
+	pub AssetDeposit: Balance = 100 * unit(GAKI);
+	pub AssetAccountDeposit: Balance = deposit(1, 16, GAKI);
+	pub ApprovalDeposit: Balance = deposit(1, 0, GAKI);
+	pub MetadataDepositBase: Balance = deposit(1, 68, GAKI);
+	pub MetadataDepositPerByte: Balance = deposit(0, 1, GAKI);
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_sudo::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -346,12 +375,13 @@ impl pallet_evm::Config for Runtime {
 	type AddressMapping = ProofAddressMapping;
 	type Currency = Balances;
 	type Event = Event;
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = gafi_tx::GafiRunner<Self, pallet_evm::runner::stack::Runner<Self>>;
 	type PrecompilesType = FrontierPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
-	type OnChargeTransaction = GafiEVMCurrencyAdapter<Balances, DealWithFees<Runtime>>;
+	type OnChargeTransaction =
+		GafiEVMCurrencyAdapter<Balances, staking_pool::ShareFees<Runtime, DealWithFees<Runtime>>>;
 	type FindAuthor = FindAuthorTruncated<Aura>;
 }
 
@@ -403,6 +433,10 @@ impl pallet_player::Config for Runtime {
 
 parameter_types! {
 	pub const MaxPlayerStorage: u32 = 10000;
+	pub const MaxChargePerBlock: u32 = 100;
+	pub const GracePeriods: u32 = 1;
+	pub const MaxPrepaidPeriods: u32 = 30;
+	pub const MaxPrepaidTiers: u32 = 10;
 }
 
 pub struct UpfrontPoolDefaultServices {}
@@ -433,6 +467,11 @@ impl upfront_pool::Config for Runtime {
 	type MaxPlayerStorage = MaxPlayerStorage;
 	type MasterPool = Pool;
 	type UpfrontServices = UpfrontPoolDefaultServices;
+	type MaxChargePerBlock = MaxChargePerBlock;
+	type GracePeriods = GracePeriods;
+	type MaxPrepaidPeriods = MaxPrepaidPeriods;
+	type MaxPrepaidTiers = MaxPrepaidTiers;
+	type AdminOrigin = EnsureRoot<AccountId>;
 }
 
 pub struct StakingPoolDefaultServices {}
@@ -456,11 +495,24 @@ impl SystemDefaultServices for StakingPoolDefaultServices {
 	}
 }
 
+parameter_types! {
+	pub const UnbondingPeriod: BlockNumber = 2 * DAYS;
+	pub const MaxUnlockingChunks: u32 = 32;
+	pub const MaxWaitlist: u32 = 1000;
+	pub const MaxBeneficiaries: u32 = 100;
+}
+
 impl staking_pool::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = staking_pool::weights::SubstrateWeight<Runtime>;
 	type StakingServices = StakingPoolDefaultServices;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MasterPool = Pool;
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxWaitlist = MaxWaitlist;
+	type MaxBeneficiaries = MaxBeneficiaries;
 }
 
 parameter_types! {
@@ -471,13 +523,19 @@ parameter_types! {
 	pub MaxTxLimit: u32 = 100;
 	pub MaxPoolOwned: u32 =  10;
 	pub MaxPoolTarget: u32 =  10;
+	pub MaxExpiringPools: u32 = 50;
+	pub CleanupBatch: u32 = 100;
+	pub MaxTargetSelector: u32 = 10;
+	pub MaxAllowlist: u32 = 500;
 }
 
 impl sponsored_pool::Config for Runtime {
 	type Event = Event;
-	type Randomness = RandomnessCollectiveFlip;
 	type PoolName = PoolName;
+	type MasterPool = Pool;
 	type Currency = Balances;
+	type Assets = Assets;
+	type AssetConversion = gafi_tx::BurnAsset<Runtime, Assets>;
 	type MinPoolBalance = MinPoolBalance;
 	type MinDiscountPercent = MinDiscountPercent;
 	type MaxDiscountPercent = MaxDiscountPercent;
@@ -485,9 +543,22 @@ impl sponsored_pool::Config for Runtime {
 	type MaxTxLimit = MaxTxLimit;
 	type MaxPoolOwned = MaxPoolOwned;
 	type MaxPoolTarget = MaxPoolTarget;
+	type MaxExpiringPools = MaxExpiringPools;
+	type CleanupBatch = CleanupBatch;
+	type MaxTargetSelector = MaxTargetSelector;
+	type MaxAllowlist = MaxAllowlist;
 	type WeightInfo = sponsored_pool::weights::SponsoredWeight<Runtime>;
 }
 
+/// The owner of the sponsored pool, who paid the deposit of the pool name
+pub struct SponsoredPoolOwner;
+
+impl Convert<ID, Option<AccountId>> for SponsoredPoolOwner {
+	fn convert(pool_id: ID) -> Option<AccountId> {
+		sponsored_pool::Pools::<Runtime>::get(pool_id).map(|pool| pool.owner)
+	}
+}
+
 parameter_types! {
 	pub Prefix: &'static [u8] =  b"Bond Gafi Network account:";
 	pub Fee: u128 = 1 * unit(GAKI);
@@ -508,9 +579,14 @@ parameter_types! {
 impl gafi_tx::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
-	type OnChargeEVMTxHandler = EVMCurrencyAdapter<Balances, DealWithFees<Runtime>>;
+	type OnChargeEVMTxHandler =
+		EVMCurrencyAdapter<Balances, staking_pool::ShareFees<Runtime, DealWithFees<Runtime>>>;
 	type AddressMapping = ProofAddressMapping;
 	type PlayerTicket = Pool;
+	type SponsoredBudget = SponsoredPool;
+	type SponsoredAsset = SponsoredPool;
+	type AssetConversion = gafi_tx::BurnAsset<Runtime, Assets>;
+	type RateOrigin = MoreThanHalfCouncil;
 	type GameCreatorReward = GameCreatorReward;
 	type GetGameCreator = GameCreator;
 }
@@ -581,6 +657,7 @@ impl pallet_pool::Config for Runtime {
 	type SponsoredPool = SponsoredPool;
 	type Cache = PalletCache;
 	type TimeServiceStorage = TimeServiceStorage;
+	type AdminOrigin = EnsureRoot<AccountId>;
 }
 
 parameter_types! {
@@ -833,6 +910,7 @@ construct_runtime!(
 		Grandpa: pallet_grandpa::{Pallet, Call, Storage, Config, Event},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin},
 		EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>},
@@ -925,8 +1003,23 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPallets,
+	Migrations,
 >;
 
+/// The storage migrations of the Gafi pallets
+pub type Migrations = (
+	upfront_pool::migrations::v1::MigrateToNamedReserve<Runtime>,
+	staking_pool::migrations::v1::MigrateToNamedReserve<Runtime>,
+	staking_pool::migrations::v2::InitializeTotalStaked<Runtime>,
+	staking_pool::migrations::v3::InitializeLevelPlayerCount<Runtime>,
+	staking_pool::migrations::v4::MigrateToLevelMaxPlayer<Runtime>,
+	pallet_pool_names::migrations::v1::MigrateToNamedReserve<Runtime, SponsoredPoolOwner>,
+	proof_address_mapping::migrations::v1::MigrateToNamedReserve<Runtime>,
+	game_creator::migrations::v1::MigrateToNamedReserve<Runtime>,
+	pallet_pool::migrations::v1::MigrateToEpochTickets<Runtime, pallet_cache::Instance2>,
+	pallet_pool::migrations::v2::BackfillSponsoredMembers<Runtime>,
+);
+
 impl fp_self_contained::SelfContainedCall for Call {
     type SignedInfo = H160;
 
@@ -1234,6 +1327,46 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_pool_rpc_runtime_api::GafiPoolApi<Block, AccountId, Balance> for Runtime {
+		fn joined_pools(
+			player: AccountId,
+		) -> Vec<(pallet_pool_rpc_runtime_api::ID, pallet_pool_rpc_runtime_api::TicketInfo)> {
+			Pool::joined_pools(&player)
+		}
+
+		fn tickets_remaining(player: AccountId, pool_id: pallet_pool_rpc_runtime_api::ID) -> Option<u32> {
+			Pool::tickets_remaining(&player, pool_id)
+		}
+
+		fn pool_service(
+			pool_id: pallet_pool_rpc_runtime_api::ID,
+		) -> Option<pallet_pool_rpc_runtime_api::Service> {
+			<Pool as gafi_primitives::ticket::PlayerTicket<AccountId>>::get_service(pool_id)
+		}
+
+		fn sponsored_pool(
+			pool_id: pallet_pool_rpc_runtime_api::ID,
+		) -> Option<pallet_pool_rpc_runtime_api::SponsoredPoolInfo<AccountId, Balance>> {
+			SponsoredPool::pool_info(pool_id)
+		}
+
+		fn next_renewal_in() -> u128 {
+			Pool::next_renewal_in()
+		}
+
+		fn pending_rewards(player: AccountId) -> Balance {
+			StakingPool::pending_rewards(&player)
+		}
+
+		fn waitlist_position(player: AccountId) -> Option<u32> {
+			StakingPool::waitlist_position(&player)
+		}
+
+		fn beneficiaries(sponsor: AccountId) -> Vec<AccountId> {
+			StakingPool::beneficiaries(&sponsor).into_inner()
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			opaque::SessionKeys::generate(seed)
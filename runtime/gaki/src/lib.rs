@@ -18,7 +18,7 @@ use sp_core::{
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, IdentifyAccount, Verify},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -79,7 +79,7 @@ use gafi_primitives::{
 };
 
 // Primitives
-use gafi_primitives::currency::{centi, unit, NativeToken::GAFI};
+use gafi_primitives::currency::{centi, unit, AssetId, NativeToken::GAFI};
 
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -152,8 +152,22 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// The storage migrations of the Gafi pallets
+pub type Migrations = (
+	upfront_pool::migrations::v1::MigrateToNamedReserve<Runtime>,
+	staking_pool::migrations::v1::MigrateToNamedReserve<Runtime>,
+	staking_pool::migrations::v2::InitializeTotalStaked<Runtime>,
+	staking_pool::migrations::v3::InitializeLevelPlayerCount<Runtime>,
+	staking_pool::migrations::v4::MigrateToLevelMaxPlayer<Runtime>,
+	pallet_pool_names::migrations::v1::MigrateToNamedReserve<Runtime, SponsoredPoolOwner>,
+	proof_address_mapping::migrations::v1::MigrateToNamedReserve<Runtime>,
+	pallet_pool::migrations::v1::MigrateToEpochTickets<Runtime>,
+	pallet_pool::migrations::v2::BackfillSponsoredMembers<Runtime>,
+);
+
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
 ///
@@ -426,13 +440,40 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction =
+		pallet_transaction_payment::CurrencyAdapter<Balances, staking_pool::ShareFees<Runtime, ()>>;
 	type WeightToFee = WeightToFee;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 }
 
+parameter_types! {
+	pub AssetDeposit: Balance = 100 * unit(GAFI);
+	pub AssetAccountDeposit: Balance = centi(GAFI);
+	pub ApprovalDeposit: Balance = centi(GAFI);
+	pub MetadataDepositBase: Balance = 10 * unit(GAFI);
+	pub MetadataDepositPerByte: Balance = centi(GAFI);
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
@@ -562,7 +603,7 @@ impl pallet_evm::Config for Runtime {
 	type PrecompilesValue = PrecompilesValue;
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
-	type OnChargeTransaction = EVMCurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = EVMCurrencyAdapter<Balances, staking_pool::ShareFees<Runtime, ()>>;
 	type FindAuthor = FindAuthorTruncated<Aura>;
 }
 
@@ -594,11 +635,24 @@ impl SystemDefaultServices for StakingPoolDefaultServices {
 	}
 }
 
+parameter_types! {
+	pub const UnbondingPeriod: BlockNumber = 2 * DAYS;
+	pub const MaxUnlockingChunks: u32 = 32;
+	pub const MaxWaitlist: u32 = 1000;
+	pub const MaxBeneficiaries: u32 = 100;
+}
+
 impl staking_pool::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = staking_pool::weights::SubstrateWeight<Runtime>;
 	type StakingServices = StakingPoolDefaultServices;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MasterPool = Pool;
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxWaitlist = MaxWaitlist;
+	type MaxBeneficiaries = MaxBeneficiaries;
 }
 
 parameter_types! {
@@ -648,6 +702,10 @@ impl SystemDefaultServices for UpfrontPoolDefaultServices {
 
 parameter_types! {
 	pub const MaxPlayerStorage: u32 = 10000;
+	pub const MaxChargePerBlock: u32 = 100;
+	pub const GracePeriods: u32 = 1;
+	pub const MaxPrepaidPeriods: u32 = 30;
+	pub const MaxPrepaidTiers: u32 = 10;
 }
 
 impl upfront_pool::Config for Runtime {
@@ -657,6 +715,11 @@ impl upfront_pool::Config for Runtime {
 	type MaxPlayerStorage = MaxPlayerStorage;
 	type MasterPool = Pool;
 	type UpfrontServices = UpfrontPoolDefaultServices;
+	type MaxChargePerBlock = MaxChargePerBlock;
+	type GracePeriods = GracePeriods;
+	type MaxPrepaidPeriods = MaxPrepaidPeriods;
+	type MaxPrepaidTiers = MaxPrepaidTiers;
+	type AdminOrigin = EnsureRoot<AccountId>;
 }
 
 // parameter_types! {
@@ -696,13 +759,19 @@ parameter_types! {
 	pub MaxTxLimit: u32 = 100;
 	pub MaxPoolOwned: u32 =  10;
 	pub MaxPoolTarget: u32 =  10;
+	pub MaxExpiringPools: u32 = 50;
+	pub CleanupBatch: u32 = 100;
+	pub MaxTargetSelector: u32 = 10;
+	pub MaxAllowlist: u32 = 500;
 }
 
 impl sponsored_pool::Config for Runtime {
 	type Event = Event;
-	type Randomness = RandomnessCollectiveFlip;
 	type PoolName = PoolName;
+	type MasterPool = Pool;
 	type Currency = Balances;
+	type Assets = Assets;
+	type AssetConversion = ();
 	type MinPoolBalance = MinPoolBalance;
 	type MinDiscountPercent = MinDiscountPercent;
 	type MaxDiscountPercent = MaxDiscountPercent;
@@ -710,9 +779,22 @@ impl sponsored_pool::Config for Runtime {
 	type MaxTxLimit = MaxTxLimit;
 	type MaxPoolOwned = MaxPoolOwned;
 	type MaxPoolTarget = MaxPoolTarget;
+	type MaxExpiringPools = MaxExpiringPools;
+	type CleanupBatch = CleanupBatch;
+	type MaxTargetSelector = MaxTargetSelector;
+	type MaxAllowlist = MaxAllowlist;
 	type WeightInfo = sponsored_pool::weights::SponsoredWeight<Runtime>;
 }
 
+/// The owner of the sponsored pool, who paid the deposit of the pool name
+pub struct SponsoredPoolOwner;
+
+impl Convert<ID, Option<AccountId>> for SponsoredPoolOwner {
+	fn convert(pool_id: ID) -> Option<AccountId> {
+		sponsored_pool::Pools::<Runtime>::get(pool_id).map(|pool| pool.owner)
+	}
+}
+
 parameter_types! {
 	pub MaxJoinedSponsoredPool: u32 = 5;
 	pub TimeServiceStorage: u128 = 30 * 60_000u128;
@@ -728,6 +810,7 @@ impl pallet_pool::Config for Runtime {
 	type SponsoredPool = SponsoredPool;
 	type Cache = PalletCache;
 	type TimeServiceStorage = TimeServiceStorage;
+	type AdminOrigin = EnsureRoot<AccountId>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -749,6 +832,7 @@ construct_runtime!(
 		// Monetary stuff.
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 12,
 
 		// Collator support. The order of these 4 are important and shall not change.
 		Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
@@ -922,6 +1006,46 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_pool_rpc_runtime_api::GafiPoolApi<Block, AccountId, Balance> for Runtime {
+		fn joined_pools(
+			player: AccountId,
+		) -> Vec<(pallet_pool_rpc_runtime_api::ID, pallet_pool_rpc_runtime_api::TicketInfo)> {
+			Pool::joined_pools(&player)
+		}
+
+		fn tickets_remaining(player: AccountId, pool_id: pallet_pool_rpc_runtime_api::ID) -> Option<u32> {
+			Pool::tickets_remaining(&player, pool_id)
+		}
+
+		fn pool_service(
+			pool_id: pallet_pool_rpc_runtime_api::ID,
+		) -> Option<pallet_pool_rpc_runtime_api::Service> {
+			<Pool as gafi_primitives::ticket::PlayerTicket<AccountId>>::get_service(pool_id)
+		}
+
+		fn sponsored_pool(
+			pool_id: pallet_pool_rpc_runtime_api::ID,
+		) -> Option<pallet_pool_rpc_runtime_api::SponsoredPoolInfo<AccountId, Balance>> {
+			SponsoredPool::pool_info(pool_id)
+		}
+
+		fn next_renewal_in() -> u128 {
+			Pool::next_renewal_in()
+		}
+
+		fn pending_rewards(player: AccountId) -> Balance {
+			StakingPool::pending_rewards(&player)
+		}
+
+		fn waitlist_position(player: AccountId) -> Option<u32> {
+			StakingPool::waitlist_position(&player)
+		}
+
+		fn beneficiaries(sponsor: AccountId) -> Vec<AccountId> {
+			StakingPool::beneficiaries(&sponsor).into_inner()
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -0,0 +1,272 @@
+use crate::mock::*;
+use frame_support::{
+    assert_ok,
+    traits::{fungibles, Currency},
+    weights::{DispatchInfo, PostDispatchInfo},
+};
+use gafi_primitives::{
+    constant::ID,
+    currency::{unit, AssetId, NativeToken::GAKI},
+    ticket::{CustomTicket, SystemTicket, TicketLevel, TicketTarget, TicketType},
+};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_core::H160;
+use sp_runtime::{AccountId32, FixedPointNumber, FixedU128, Permill};
+
+type OnChargeTx = <Test as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+fn new_account(account: [u8; 32], balance: u128) -> AccountId32 {
+    let acc: AccountId32 = AccountId32::from(account);
+    let _ = pallet_balances::Pallet::<Test>::deposit_creating(&acc, balance);
+    assert_eq!(Balances::free_balance(&acc), balance);
+    acc
+}
+
+fn transfer_call(dest: &AccountId32) -> Call {
+    Call::Balances(BalancesCall::transfer {
+        dest: dest.clone(),
+        value: unit(GAKI),
+    })
+}
+
+fn charge_fee(account: &AccountId32, call: &Call, fee: u128) {
+    let info = DispatchInfo::default();
+    let post_info = PostDispatchInfo::default();
+
+    let liquidity_info =
+        <OnChargeTx as OnChargeTransaction<Test>>::withdraw_fee(account, call, &info, fee, 0)
+            .unwrap();
+    assert_ok!(<OnChargeTx as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+        account,
+        &info,
+        &post_info,
+        fee,
+        0,
+        liquidity_info
+    ));
+}
+
+fn create_pool(account: AccountId32, pool_value: u128, discount: Permill) -> ID {
+    assert_ok!(SponsoredPool::create_pool(
+        Origin::signed(account.clone()),
+        vec![H160::default()],
+        pool_value,
+        discount,
+        10_u32,
+        None,
+        None
+    ));
+    *SponsoredPool::pool_owned(&account).last().unwrap()
+}
+
+#[test]
+fn charge_native_tx_without_ticket_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let fee = unit(GAKI);
+
+        charge_fee(&account, &transfer_call(&account), fee);
+        assert_eq!(Balances::free_balance(&account), account_balance - fee);
+    })
+}
+
+#[test]
+fn charge_native_tx_with_system_ticket_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let fee = unit(GAKI);
+
+        assert_ok!(Pool::join(
+            Origin::signed(account.clone()),
+            TicketType::System(SystemTicket::Staking(TicketLevel::Basic))
+        ));
+        let before_balance = Balances::free_balance(&account);
+
+        charge_fee(&account, &transfer_call(&account), fee);
+        let discount = Permill::from_percent(30) * fee;
+        assert_eq!(
+            Balances::free_balance(&account),
+            before_balance - (fee - discount)
+        );
+    })
+}
+
+#[test]
+fn charge_native_tx_with_sponsored_ticket_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let owner = new_account([0_u8; 32], account_balance);
+        let account = new_account([1_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let fee = unit(GAKI);
+
+        let pool_id = create_pool(owner.clone(), pool_value, Permill::from_percent(30));
+        let pool_account = AccountId32::from(pool_id);
+        let call = transfer_call(&owner);
+        let call_target = match PalletTxHandler::call_target(&call) {
+            Some(TicketTarget::Call(pallet_index, call_index)) => (pallet_index, call_index),
+            _ => unreachable!(),
+        };
+        assert_ok!(SponsoredPool::new_call_targets(
+            Origin::signed(owner.clone()),
+            pool_id,
+            vec![call_target]
+        ));
+        assert_ok!(Pool::join(
+            Origin::signed(account.clone()),
+            TicketType::Custom(CustomTicket::Sponsored(pool_id))
+        ));
+
+        // the call is not sponsored
+        charge_fee(&account, &Call::System(frame_system::Call::remark { remark: vec![] }), fee);
+        assert_eq!(Balances::free_balance(&account), account_balance - fee);
+        assert_eq!(Balances::free_balance(&pool_account), pool_value);
+
+        let discount = Permill::from_percent(30) * fee;
+        charge_fee(&account, &call, fee);
+        assert_eq!(
+            Balances::free_balance(&account),
+            account_balance - fee - (fee - discount)
+        );
+        assert_eq!(Balances::free_balance(&pool_account), pool_value - discount);
+    })
+}
+
+#[test]
+fn charge_native_tx_over_sponsored_cap_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let owner = new_account([0_u8; 32], account_balance);
+        let account = new_account([1_u8; 32], account_balance);
+        let pool_value = 1000 * unit(GAKI);
+        let fee = unit(GAKI);
+
+        let pool_id = create_pool(owner.clone(), pool_value, Permill::from_percent(30));
+        let pool_account = AccountId32::from(pool_id);
+        let call = transfer_call(&owner);
+        let call_target = match PalletTxHandler::call_target(&call) {
+            Some(TicketTarget::Call(pallet_index, call_index)) => (pallet_index, call_index),
+            _ => unreachable!(),
+        };
+        assert_ok!(SponsoredPool::new_call_targets(
+            Origin::signed(owner.clone()),
+            pool_id,
+            vec![call_target]
+        ));
+        assert_ok!(Pool::join(
+            Origin::signed(account.clone()),
+            TicketType::Custom(CustomTicket::Sponsored(pool_id))
+        ));
+        assert_ok!(SponsoredPool::set_budget(
+            Origin::signed(owner.clone()),
+            pool_id,
+            Some(fee / 2),
+            None,
+            None
+        ));
+
+        let discount = Permill::from_percent(30) * fee;
+        charge_fee(&account, &call, fee);
+        assert_eq!(Balances::free_balance(&account), account_balance - (fee - discount));
+        assert_eq!(Balances::free_balance(&pool_account), pool_value - discount);
+
+        // the player cap is hit, the player pays the whole fee
+        charge_fee(&account, &call, fee);
+        assert_eq!(
+            Balances::free_balance(&account),
+            account_balance - (fee - discount) - fee
+        );
+        assert_eq!(Balances::free_balance(&pool_account), pool_value - discount);
+        System::assert_has_event(Event::SponsoredPool(sponsored_pool::Event::CapReached {
+            id: pool_id,
+            player: account.clone(),
+            cap: sponsored_pool::BudgetCap::Player,
+        }));
+    })
+}
+
+#[test]
+fn charge_native_tx_with_asset_pool_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(1);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let owner = new_account([0_u8; 32], account_balance);
+        let account = new_account([1_u8; 32], account_balance);
+        let asset_id: AssetId = 1;
+        let pool_value = 2000 * unit(GAKI);
+        let fee = unit(GAKI);
+
+        assert_ok!(<Assets as fungibles::Create<AccountId32>>::create(
+            asset_id,
+            owner.clone(),
+            false,
+            1
+        ));
+        assert_ok!(<Assets as fungibles::Mutate<AccountId32>>::mint_into(
+            asset_id,
+            &owner,
+            account_balance
+        ));
+        assert_ok!(PalletTxHandler::set_asset_rate(
+            Origin::root(),
+            asset_id,
+            Some(FixedU128::from(2))
+        ));
+        assert_ok!(SponsoredPool::create_asset_pool(
+            Origin::signed(owner.clone()),
+            vec![H160::default()],
+            asset_id,
+            pool_value,
+            Permill::from_percent(30),
+            10_u32,
+            None,
+            None
+        ));
+        let pool_id = *SponsoredPool::pool_owned(&owner).last().unwrap();
+        let pool_account = AccountId32::from(pool_id);
+        let call = transfer_call(&owner);
+        let call_target = match PalletTxHandler::call_target(&call) {
+            Some(TicketTarget::Call(pallet_index, call_index)) => (pallet_index, call_index),
+            _ => unreachable!(),
+        };
+        assert_ok!(SponsoredPool::new_call_targets(
+            Origin::signed(owner.clone()),
+            pool_id,
+            vec![call_target]
+        ));
+        assert_ok!(Pool::join(
+            Origin::signed(account.clone()),
+            TicketType::Custom(CustomTicket::Sponsored(pool_id))
+        ));
+        let pool_native = Balances::free_balance(&pool_account);
+
+        // the sponsored portion is burnt from the asset of the pool at the rate
+        let discount = Permill::from_percent(30) * fee;
+        charge_fee(&account, &call, fee);
+        assert_eq!(Balances::free_balance(&account), account_balance - (fee - discount));
+        assert_eq!(Balances::free_balance(&pool_account), pool_native);
+        assert_eq!(Assets::balance(asset_id, &pool_account), pool_value - 2 * discount);
+
+        // the sponsor pays less of the asset once the rate drops
+        assert_ok!(PalletTxHandler::set_asset_rate(
+            Origin::root(),
+            asset_id,
+            Some(FixedU128::saturating_from_rational(1, 2))
+        ));
+        charge_fee(&account, &call, fee);
+        assert_eq!(
+            Balances::free_balance(&account),
+            account_balance - 2 * (fee - discount)
+        );
+        assert_eq!(
+            Assets::balance(asset_id, &pool_account),
+            pool_value - 2 * discount - discount / 2
+        );
+    })
+}
@@ -14,3 +14,6 @@ mod pool_test;
 
 #[cfg(test)]
 mod sponsored_pool_test;
+
+#[cfg(test)]
+mod gafi_tx_test;
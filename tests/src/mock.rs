@@ -5,18 +5,18 @@ use frame_support::{
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU8, GenesisBuild},
+	traits::{ConstU128, ConstU8, GenesisBuild},
 	weights::IdentityFee,
 };
 use frame_system as system;
-use gafi_primitives::currency::{unit, NativeToken::GAKI};
+use gafi_primitives::currency::{unit, AssetId, NativeToken::GAKI};
 use gafi_primitives::ticket::TicketInfo;
 use gafi_primitives::{
 	system_services::{SystemService, SystemDefaultServices},
 	ticket::{TicketLevel, TicketType, SystemTicket},
 	constant::ID
 };
-use gafi_tx::GafiEVMCurrencyAdapter;
+use gafi_tx::{GafiCurrencyAdapter, GafiEVMCurrencyAdapter};
 pub use pallet_balances::Call as BalancesCall;
 use pallet_evm::{EnsureAddressNever, EnsureAddressRoot};
 use pallet_timestamp;
@@ -43,6 +43,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		UpfrontPool: upfront_pool::{Pallet, Call, Storage, Event<T>},
 		Pool: pallet_pool::{Pallet, Call, Storage, Event<T>},
@@ -91,7 +92,7 @@ impl proof_address_mapping::Config for Test {
 }
 
 impl pallet_transaction_payment::Config for Test {
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = GafiCurrencyAdapter<CurrencyAdapter<Balances, ()>>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<u128>;
 	type LengthToFee = IdentityFee<u128>;
@@ -112,7 +113,7 @@ impl pallet_evm::Config for Test {
 	type AddressMapping = ProofAddressMapping;
 	type Currency = Balances;
 	type Event = Event;
-	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type Runner = gafi_tx::GafiRunner<Self, pallet_evm::runner::stack::Runner<Self>>;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
 	type ChainId = ChainId;
@@ -152,6 +153,7 @@ impl pallet_pool::Config for Test {
 	type MaxJoinedSponsoredPool = MaxJoinedSponsoredPool;
 	type Cache = PalletCache;
 	type TimeServiceStorage = TimeServiceStorage;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
 }
 
 pub struct UpfrontPoolDefaultServices {}
@@ -177,6 +179,10 @@ impl SystemDefaultServices for UpfrontPoolDefaultServices {
 
 parameter_types! {
 	pub MaxPlayerStorage: u32 = 1000;
+	pub MaxChargePerBlock: u32 = 10;
+	pub GracePeriods: u32 = 1;
+	pub MaxPrepaidPeriods: u32 = 10;
+	pub MaxPrepaidTiers: u32 = 5;
 }
 
 impl upfront_pool::Config for Test {
@@ -186,6 +192,11 @@ impl upfront_pool::Config for Test {
 	type MaxPlayerStorage = MaxPlayerStorage;
 	type MasterPool = Pool;
 	type UpfrontServices = UpfrontPoolDefaultServices;
+	type MaxChargePerBlock = MaxChargePerBlock;
+	type GracePeriods = GracePeriods;
+	type MaxPrepaidPeriods = MaxPrepaidPeriods;
+	type MaxPrepaidTiers = MaxPrepaidTiers;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
 }
 
 pub struct StakingPoolDefaultServices {}
@@ -209,16 +220,33 @@ impl SystemDefaultServices for StakingPoolDefaultServices {
 	}
 }
 
+parameter_types! {
+	pub UnbondingPeriod: u64 = 10;
+	pub MaxUnlockingChunks: u32 = 5;
+	pub MaxWaitlist: u32 = 5;
+	pub MaxBeneficiaries: u32 = 2;
+}
+
 impl staking_pool::Config for Test {
 	type Event = Event;
 	type Currency = Balances;
 	type WeightInfo = ();
 	type StakingServices = StakingPoolDefaultServices;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
+	type MasterPool = Pool;
+	type UnbondingPeriod = UnbondingPeriod;
+	type MaxUnlockingChunks = MaxUnlockingChunks;
+	type MaxWaitlist = MaxWaitlist;
+	type MaxBeneficiaries = MaxBeneficiaries;
 }
 
 parameter_types! {
 	pub MaxPoolOwned: u32 =  10;
 	pub MaxPoolTarget: u32 = 10;
+	pub MaxExpiringPools: u32 = 2;
+	pub CleanupBatch: u32 = 2;
+	pub MaxTargetSelector: u32 = 10;
+	pub MaxAllowlist: u32 = 5;
 	pub MinPoolBalance: u128 = 1000 * unit(GAKI);
 	pub MinDiscountPercent: Permill = Permill::from_percent(10);
 	pub MaxDiscountPercent: Permill = Permill::from_percent(70);
@@ -228,11 +256,17 @@ parameter_types! {
 
 impl sponsored_pool::Config for Test {
 	type Event = Event;
-	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
+	type Assets = Assets;
+	type AssetConversion = gafi_tx::BurnAsset<Self, Assets>;
 	type PoolName = PoolNames;
+	type MasterPool = Pool;
 	type MaxPoolOwned = MaxPoolOwned;
 	type MaxPoolTarget = MaxPoolTarget;
+	type MaxExpiringPools = MaxExpiringPools;
+	type CleanupBatch = CleanupBatch;
+	type MaxTargetSelector = MaxTargetSelector;
+	type MaxAllowlist = MaxAllowlist;
 	type MinDiscountPercent = MinDiscountPercent;
 	type MaxDiscountPercent = MaxDiscountPercent;
 	type MinTxLimit = MinTxLimit;
@@ -266,7 +300,7 @@ parameter_types! {
 
 impl pallet_balances::Config for Test {
 	type MaxLocks = ();
-	type MaxReserves = ();
+	type MaxReserves = frame_support::traits::ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = u128;
 	type Event = Event;
@@ -276,6 +310,23 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u128;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+	type AssetDeposit = ConstU128<0>;
+	type AssetAccountDeposit = ConstU128<0>;
+	type MetadataDepositBase = ConstU128<0>;
+	type MetadataDepositPerByte = ConstU128<0>;
+	type ApprovalDeposit = ConstU128<0>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
 pub const RESERVATION_FEE: u128 = 2;
 
 parameter_types! {
@@ -332,6 +383,10 @@ impl gafi_tx::Config for Test {
 	type OnChargeEVMTxHandler = ();
 	type AddressMapping = ProofAddressMapping;
 	type PlayerTicket = Pool;
+	type SponsoredBudget = SponsoredPool;
+	type SponsoredAsset = SponsoredPool;
+	type AssetConversion = gafi_tx::BurnAsset<Self, Assets>;
+	type RateOrigin = frame_system::EnsureRoot<AccountId32>;
 	type GameCreatorReward = GameCreatorReward;
 	type GetGameCreator = GameCreator;
 }
@@ -355,6 +410,7 @@ pub fn run_to_block(n: u64) {
 		System::on_initialize(System::block_number());
 		UpfrontPool::on_initialize(System::block_number());
 		Pool::on_initialize(System::block_number());
+		SponsoredPool::on_initialize(System::block_number());
 		Timestamp::set_timestamp((System::block_number() as u64 * SLOT_DURATION) + INIT_TIMESTAMP);
 	}
 }
@@ -388,7 +444,7 @@ impl ExtBuilder {
 		)
 		.unwrap();
 		GenesisBuild::<Test>::assimilate_storage(
-			&staking_pool::GenesisConfig {},
+			&staking_pool::GenesisConfig::default(),
 			&mut storage,
 		)
 		.unwrap();
@@ -4,7 +4,7 @@ use gafi_primitives::constant::ID;
 use gafi_primitives::ticket::PlayerTicket;
 use gafi_primitives::{
     currency::{unit, NativeToken::GAKI},
-    ticket::{CustomTicket, TicketType},
+    ticket::{CustomTicket, TicketTarget, TicketType},
 };
 use sp_core::H160;
 use sp_runtime::{AccountId32, Permill};
@@ -34,7 +34,9 @@ fn create_pool(
         targets,
         pool_value,
         discount,
-        tx_limit
+        tx_limit,
+        None,
+        None
     ));
     assert_eq!(
         Balances::free_balance(&account),
@@ -82,8 +84,8 @@ fn rejoin_sponsored_pool_works() {
             TicketType::Custom(CustomTicket::Sponsored(pool_id))
         ));
 
-        Pool::use_ticket(account_1.clone(), Some(H160::default()));
-        Pool::use_ticket(account_1.clone(), Some(H160::default()));
+        Pool::use_ticket(account_1.clone(), Some(TicketTarget::Contract(H160::default())));
+        Pool::use_ticket(account_1.clone(), Some(TicketTarget::Contract(H160::default())));
         assert_eq!(Pool::tickets(account_1.clone(), pool_id).unwrap().tickets, 98_u32);
 
         run_to_block(10);
@@ -132,3 +134,38 @@ fn limit_join_sponsored_pool_works() {
 		), pallet_pool::Error::<Test>::ExceedJoinedPool);
     })
 }
+
+#[test]
+fn expired_sponsored_pool_removes_tickets_works() {
+    ExtBuilder::default().build_and_execute(|| {
+        run_to_block(ADD_BLOCK);
+        let account_balance = 1_000_000 * unit(GAKI);
+        let account = new_account([0_u8; 32], account_balance);
+        let end_block = ADD_BLOCK + 5;
+        assert_ok!(SponsoredPool::create_pool(
+            Origin::signed(account.clone()),
+            vec![H160::default()],
+            1000 * unit(GAKI),
+            Permill::from_percent(30),
+            100_u32,
+            Some(end_block),
+            None
+        ));
+        let pool_id: ID = *SponsoredPool::pool_owned(&account).last().unwrap();
+
+        let account_1 = new_account([1_u8; 32], account_balance);
+        assert_ok!(Pool::join(
+            Origin::signed(account_1.clone()),
+            TicketType::Custom(CustomTicket::Sponsored(pool_id))
+        ));
+        assert!(Pool::tickets(account_1.clone(), pool_id).is_some());
+
+        run_to_block(end_block);
+        assert_eq!(Pool::tickets(account_1.clone(), pool_id), None);
+        assert_eq!(Balances::free_balance(&account), account_balance);
+        assert_eq!(
+            Pool::use_ticket(account_1, Some(TicketTarget::Contract(H160::default()))),
+            None
+        );
+    })
+}
@@ -49,6 +49,10 @@ fn leave_pool(account: AccountId32, ticket: TicketType) {
 	let staking_amount = StakingPool::get_service(pool_id).unwrap().value;
 
 	assert_ok!(Pool::leave(Origin::signed(account.clone()), pool_id));
+	assert_eq!(<Test as Config>::Currency::free_balance(account.clone()), before_balance);
+
+	run_to_block(System::block_number() + UnbondingPeriod::get());
+	assert_ok!(StakingPool::withdraw_unbonded(Origin::signed(account.clone())));
 	assert_eq!(
 		<Test as Config>::Currency::free_balance(account.clone()),
 		before_balance + staking_amount
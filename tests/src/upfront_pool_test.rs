@@ -4,7 +4,7 @@ use frame_support::{assert_ok, traits::Currency};
 use gafi_primitives::system_services::SystemPool;
 use gafi_primitives::{
 	currency::{unit, NativeToken::GAKI},
-	ticket::{TicketLevel, TicketType, SystemTicket, CustomTicket},
+	ticket::{PlayerTicket, TicketLevel, TicketType, SystemTicket, CustomTicket},
 };
 use gafi_tx::Config;
 use rand::prelude::*;
@@ -160,3 +160,63 @@ fn leave_pool_over_works() {
 		}
 	}
 }
+
+#[test]
+fn charge_after_time_service_changed_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let sender = AccountId32::new([0_u8; 32]);
+		let ticket = TicketType::System(SystemTicket::Upfront(TicketLevel::Basic));
+		let pool_id = SystemTicket::Upfront(TicketLevel::Basic).using_encoded(blake2_256);
+		let pool_fee = UpfrontPool::get_service(pool_id).unwrap().value;
+		let _ = <Test as Config>::Currency::deposit_creating(&sender, 1_000_000 * unit(GAKI));
+
+		run_to_block(1);
+		let before_balance = <Test as Config>::Currency::free_balance(sender.clone());
+		assert_ok!(Pool::join(Origin::signed(sender.clone()), ticket));
+		assert_ok!(Pool::set_time_service(Origin::root(), TIME_SERVICE * 2));
+
+		// the current period is charged with the old TimeService
+		run_to_block(CIRCLE_BLOCK + ADDITIONAL_BLOCK);
+		assert_eq!(Pool::time_service(), TIME_SERVICE * 2);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(sender.clone()),
+			before_balance - (pool_fee * 2)
+		);
+
+		run_to_block(CIRCLE_BLOCK * 3);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(sender.clone()),
+			before_balance - (pool_fee * 2)
+		);
+
+		run_to_block(CIRCLE_BLOCK * 3 + ADDITIONAL_BLOCK);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(sender.clone()),
+			before_balance - (pool_fee * 3)
+		);
+	})
+}
+
+#[test]
+fn suspend_ticket_when_payment_failed_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let sender = AccountId32::new([0_u8; 32]);
+		let ticket = TicketType::System(SystemTicket::Upfront(TicketLevel::Basic));
+		let pool_id = SystemTicket::Upfront(TicketLevel::Basic).using_encoded(blake2_256);
+		let pool_fee = UpfrontPool::get_service(pool_id).unwrap().value;
+		let _ = <Test as Config>::Currency::deposit_creating(&sender, pool_fee * 2 + pool_fee / 2);
+
+		run_to_block(1);
+		assert_ok!(Pool::join(Origin::signed(sender.clone()), ticket));
+		assert_ne!(Pool::use_ticket(sender.clone(), None), None);
+
+		// sender can't pay the next period, the ticket is suspended
+		run_to_block(CIRCLE_BLOCK + ADDITIONAL_BLOCK);
+		assert!(UpfrontPool::delinquents(sender.clone()).is_some());
+		assert_eq!(Pool::use_ticket(sender.clone(), None), None);
+
+		let _ = <Test as Config>::Currency::deposit_creating(&sender, pool_fee);
+		assert_ok!(UpfrontPool::pay_arrears(Origin::signed(sender.clone())));
+		assert_ne!(Pool::use_ticket(sender.clone(), None), None);
+	})
+}
@@ -113,7 +113,14 @@ async function leave_pool(context, sub_account, pool_id) {
 async function create_pool(context, sub_account, arguments) {
     const api = await ApiPromise.create({ provider: context.wsProvider });
 
-    const txExecute = api.tx.sponsoredPool.createPool(arguments.targets, arguments.value, arguments.discount, arguments.txLimit);
+    const txExecute = api.tx.sponsoredPool.createPool(
+        arguments.targets,
+        arguments.value,
+        arguments.discount,
+        arguments.txLimit,
+        arguments.endBlock || null,
+        arguments.salt || null,
+    );
     const unsub = await txExecute
         .signAndSend(sub_account);
     await createAndFinalizeBlock(context.web3);
@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::traits::{ExistenceRequirement, Currency};
-use sp_runtime::DispatchResult;
+use frame_support::traits::{ExistenceRequirement, Currency, NamedReservableCurrency};
+use sp_runtime::{traits::Zero, DispatchResult, Saturating};
 use frame_support::traits::fungible::{Inspect};
 
 /// Transfer all tokens from sender to beneficiary with the option to keep the sender alive or not
@@ -34,3 +34,27 @@ where
         existence,
     )
 }
+
+/// Move the balance reserved by the account without a name to the named reserve, return
+/// the amount moved
+///
+/// Parameters:
+/// - `id`: the identifier of the named reserve
+/// - `who`: the account
+/// - `amount`: the amount to move
+pub fn move_to_named_reserve<AccountId, C>(
+    id: &C::ReserveIdentifier,
+    who: &AccountId,
+    amount: C::Balance,
+) -> C::Balance
+where
+    C: NamedReservableCurrency<AccountId>,
+{
+    let moved = amount.saturating_sub(C::unreserve(who, amount));
+    if C::reserve_named(id, who, moved).is_err() {
+        // keep the balance reserved without a name when the account has too many reserves
+        let _ = C::reserve(who, moved);
+        return Zero::zero();
+    }
+    moved
+}
@@ -1,7 +1,8 @@
 use crate::*;
 use crate::mock::*;
 use sp_runtime::AccountId32;
-use gu_currency::transfer_all;
+use frame_support::{assert_ok, traits::{NamedReservableCurrency, ReservableCurrency}};
+use gu_currency::{move_to_named_reserve, transfer_all};
 
 fn make_deposit(account: &AccountId32, balance: u128) {
     let _ = pallet_balances::Pallet::<Test>::deposit_creating(account, balance);
@@ -50,3 +51,22 @@ fn transfer_all_allow_death_works() {
 		}
     })
 }
+
+#[test]
+fn move_to_named_reserve_works() {
+    new_test_ext().execute_with(|| {
+		const BALANCE: u128 = 1_000_000_000;
+		const RESERVE_ID: [u8; 8] = *b"gafi/tst";
+
+        let account = new_account([0_u8; 32], BALANCE);
+		assert_ok!(Balances::reserve(&account, 1_000));
+
+		// only the reserved balance is moved
+		assert_eq!(move_to_named_reserve::<AccountId32, Balances>(&RESERVE_ID, &account, 2_000), 1_000);
+		{
+			assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &account), 1_000);
+			assert_eq!(Balances::reserved_balance(&account), 1_000);
+			assert_eq!(Balances::free_balance(&account), BALANCE - 1_000);
+		}
+    })
+}